@@ -53,6 +53,10 @@ pub struct TargetCursor {
     pub index: usize,
 }
 
+/// 行動順予測テキストのマーカー
+#[derive(Component)]
+pub struct TurnOrderText;
+
 /// HP割合に応じた色を返す（>50%=緑, >25%=黄, それ以下=赤）
 fn hp_bar_color(ratio: f32) -> Color {
     if ratio > 0.5 {
@@ -120,7 +124,7 @@ pub fn battle_status_display_system(
     for (hp_text, mut text) in &mut party_hp_query {
         if hp_text.index < game_state.state.party.len() {
             let display_hp = ui_state.display_party_hp.get(hp_text.index).copied().unwrap_or(0);
-            let max_hp = game_state.state.party[hp_text.index].stats.max_hp;
+            let max_hp = game_state.state.party[hp_text.index].effective_max_hp();
             **text = format!("HP:{}/{}", display_hp, max_hp);
         }
     }
@@ -129,20 +133,20 @@ pub fn battle_status_display_system(
     for (mp_text, mut text) in &mut party_mp_query {
         if mp_text.index < game_state.state.party.len() {
             let display_mp = ui_state.display_party_mp.get(mp_text.index).copied().unwrap_or(0);
-            let max_mp = game_state.state.party[mp_text.index].stats.max_mp;
+            let max_mp = game_state.state.party[mp_text.index].effective_max_mp();
             **text = format!("MP:{}/{}", display_mp, max_mp);
         }
     }
 
     // パーティ名前+レベル更新＆ハイライト（味方ターゲット選択時）
     let is_ally_target_select = matches!(ui_state.phase, BattlePhase::AllyTargetSelect { .. });
-    let alive_party = game_state.state.alive_party_indices();
+    let ally_candidates = ui_state.ally_target_candidates(&game_state.state);
     for (name_text, mut text, mut color) in &mut party_name_query {
         if name_text.index < game_state.state.party.len() {
             let member = &game_state.state.party[name_text.index];
             **text = format!("{} Lv.{}", member.kind.name(), member.level);
         }
-        if is_ally_target_select && alive_party.get(ui_state.ally_target_offset) == Some(&name_text.index) {
+        if is_ally_target_select && ally_candidates.get(ui_state.ally_target_offset) == Some(&name_text.index) {
             *color = TextColor(ALLY_TARGET_HIGHLIGHT);
         } else {
             *color = TextColor(Color::WHITE);
@@ -153,7 +157,7 @@ pub fn battle_status_display_system(
     for (bar, mut node, mut bg) in &mut party_bar_query {
         if bar.index < game_state.state.party.len() {
             let display_hp = ui_state.display_party_hp.get(bar.index).copied().unwrap_or(0);
-            let max_hp = game_state.state.party[bar.index].stats.max_hp;
+            let max_hp = game_state.state.party[bar.index].effective_max_hp();
             let ratio = display_hp as f32 / max_hp as f32;
             node.width = Val::Percent(ratio * 100.0);
             *bg = BackgroundColor(hp_bar_color(ratio));
@@ -221,6 +225,48 @@ pub fn battle_status_display_system(
     }
 }
 
+/// コマンド選択中に行動順の予測を表示するシステム
+pub fn battle_turn_order_system(
+    game_state: Res<BattleGameState>,
+    ui_state: Res<BattleUIState>,
+    mut query: Query<(&mut Text, &mut Visibility), With<TurnOrderText>>,
+) {
+    let preview = ui_state.turn_order_preview(&game_state);
+    for (mut text, mut vis) in &mut query {
+        match &preview {
+            Some(names) => {
+                **text = format!("こうどうじゅん: {}", names.join(" → "));
+                *vis = Visibility::Inherited;
+            }
+            None => *vis = Visibility::Hidden,
+        }
+    }
+}
+
+/// 敵スプライトの画像を敵の種類に合わせるシステム（戦闘中に呼び出された敵の表示用）
+pub fn battle_enemy_sprite_system(
+    game_state: Res<BattleGameState>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(&EnemySprite, &mut ImageNode)>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+    for (sprite, mut image) in &mut query {
+        let Some(enemy) = game_state.state.enemies.get(sprite.index) else {
+            continue;
+        };
+        let path = enemy.kind.sprite_path();
+        let loaded = image
+            .image
+            .path()
+            .is_some_and(|p| p.path() == std::path::Path::new(path));
+        if !loaded {
+            image.image = asset_server.load(path);
+        }
+    }
+}
+
 /// 画面シェイクシステム: BattleSceneRootのStyleにleftオフセットを適用
 pub fn battle_shake_system(
     time: Res<Time>,
@@ -243,11 +289,11 @@ pub fn battle_shake_system(
         return;
     }
 
-    // sin波(2往復) × 減衰(1→0)、振幅3px、X方向のみ
+    // sin波(2往復) × 減衰(1→0)、X方向のみ
     let progress = timer.fraction();
     let decay = 1.0 - progress;
     let wave = (progress * 2.0 * std::f32::consts::TAU).sin();
-    node.left = Val::Px(wave * 3.0 * decay);
+    node.left = Val::Px(wave * ui_state.shake_amplitude * decay);
 }
 
 /// 敵スプライト点滅システム: ダメージを受けた敵を高速で明滅させる
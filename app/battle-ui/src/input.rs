@@ -1,11 +1,13 @@
 use bevy::prelude::*;
+use rand::Rng;
 
-use battle::{ActorId, BattleAction, TargetId, TurnRandomFactors, TurnResult};
-use spell::{SpellEffect, SpellEntry, SpellTarget};
+use battle::{ActorId, BattleAction, BattleRecord, BattleRewards, RewardRandomFactors, SupportSource, TargetId, TurnRandomFactors, TurnResult};
+use spell::{Affinity, SpellEffect, SpellEntry, SpellTarget};
 use item::ItemEffect;
 
-use scene_state::BattleState;
-use party_state::CharacterParams;
+use scene_state::{BattleState, SceneState};
+use party_state::{CharacterParams, PartyState};
+use party::{CharacterParamTable, PartyMemberKind};
 
 use super::scene::{
     enemy_display_names, BattleGameState, BattlePhase, BattleUIState, MessageEffect,
@@ -17,29 +19,31 @@ pub fn battle_input_system(
     mut game_state: ResMut<BattleGameState>,
     mut ui_state: ResMut<BattleUIState>,
     mut next_state: ResMut<NextState<BattleState>>,
+    mut next_scene: ResMut<NextState<SceneState>>,
     char_params: Res<CharacterParams>,
+    mut party_state: ResMut<PartyState>,
 ) {
     match ui_state.phase.clone() {
         BattlePhase::CommandSelect { member_index } => {
-            handle_command_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params);
+            handle_command_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params, &mut party_state);
         }
         BattlePhase::SpellSelect { member_index } => {
-            handle_spell_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params);
+            handle_spell_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params, &mut party_state);
         }
         BattlePhase::ItemSelect { member_index } => {
-            handle_item_select(&keyboard, &game_state, &mut ui_state, member_index);
+            handle_item_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params, &mut party_state);
         }
         BattlePhase::TargetSelect { member_index } => {
-            handle_target_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params);
+            handle_target_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params, &mut party_state);
         }
         BattlePhase::AllyTargetSelect { member_index } => {
-            handle_ally_target_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params);
+            handle_ally_target_select(&keyboard, &mut game_state, &mut ui_state, member_index, &char_params, &mut party_state);
         }
         BattlePhase::ShowMessage { messages, index } => {
             handle_show_message(&keyboard, &game_state, &mut ui_state, index, messages.len());
         }
         BattlePhase::BattleOver { .. } => {
-            handle_battle_over(&keyboard, &mut next_state);
+            handle_battle_over(&keyboard, &game_state, &mut next_state, &mut next_scene);
         }
     }
 }
@@ -50,13 +54,14 @@ fn handle_command_select(
     ui_state: &mut BattleUIState,
     member_index: usize,
     char_params: &CharacterParams,
+    party_state: &mut PartyState,
 ) {
-    // 上下でカーソル移動 (0=たたかう, 1=じゅもん, 2=どうぐ, 3=にげる)
+    // 上下でカーソル移動 (0=たたかう, 1=じゅもん, 2=どうぐ, 3=ぼうぎょ, 4=にげる)
     if input_ui::is_up_just_pressed(keyboard) {
-        ui_state.selected_command = if ui_state.selected_command > 0 { ui_state.selected_command - 1 } else { 3 };
+        ui_state.selected_command = if ui_state.selected_command > 0 { ui_state.selected_command - 1 } else { 4 };
     }
     if input_ui::is_down_just_pressed(keyboard) {
-        ui_state.selected_command = if ui_state.selected_command < 3 { ui_state.selected_command + 1 } else { 0 };
+        ui_state.selected_command = if ui_state.selected_command < 4 { ui_state.selected_command + 1 } else { 0 };
     }
 
     // キャンセル: 前のメンバーに戻る
@@ -99,13 +104,21 @@ fn handle_command_select(
                 ui_state.selected_item = 0;
                 ui_state.phase = BattlePhase::ItemSelect { member_index };
             }
+            3 => {
+                // ぼうぎょ → ターゲット不要なので即登録
+                ui_state.pending_commands.set(member_index, BattleAction::Defend);
+                advance_to_next_member(game_state, ui_state, member_index, char_params, party_state);
+            }
             _ => {
-                // にげる → 全員Flee確定、即実行
+                // にげる → ボス戦では選べない。それ以外は全員Flee確定、即実行
+                if !game_state.state.can_flee {
+                    return;
+                }
                 ui_state.pending_commands.clear();
                 for i in 0..game_state.state.party.len() {
                     ui_state.pending_commands.set(i, BattleAction::Flee);
                 }
-                execute_turn(game_state, ui_state, char_params);
+                execute_turn(game_state, ui_state, char_params, party_state);
             }
         }
         ui_state.selected_command = 0;
@@ -118,6 +131,7 @@ fn handle_spell_select(
     ui_state: &mut BattleUIState,
     member_index: usize,
     char_params: &CharacterParams,
+    party_state: &mut PartyState,
 ) {
     let member_kind = game_state.state.party[member_index].kind;
     let spells = party::available_spells(member_kind, game_state.state.party[member_index].level, char_params);
@@ -147,6 +161,13 @@ fn handle_spell_select(
             return; // MP不足なら何もしない
         }
 
+        // 蘇生対象がいなければ選べない
+        if spell.target_type == SpellTarget::SingleDeadAlly
+            && game_state.state.dead_party_indices().is_empty()
+        {
+            return;
+        }
+
         ui_state.pending_spell = Some(spell);
 
         match spell.target_type {
@@ -165,9 +186,9 @@ fn handle_spell_select(
                         target: TargetId::Enemy(0),
                     },
                 );
-                advance_to_next_member(game_state, ui_state, member_index, char_params);
+                advance_to_next_member(game_state, ui_state, member_index, char_params, party_state);
             }
-            SpellTarget::SingleAlly => {
+            SpellTarget::SingleAlly | SpellTarget::SingleDeadAlly => {
                 // 単体味方 → 味方選択へ
                 ui_state.ally_target_offset = 0;
                 ui_state.phase = BattlePhase::AllyTargetSelect { member_index };
@@ -182,7 +203,7 @@ fn handle_spell_select(
                         target: TargetId::Party(0),
                     },
                 );
-                advance_to_next_member(game_state, ui_state, member_index, char_params);
+                advance_to_next_member(game_state, ui_state, member_index, char_params, party_state);
             }
         }
     }
@@ -194,6 +215,7 @@ fn advance_to_next_member(
     ui_state: &mut BattleUIState,
     current_member: usize,
     char_params: &CharacterParams,
+    party_state: &mut PartyState,
 ) {
     let next = find_next_alive_member(game_state, current_member);
     if let Some(next_idx) = next {
@@ -203,15 +225,17 @@ fn advance_to_next_member(
         };
     } else {
         // 全員入力完了 → ターン実行
-        execute_turn(game_state, ui_state, char_params);
+        execute_turn(game_state, ui_state, char_params, party_state);
     }
 }
 
 fn handle_item_select(
     keyboard: &ButtonInput<KeyCode>,
-    game_state: &BattleGameState,
+    game_state: &mut BattleGameState,
     ui_state: &mut BattleUIState,
     member_index: usize,
+    char_params: &CharacterParams,
+    party_state: &mut PartyState,
 ) {
     let owned = game_state.state.party[member_index].inventory.owned_items();
     if owned.is_empty() {
@@ -239,13 +263,33 @@ fn handle_item_select(
         let item = owned[ui_state.selected_item];
 
         // キーアイテム・素材・装備は戦闘中使用不可
-        if matches!(item.entry().effect, ItemEffect::KeyItem | ItemEffect::Material) {
+        if matches!(item.entry().effect, ItemEffect::KeyItem | ItemEffect::Material | ItemEffect::Equipment) {
+            return;
+        }
+
+        // 蘇生アイテムは戦闘不能の味方がいるときだけ使える
+        if matches!(item.entry().effect, ItemEffect::Revive { .. })
+            && game_state.state.dead_party_indices().is_empty()
+        {
+            return;
+        }
+
+        // 逃走アイテムは対象選択なしで登録（ボス戦では使えない）
+        if item.entry().effect == ItemEffect::Escape {
+            if !game_state.state.can_flee {
+                return;
+            }
+            ui_state.pending_commands.set(
+                member_index,
+                BattleAction::UseItem { item, target: TargetId::Party(member_index) },
+            );
+            advance_to_next_member(game_state, ui_state, member_index, char_params, party_state);
             return;
         }
 
         ui_state.pending_item = Some(item);
 
-        // 回復アイテム → 味方選択へ
+        // 回復・蘇生アイテム → 味方選択へ
         ui_state.ally_target_offset = 0;
         ui_state.phase = BattlePhase::AllyTargetSelect { member_index };
     }
@@ -257,6 +301,7 @@ fn handle_target_select(
     ui_state: &mut BattleUIState,
     member_index: usize,
     char_params: &CharacterParams,
+    party_state: &mut PartyState,
 ) {
     let alive_enemies = game_state.state.alive_enemy_indices();
     if alive_enemies.is_empty() {
@@ -315,7 +360,7 @@ fn handle_target_select(
             };
         } else {
             // 全員入力完了 → ターン実行
-            execute_turn(game_state, ui_state, char_params);
+            execute_turn(game_state, ui_state, char_params, party_state);
         }
     }
 }
@@ -326,9 +371,10 @@ fn handle_ally_target_select(
     ui_state: &mut BattleUIState,
     member_index: usize,
     char_params: &CharacterParams,
+    party_state: &mut PartyState,
 ) {
-    let alive_party = game_state.state.alive_party_indices();
-    if alive_party.is_empty() {
+    let candidates = ui_state.ally_target_candidates(&game_state.state);
+    if candidates.is_empty() {
         return;
     }
 
@@ -337,11 +383,11 @@ fn handle_ally_target_select(
         ui_state.ally_target_offset = if ui_state.ally_target_offset > 0 {
             ui_state.ally_target_offset - 1
         } else {
-            alive_party.len() - 1
+            candidates.len() - 1
         };
     }
     if input_ui::is_right_just_pressed(keyboard) {
-        ui_state.ally_target_offset = if ui_state.ally_target_offset < alive_party.len() - 1 {
+        ui_state.ally_target_offset = if ui_state.ally_target_offset < candidates.len() - 1 {
             ui_state.ally_target_offset + 1
         } else {
             0
@@ -362,7 +408,7 @@ fn handle_ally_target_select(
 
     // 決定
     if input_ui::is_confirm_just_pressed(keyboard) {
-        let target = TargetId::Party(alive_party[ui_state.ally_target_offset]);
+        let target = TargetId::Party(candidates[ui_state.ally_target_offset]);
 
         if let Some(spell) = ui_state.pending_spell.take() {
             ui_state
@@ -383,7 +429,7 @@ fn handle_ally_target_select(
             };
         } else {
             // 全員入力完了 → ターン実行
-            execute_turn(game_state, ui_state, char_params);
+            execute_turn(game_state, ui_state, char_params, party_state);
         }
     }
 }
@@ -401,69 +447,119 @@ fn find_prev_alive_member(game_state: &BattleGameState, current: usize) -> Optio
 }
 
 /// ターンを実行してメッセージフェーズに遷移
-fn execute_turn(game_state: &mut BattleGameState, ui_state: &mut BattleUIState, char_params: &CharacterParams) {
+fn execute_turn(
+    game_state: &mut BattleGameState,
+    ui_state: &mut BattleUIState,
+    char_params: &CharacterParams,
+    party_state: &mut PartyState,
+) {
     let party_count = game_state.state.party.len();
     let enemy_count = game_state.state.enemies.len();
     let total_actors = party_count + enemy_count;
 
+    let rng = &mut game_state.rng;
     let damage_randoms: Vec<f32> = (0..total_actors)
-        .map(|_| 0.8 + rand::random::<f32>() * 0.4)
+        .map(|_| rng.gen_range(0.8..1.2))
         .collect();
-    let flee_random: f32 = rand::random();
+    let flee_random: f32 = rng.gen_range(0.0..1.0);
 
     let spell_randoms: Vec<f32> = (0..enemy_count)
-        .map(|_| rand::random::<f32>())
+        .map(|_| rng.gen_range(0.0..1.0))
+        .collect();
+
+    let target_randoms: Vec<f32> = (0..enemy_count)
+        .map(|_| rng.gen_range(0.0..1.0))
+        .collect();
+
+    let hit_randoms: Vec<f32> = (0..total_actors)
+        .map(|_| rng.gen_range(0.0..1.0))
+        .collect();
+
+    let critical_randoms: Vec<f32> = (0..total_actors)
+        .map(|_| rng.gen_range(0.0..1.0))
+        .collect();
+
+    let confusion_randoms: Vec<f32> = (0..total_actors)
+        .map(|_| rng.gen_range(0.0..1.0))
+        .collect();
+
+    let recovery_randoms: Vec<f32> = (0..total_actors)
+        .map(|_| rng.gen_range(0.0..1.0))
         .collect();
 
     let random_factors = TurnRandomFactors {
         damage_randoms,
         flee_random,
         spell_randoms,
+        target_randoms,
+        hit_randoms,
+        critical_randoms,
+        confusion_randoms,
+        recovery_randoms,
+        // 行動順の予測に使った揺らぎをそのまま使う
+        speed_randoms: std::mem::take(&mut game_state.speed_randoms),
     };
 
     // ターン実行前のパーティHP/MP状態をスナップショット
     let pre_party_hp: Vec<i32> = game_state.state.party.iter().map(|m| m.stats.hp).collect();
     let pre_party_mp: Vec<i32> = game_state.state.party.iter().map(|m| m.stats.mp).collect();
 
-    let results = game_state
-        .state
-        .execute_turn(&ui_state.pending_commands.to_commands(), &random_factors);
+    let commands = ui_state.pending_commands.to_commands();
+    let record = game_state
+        .record
+        .get_or_insert_with(|| BattleRecord::start(&game_state.state));
+    let results = game_state.state.execute_turn(&commands, &random_factors);
+    record.push_turn(&commands, &random_factors, &results);
+    game_state.roll_speed_randoms();
 
     ui_state.pending_commands.clear();
 
+    // 戦闘中に呼び出された敵は、登場メッセージで表示するまで隠しておく
+    let enemy_count = game_state.state.enemies.len();
+    ui_state.hidden_enemies.resize(enemy_count, true);
+
     let (mut messages, effects) =
         results_to_messages(&results, &game_state.state, &pre_party_hp, &pre_party_mp);
     ui_state.message_effects = effects;
 
-    // 勝利時: 経験値獲得メッセージとレベルアップ処理を追加
+    // 勝利時: 報酬の獲得メッセージとレベルアップ処理を追加
     if game_state.state.is_victory() {
-        let total_exp = game_state.state.total_exp_reward();
+        let reward_randoms = RewardRandomFactors {
+            gold_randoms: (0..enemy_count).map(|_| game_state.rng.gen_range(0.0..1.0)).collect(),
+            drop_randoms: (0..enemy_count).map(|_| game_state.rng.gen_range(0.0..1.0)).collect(),
+        };
+        let rewards = game_state.state.calculate_rewards(&reward_randoms);
+        let total_exp = rewards.exp;
         messages.push(format!("けいけんち {}ポイント かくとく！", total_exp));
+        messages.extend(apply_battle_rewards(&rewards, party_state));
 
         // 生存メンバーに経験値を付与してレベルアップ判定
         let alive = game_state.state.alive_party_indices();
         for &i in &alive {
             let member = &mut game_state.state.party[i];
             let old_level = member.level;
-            let level_ups = member.gain_exp(total_exp, char_params);
-            if level_ups > 0 {
-                messages.push(format!(
-                    "{}は レベル{}に あがった！",
-                    member.kind.name(),
-                    member.level
-                ));
-                // レベルアップで新しく習得した呪文をチェック
-                for lvl in (old_level + 1)..=member.level {
-                    for spell in party::spells_learned_at_level(member.kind, lvl, char_params) {
-                        messages.push(format!(
-                            "{}は {}を おぼえた！",
-                            member.kind.name(),
-                            spell.name
-                        ));
-                    }
-                }
+            if member.gain_exp(total_exp, char_params) > 0 {
+                messages.extend(level_up_messages(member.kind, old_level, member.level, char_params));
             }
         }
+
+        // 生存しているひかえのメンバーにも経験値の一部を分配
+        let (share, reserve_level_ups) = party_state.grant_reserve_exp(total_exp, char_params);
+        if share > 0 {
+            messages.push(format!("ひかえの なかまは {}ポイント かくとく", share));
+        }
+        for level_up in reserve_level_ups {
+            messages.extend(level_up_messages(
+                level_up.kind,
+                level_up.old_level,
+                level_up.new_level,
+                char_params,
+            ));
+        }
+    }
+
+    if game_state.state.is_party_wiped() {
+        messages.push("パーティは ぜんめつした…".to_string());
     }
 
     if messages.is_empty() {
@@ -503,6 +599,41 @@ fn execute_turn(game_state: &mut BattleGameState, ui_state: &mut BattleUIState,
     }
 }
 
+/// レベルアップと、上がったレベルで新しく習得した呪文のメッセージを作る
+fn level_up_messages(
+    kind: PartyMemberKind,
+    old_level: u32,
+    new_level: u32,
+    char_params: &CharacterParamTable,
+) -> Vec<String> {
+    let mut messages = vec![format!("{}は レベル{}に あがった！", kind.name(), new_level)];
+    for lvl in (old_level + 1)..=new_level {
+        for spell in party::spells_learned_at_level(kind, lvl, char_params) {
+            messages.push(format!("{}は {}を おぼえた！", kind.name(), spell.name));
+        }
+    }
+    messages
+}
+
+/// 戦闘報酬のゴールドとドロップアイテムをパーティに反映し、獲得メッセージを返す
+///
+/// ふくろに入りきらないアイテムは捨てて、その旨のメッセージを出す。
+fn apply_battle_rewards(rewards: &BattleRewards, party_state: &mut PartyState) -> Vec<String> {
+    let mut messages = Vec::new();
+    if rewards.gold > 0 {
+        party_state.gold += rewards.gold;
+        messages.push(format!("{}ゴールドを てにいれた！", rewards.gold));
+    }
+    for &item in &rewards.items {
+        if party_state.bag.try_add(item, 1) {
+            messages.push(format!("{}を てにいれた！", item.name()));
+        } else {
+            messages.push(format!("ふくろが いっぱいで {}を もちきれない…", item.name()));
+        }
+    }
+    messages
+}
+
 /// TurnResult列をメッセージ文字列列とMessageEffect列に変換
 fn results_to_messages(
     results: &[TurnResult],
@@ -676,7 +807,7 @@ fn results_to_messages(
 
                 // ターゲットのHP更新エフェクト
                 if let TargetId::Party(pi) = target {
-                    let max_hp = state.party[*pi].stats.max_hp;
+                    let max_hp = state.party[*pi].effective_max_hp();
                     running_party_hp[*pi] = (running_party_hp[*pi] + amount).min(max_hp);
                     effects.push((
                         msg_index,
@@ -715,20 +846,23 @@ fn results_to_messages(
                             amount
                         ));
                     }
-                } else if is_continuation {
-                    messages.push(format!(
-                        "{}の こうげきりょくが {}あがった！",
-                        target_name, amount
-                    ));
                 } else {
-                    let caster_name = actor_name(caster, state, &enemy_names);
-                    messages.push(format!(
-                        "{}は {}を となえた！ {}の こうげきりょくが {}あがった！",
-                        caster_name,
-                        spell.name,
-                        target_name,
-                        amount
-                    ));
+                    let change = stat_change_text(spell.effect, *amount);
+                    if is_continuation {
+                        messages.push(format!("{}の {}", target_name, change));
+                    } else {
+                        let caster_name = actor_name(caster, state, &enemy_names);
+                        messages.push(format!(
+                            "{}は {}を となえた！ {}の {}",
+                            caster_name, spell.name, target_name, change
+                        ));
+                    }
+                    if let TargetId::Enemy(ei) = target {
+                        effects.push((
+                            msg_index,
+                            MessageEffect::BlinkEnemy { enemy_index: *ei },
+                        ));
+                    }
                 }
                 last_aoe_caster_spell = Some((*caster, *spell));
 
@@ -747,12 +881,13 @@ fn results_to_messages(
                     ));
                 }
             }
-            TurnResult::BuffExpired { target, .. } => {
+            TurnResult::BuffExpired { target, stat } => {
                 last_aoe_caster_spell = None;
                 let target_name = target_name_str(target, state, &enemy_names);
                 messages.push(format!(
-                    "{}の こうげきりょくアップの こうかが きれた！",
-                    target_name
+                    "{}の {}の こうかが きれた！",
+                    target_name,
+                    stat.label()
                 ));
             }
             TurnResult::BlockDecayed { target, remaining } => {
@@ -789,7 +924,7 @@ fn results_to_messages(
                 ));
 
                 if let TargetId::Party(pi) = target {
-                    let max_hp = state.party[*pi].stats.max_hp;
+                    let max_hp = state.party[*pi].effective_max_hp();
                     running_party_hp[*pi] = (running_party_hp[*pi] + amount).min(max_hp);
                     effects.push((
                         msg_index,
@@ -800,6 +935,54 @@ fn results_to_messages(
                     ));
                 }
             }
+            TurnResult::Revived {
+                user,
+                source,
+                target,
+                hp,
+            } => {
+                last_aoe_caster_spell = None;
+                let user_name = actor_name(user, state, &enemy_names);
+                let target_name = target_name_str(target, state, &enemy_names);
+                let msg_index = messages.len();
+                match source {
+                    SupportSource::Spell(spell) => {
+                        messages.push(format!(
+                            "{}は {}を となえた！ {}が いきかえった！",
+                            user_name, spell.name, target_name
+                        ));
+                        if let ActorId::Party(ci) = user {
+                            running_party_mp[*ci] = (running_party_mp[*ci] - spell.mp_cost).max(0);
+                            effects.push((
+                                msg_index,
+                                MessageEffect::UpdatePartyMp {
+                                    member_index: *ci,
+                                    new_mp: running_party_mp[*ci],
+                                },
+                            ));
+                        }
+                    }
+                    SupportSource::Item(item) => {
+                        messages.push(format!(
+                            "{}は {}を つかった！ {}が いきかえった！",
+                            user_name,
+                            item.name(),
+                            target_name
+                        ));
+                    }
+                }
+
+                if let TargetId::Party(pi) = target {
+                    running_party_hp[*pi] = *hp;
+                    effects.push((
+                        msg_index,
+                        MessageEffect::UpdatePartyHp {
+                            member_index: *pi,
+                            new_hp: *hp,
+                        },
+                    ));
+                }
+            }
             TurnResult::MpDrained {
                 caster,
                 spell,
@@ -981,6 +1164,112 @@ fn results_to_messages(
                 let name = actor_name(actor, state, &enemy_names);
                 messages.push(format!("{}は ねむっている…", name));
             }
+            TurnResult::Paralyzed { actor } => {
+                last_aoe_caster_spell = None;
+                let name = actor_name(actor, state, &enemy_names);
+                messages.push(format!("{}は からだが しびれて うごけない！", name));
+            }
+            TurnResult::Silenced { actor, spell } => {
+                last_aoe_caster_spell = None;
+                let name = actor_name(actor, state, &enemy_names);
+                messages.push(format!(
+                    "{}は {}を となえようとした！ しかし じゅもんを ふうじられている！",
+                    name, spell.name
+                ));
+            }
+            TurnResult::Confused { actor } => {
+                last_aoe_caster_spell = None;
+                let name = actor_name(actor, state, &enemy_names);
+                messages.push(format!("{}は こんらんしている！", name));
+            }
+            TurnResult::Cured {
+                user,
+                source,
+                target,
+                ailments,
+            } => {
+                let user_name = actor_name(user, state, &enemy_names);
+                let target_name = target_name_str(target, state, &enemy_names);
+                let cured = if ailments.is_empty() {
+                    None
+                } else {
+                    let names: Vec<&str> = ailments.iter().map(|a| a.name()).collect();
+                    Some(format!("{}の {}が なおった！", target_name, names.join("と ")))
+                };
+                let msg_index = messages.len();
+                match source {
+                    SupportSource::Spell(spell) => {
+                        let is_continuation = last_aoe_caster_spell == Some((*user, *spell));
+                        last_aoe_caster_spell = Some((*user, *spell));
+                        if is_continuation {
+                            // 全体呪文で何も治らなかった仲間の分は表示しない
+                            let Some(cured) = cured else { continue };
+                            messages.push(cured);
+                            continue;
+                        }
+                        messages.push(match cured {
+                            Some(cured) => format!("{}は {}を となえた！ {}", user_name, spell.name, cured),
+                            None if spell.target_type == SpellTarget::AllAllies => {
+                                format!("{}は {}を となえた！", user_name, spell.name)
+                            }
+                            None => format!("{}は {}を となえた！ しかし こうかが なかった", user_name, spell.name),
+                        });
+                        if let ActorId::Party(ci) = user {
+                            running_party_mp[*ci] = (running_party_mp[*ci] - spell.mp_cost).max(0);
+                            effects.push((
+                                msg_index,
+                                MessageEffect::UpdatePartyMp {
+                                    member_index: *ci,
+                                    new_mp: running_party_mp[*ci],
+                                },
+                            ));
+                        }
+                    }
+                    SupportSource::Item(item) => {
+                        last_aoe_caster_spell = None;
+                        messages.push(format!(
+                            "{}は {}を つかった！ {}",
+                            user_name,
+                            item.name(),
+                            cured.unwrap_or_else(|| "しかし こうかが なかった".to_string())
+                        ));
+                    }
+                }
+            }
+            TurnResult::Missed { attacker, target } => {
+                last_aoe_caster_spell = None;
+                let attacker_name = actor_name(attacker, state, &enemy_names);
+                let target_name = target_name_str(target, state, &enemy_names);
+                messages.push(format!(
+                    "{}の こうげき！ {}は ひらりと みを かわした！",
+                    attacker_name, target_name
+                ));
+            }
+            TurnResult::Critical { attacker } => {
+                last_aoe_caster_spell = None;
+                let msg_index = messages.len();
+                messages.push(match attacker {
+                    ActorId::Party(_) => "かいしんの いちげき！".to_string(),
+                    ActorId::Enemy(_) => "つうこんの いちげき！".to_string(),
+                });
+                effects.push((msg_index, MessageEffect::HeavyShake));
+            }
+            TurnResult::ElementReaction { target, affinity, healed } => {
+                // 全体呪文の途中でも続きのメッセージとして扱う（last_aoe_caster_spellは保持）
+                let target_name = target_name_str(target, state, &enemy_names);
+                messages.push(match affinity {
+                    Affinity::Weak => "こうかは ばつぐんだ！".to_string(),
+                    Affinity::Resist => "こうかは いまひとつの ようだ…".to_string(),
+                    Affinity::Immune => format!("{}には こうかが ないようだ…", target_name),
+                    Affinity::Absorb => format!("{}は じゅもんを きゅうしゅうした！ HPが {}かいふく", target_name, healed),
+                    Affinity::Normal => continue,
+                });
+            }
+            TurnResult::Defending { actor } => {
+                last_aoe_caster_spell = None;
+                let name = actor_name(actor, state, &enemy_names);
+                messages.push(format!("{}は みを まもっている", name));
+            }
             TurnResult::PoisonDamage { target, damage } => {
                 last_aoe_caster_spell = None;
                 let target_name = target_name_str(target, state, &enemy_names);
@@ -1019,6 +1308,29 @@ fn results_to_messages(
                     ailment.name()
                 ));
             }
+            TurnResult::BossMessage { message } => {
+                last_aoe_caster_spell = None;
+                messages.push(message.clone());
+            }
+            TurnResult::Summoned { enemy } => {
+                last_aoe_caster_spell = None;
+                let msg_index = messages.len();
+                messages.push(format!("{}が あらわれた！", enemy_names[*enemy]));
+                effects.push((
+                    msg_index,
+                    MessageEffect::ShowEnemy { enemy_index: *enemy },
+                ));
+            }
+            TurnResult::Transformed { enemy } => {
+                last_aoe_caster_spell = None;
+                let msg_index = messages.len();
+                messages.push(format!("{}の すがたが かわった！", enemy_names[*enemy]));
+                effects.push((msg_index, MessageEffect::Shake));
+                effects.push((
+                    msg_index,
+                    MessageEffect::BlinkEnemy { enemy_index: *enemy },
+                ));
+            }
             TurnResult::Fled => {
                 last_aoe_caster_spell = None;
                 messages.push("うまく にげきれた！".to_string());
@@ -1027,6 +1339,15 @@ fn results_to_messages(
                 last_aoe_caster_spell = None;
                 messages.push("にげられなかった！".to_string());
             }
+            TurnResult::EscapeItemUsed { user, item } => {
+                last_aoe_caster_spell = None;
+                let user_name = actor_name(user, state, &enemy_names);
+                messages.push(format!("{}は {}を なげつけた！", user_name, item.name()));
+            }
+            TurnResult::FleeBlocked => {
+                last_aoe_caster_spell = None;
+                messages.push("この たたかいからは にげられない！".to_string());
+            }
         }
     }
 
@@ -1060,6 +1381,12 @@ fn process_message_effects(ui_state: &mut BattleUIState, message_index: usize) {
             MessageEffect::Shake => {
                 ui_state.shake_timer =
                     Some(Timer::from_seconds(0.15, TimerMode::Once));
+                ui_state.shake_amplitude = 3.0;
+            }
+            MessageEffect::HeavyShake => {
+                ui_state.shake_timer =
+                    Some(Timer::from_seconds(0.3, TimerMode::Once));
+                ui_state.shake_amplitude = 8.0;
             }
             MessageEffect::BlinkEnemy { enemy_index } => {
                 ui_state.blink_timer =
@@ -1074,6 +1401,11 @@ fn process_message_effects(ui_state: &mut BattleUIState, message_index: usize) {
                     *mp = new_mp;
                 }
             }
+            MessageEffect::ShowEnemy { enemy_index } => {
+                if let Some(hidden) = ui_state.hidden_enemies.get_mut(enemy_index) {
+                    *hidden = false;
+                }
+            }
         }
     }
 }
@@ -1112,6 +1444,17 @@ fn target_name_str(
     }
 }
 
+/// バフ・デバフ呪文による能力変化のメッセージ
+fn stat_change_text(effect: SpellEffect, amount: i32) -> String {
+    match effect {
+        SpellEffect::DefenseBuff { .. } => format!("しゅびりょくが {}あがった！", amount),
+        SpellEffect::SpeedBuff { .. } => format!("すばやさが {}あがった！", amount),
+        SpellEffect::AttackDebuff { .. } => format!("こうげきりょくが {}さがった！", amount),
+        SpellEffect::DefenseDebuff { .. } => format!("しゅびりょくが {}さがった！", amount),
+        _ => format!("こうげきりょくが {}あがった！", amount),
+    }
+}
+
 fn handle_show_message(
     keyboard: &ButtonInput<KeyCode>,
     game_state: &BattleGameState,
@@ -1151,9 +1494,15 @@ fn handle_show_message(
 
 fn handle_battle_over(
     keyboard: &ButtonInput<KeyCode>,
+    game_state: &BattleGameState,
     next_state: &mut NextState<BattleState>,
+    next_scene: &mut NextState<SceneState>,
 ) {
     if input_ui::is_confirm_just_pressed(keyboard) {
         next_state.set(BattleState::None);
+        // 全滅していればゲームオーバー画面へ
+        if game_state.state.is_party_wiped() {
+            next_scene.set(SceneState::GameOver);
+        }
     }
 }
@@ -5,11 +5,11 @@ mod scene;
 use bevy::prelude::*;
 use scene_state::BattleState;
 
-pub use display::{battle_blink_system, battle_shake_system, battle_status_display_system, battle_update_menu_cache};
+pub use display::{battle_blink_system, battle_enemy_sprite_system, battle_shake_system, battle_status_display_system, battle_turn_order_system, battle_update_menu_cache};
 pub use input::battle_input_system;
 pub use scene::{
     cleanup_battle_scene, init_battle_resources, setup_battle_scene, setup_battle_scene_with_config,
-    BattleGameState, BattlePhase, BattleSceneConfig, BattleUIState, PendingCommands,
+    BattleGameState, BattlePhase, BattleSceneConfig, BattleUIState, PendingCommands, RecordBattles,
 };
 
 pub struct BattlePlugin;
@@ -36,9 +36,12 @@ pub fn register_battle_all_systems(app: &mut App) {
         Update,
         (
             battle_input_system,
+            battle_enemy_sprite_system,
             battle_update_menu_cache,
             hud_ui::command_menu::command_menu_display_system::<BattleUIState>,
             battle_status_display_system,
+            battle_turn_order_system
+                .run_if(resource_changed::<BattleUIState>.or(resource_changed::<BattleGameState>)),
             battle_blink_system,
             battle_shake_system,
         )
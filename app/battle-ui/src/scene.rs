@@ -1,20 +1,24 @@
+use std::path::Path;
+
 use bevy::prelude::*;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-use battle::{BattleAction, BattleState};
-use enemy::{generate_enemy_group, Enemy};
-use spell::SpellEntry;
+use battle::{ActorId, BattleAction, BattleRecord, BattleState, BATTLE_RECORD_PATH};
+use enemy::{encounter_table, floor_tier, generate_floor_enemy_group, Enemy, EnemyKind};
+use spell::{SpellEntry, SpellTarget};
 use item_data::ItemKey;
 
 use scene_state::SceneState;
 use party_state::{CharacterParams, PartyState};
-use progress_state::BossBattlePending;
-use world_state::EncounterZone;
+use progress_state::{BossBattlePending, MimicBattlePending, SymbolBattlePending};
+use world_state::{EncounterZone, GameRng};
 
 use hud_ui::command_menu::{CommandMenu, CommandMenuItem, CommandMenuScrollDown, CommandMenuScrollUp};
 
 use super::display::{
     EnemyNameLabel, MessageText, PartyMemberBlockText, PartyMemberHpBarFill,
-    PartyMemberHpText, PartyMemberMpText, PartyMemberNameText, TargetCursor,
+    PartyMemberHpText, PartyMemberMpText, PartyMemberNameText, TargetCursor, TurnOrderText,
 };
 
 /// 戦闘シーンのルートUIエンティティを識別するマーカー
@@ -72,20 +76,43 @@ pub enum MessageEffect {
     HideEnemy { enemy_index: usize },
     /// 画面シェイク
     Shake,
+    /// 会心の一撃の強い画面シェイク
+    HeavyShake,
     /// 敵スプライト点滅
     BlinkEnemy { enemy_index: usize },
+    /// 戦闘中に現れた敵を表示する
+    ShowEnemy { enemy_index: usize },
 }
 
 /// 戦闘のゲームロジック状態（game crateのBattleStateをラップ）
 #[derive(Resource)]
 pub struct BattleGameState {
     pub state: BattleState,
+    /// この戦闘で使う乱数（ダメージ・逃走・報酬などの判定）
+    pub rng: ChaCha8Rng,
+    /// 再現用の戦闘記録（最初のターン実行時に開始）
+    pub record: Option<BattleRecord>,
+    /// 次のターンの素早さの揺らぎ（コマンド選択の前に振り、行動順の予測と実際のターンで共有する）
+    pub speed_randoms: Vec<f32>,
 }
 
+impl BattleGameState {
+    /// 次のターンの素早さの揺らぎを振り直す
+    pub(crate) fn roll_speed_randoms(&mut self) {
+        let total_actors = self.state.party.len() + self.state.enemies.len();
+        let rng = &mut self.rng;
+        self.speed_randoms = (0..total_actors).map(|_| rng.gen_range(0.0..1.0)).collect();
+    }
+}
+
+/// trueなら戦闘終了ごとに直近の戦闘記録を書き出す（`--record-battles`）
+#[derive(Resource)]
+pub struct RecordBattles(pub bool);
+
 /// 戦闘のUI状態管理リソース
 #[derive(Resource)]
 pub struct BattleUIState {
-    /// 現在選択中のコマンドインデックス (0=たたかう, 1=じゅもん, 2=にげる)
+    /// 現在選択中のコマンドインデックス (0=たたかう, 1=じゅもん, 2=どうぐ, 3=ぼうぎょ, 4=にげる)
     pub selected_command: usize,
     /// ターゲット選択中の生存敵リスト内オフセット
     pub target_offset: usize,
@@ -107,12 +134,14 @@ pub struct BattleUIState {
     pub selected_item: usize,
     /// 選択済みのアイテム（ターゲット選択へ渡す）
     pub pending_item: Option<ItemKey>,
-    /// 味方ターゲット選択中の生存味方リスト内オフセット
+    /// 味方ターゲット選択中の候補リスト内オフセット
     pub ally_target_offset: usize,
     /// メッセージindex → 適用する視覚効果のリスト
     pub message_effects: Vec<(usize, MessageEffect)>,
     /// 画面シェイク用タイマー
     pub shake_timer: Option<Timer>,
+    /// 画面シェイクの振幅(px)
+    pub shake_amplitude: f32,
     /// 敵スプライト点滅用タイマー
     pub blink_timer: Option<Timer>,
     /// 点滅中の敵インデックス
@@ -126,6 +155,42 @@ pub struct BattleUIState {
 const VISIBLE_ITEMS: usize = 6;
 
 impl BattleUIState {
+    /// 蘇生の呪文・アイテムの対象を選んでいるか
+    pub fn is_selecting_revive_target(&self) -> bool {
+        self.pending_spell
+            .is_some_and(|spell| spell.target_type == SpellTarget::SingleDeadAlly)
+            || self
+                .pending_item
+                .is_some_and(|item| matches!(item.entry().effect, item::ItemEffect::Revive { .. }))
+    }
+
+    /// 味方ターゲット選択の候補（蘇生なら戦闘不能のメンバー、それ以外は生存メンバー）
+    pub fn ally_target_candidates(&self, state: &BattleState) -> Vec<usize> {
+        if self.is_selecting_revive_target() {
+            state.dead_party_indices()
+        } else {
+            state.alive_party_indices()
+        }
+    }
+
+    /// コマンド選択中に表示する行動順の予測（名前のリスト。選択中でなければNone）
+    pub fn turn_order_preview(&self, game_state: &BattleGameState) -> Option<Vec<String>> {
+        if matches!(self.phase, BattlePhase::ShowMessage { .. } | BattlePhase::BattleOver { .. }) {
+            return None;
+        }
+        let state = &game_state.state;
+        let enemy_names = enemy_display_names(&state.enemies);
+        let order = state
+            .predicted_action_order(&self.pending_commands.to_commands(), &game_state.speed_randoms)
+            .into_iter()
+            .map(|actor| match actor {
+                ActorId::Party(i) => state.party[i].kind.name().to_string(),
+                ActorId::Enemy(i) => enemy_names[i].clone(),
+            })
+            .collect();
+        Some(order)
+    }
+
     /// CommandMenu用のラベル・disabled情報を現在のphaseから再構築
     pub(crate) fn rebuild_cache(&mut self, game_state: &BattleGameState, char_params: &CharacterParams) {
         self.cached_labels.clear();
@@ -137,6 +202,7 @@ impl BattleUIState {
                     "たたかう".to_string(),
                     "じゅもん".to_string(),
                     "どうぐ".to_string(),
+                    "ぼうぎょ".to_string(),
                     "にげる".to_string(),
                 ];
                 let member = &game_state.state.party[*member_index];
@@ -146,14 +212,19 @@ impl BattleUIState {
                 if member.inventory.is_empty() {
                     self.disabled_indices.push(2);
                 }
+                if !game_state.state.can_flee {
+                    self.disabled_indices.push(4);
+                }
             }
             BattlePhase::SpellSelect { member_index } => {
                 let member = &game_state.state.party[*member_index];
                 let spells = party::available_spells(member.kind, member.level, char_params);
+                let no_fallen = game_state.state.dead_party_indices().is_empty();
                 for (i, spell) in spells.iter().enumerate() {
                     self.cached_labels
                         .push(format!("{} ({})", spell.name, spell.mp_cost));
-                    if member.stats.mp < spell.mp_cost {
+                    let no_revive_target = spell.target_type == SpellTarget::SingleDeadAlly && no_fallen;
+                    if member.stats.mp < spell.mp_cost || no_revive_target {
                         self.disabled_indices.push(i);
                     }
                 }
@@ -164,8 +235,8 @@ impl BattleUIState {
                 for (i, &item) in owned.iter().enumerate() {
                     let count = member.inventory.count(item);
                     let entry = item.entry();
-                    if entry.is_weapon() {
-                        let equipped = member.equipment.weapon == Some(item);
+                    if entry.is_equipment() {
+                        let equipped = member.equipment.is_equipped(item);
                         let equip_mark = if equipped { "E " } else { "" };
                         self.cached_labels.push(format!(
                             "{}{} x{}",
@@ -178,10 +249,16 @@ impl BattleUIState {
                             .push(format!("{} x{}", entry.name, count));
                     }
                     let effect = entry.effect;
+                    let no_revive_target = matches!(effect, item::ItemEffect::Revive { .. })
+                        && game_state.state.dead_party_indices().is_empty();
+                    let cannot_escape = effect == item::ItemEffect::Escape && !game_state.state.can_flee;
                     if matches!(
                         effect,
-                        item::ItemEffect::KeyItem | item::ItemEffect::Material
-                    ) || entry.is_weapon()
+                        item::ItemEffect::KeyItem
+                            | item::ItemEffect::Material
+                            | item::ItemEffect::Equipment
+                    ) || no_revive_target
+                        || cannot_escape
                     {
                         self.disabled_indices.push(i);
                     }
@@ -193,6 +270,7 @@ impl BattleUIState {
                     "たたかう".to_string(),
                     "じゅもん".to_string(),
                     "どうぐ".to_string(),
+                    "ぼうぎょ".to_string(),
                     "にげる".to_string(),
                 ];
             }
@@ -308,19 +386,37 @@ pub struct BattleSceneConfig {
     pub enemies: Vec<Enemy>,
     /// 初期フェーズ（Noneの場合は遭遇メッセージ表示）
     pub initial_phase: Option<BattlePhase>,
+    /// ボス戦か（逃走不可）
+    pub boss_battle: bool,
 }
 
 impl BattleSceneConfig {
     /// エンカウントゾーンに基づいてランダムな敵グループを生成
-    pub fn from_zone(zone: &EncounterZone) -> Self {
+    pub fn from_zone(zone: &EncounterZone, rng: &mut impl Rng) -> Self {
         Self {
-            enemies: generate_enemy_group(
+            enemies: generate_floor_enemy_group(
                 zone.continent_id,
                 zone.is_cave,
-                rand::random::<f32>(),
-                rand::random::<f32>(),
+                zone.floor,
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(0.0..1.0),
             ),
             initial_phase: None,
+            boss_battle: false,
+        }
+    }
+
+    /// 宝箱から現れたミミック1体（その洞窟の最も強い段階に階の深さを足す）
+    pub fn mimic(zone: &EncounterZone) -> Self {
+        let tier = encounter_table(zone.continent_id, true)
+            .iter()
+            .map(|e| e.tier)
+            .max()
+            .unwrap_or(1);
+        Self {
+            enemies: vec![Enemy::new(EnemyKind::Mimic, floor_tier(tier, zone.floor))],
+            initial_phase: None,
+            boss_battle: false,
         }
     }
 }
@@ -332,6 +428,7 @@ pub fn init_battle_resources(
     party: Vec<party::PartyMember>,
     enemies: Vec<Enemy>,
     initial_phase: Option<BattlePhase>,
+    rng: ChaCha8Rng,
 ) -> (BattleGameState, BattleUIState) {
     let display_names = enemy_display_names(&enemies);
 
@@ -357,9 +454,13 @@ pub fn init_battle_resources(
         index: 0,
     });
 
-    let game_state = BattleGameState {
+    let mut game_state = BattleGameState {
         state: battle_state,
+        rng,
+        record: None,
+        speed_randoms: Vec::new(),
     };
+    game_state.roll_speed_randoms();
     let ui_state = BattleUIState {
         selected_command: 0,
         target_offset: 0,
@@ -375,6 +476,7 @@ pub fn init_battle_resources(
         ally_target_offset: 0,
         message_effects: Vec::new(),
         shake_timer: None,
+        shake_amplitude: 0.0,
         blink_timer: None,
         blink_enemy: None,
         cached_labels: Vec::new(),
@@ -384,25 +486,48 @@ pub fn init_battle_resources(
     (game_state, ui_state)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup_battle_scene(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     party_state: Res<PartyState>,
     boss_battle: Option<Res<BossBattlePending>>,
+    mimic_battle: Option<Res<MimicBattlePending>>,
+    symbol_battle: Option<Res<SymbolBattlePending>>,
     encounter_zone: Option<Res<EncounterZone>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let config = if boss_battle.is_some() {
         commands.remove_resource::<BossBattlePending>();
         BattleSceneConfig {
             enemies: vec![Enemy::dark_lord()],
             initial_phase: None,
+            boss_battle: true,
         }
     } else {
         let default_zone = EncounterZone::default();
         let zone = encounter_zone.as_deref().unwrap_or(&default_zone);
-        BattleSceneConfig::from_zone(zone)
+        if mimic_battle.is_some() {
+            commands.remove_resource::<MimicBattlePending>();
+            BattleSceneConfig::mimic(zone)
+        } else if let Some(symbol_battle) = symbol_battle {
+            commands.remove_resource::<SymbolBattlePending>();
+            BattleSceneConfig {
+                enemies: symbol_battle.enemies.clone(),
+                initial_phase: None,
+                boss_battle: false,
+            }
+        } else {
+            BattleSceneConfig::from_zone(zone, &mut game_rng.battle)
+        }
     };
-    setup_battle_scene_inner(&mut commands, &asset_server, &party_state, config);
+    let rng = next_battle_rng(&mut game_rng);
+    setup_battle_scene_inner(&mut commands, &asset_server, &party_state, config, rng);
+}
+
+/// 戦闘ごとの乱数生成器をプレイ中の戦闘ストリームから切り出す
+fn next_battle_rng(game_rng: &mut GameRng) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(game_rng.battle.next_u64())
 }
 
 /// BattleSceneConfigリソースから設定を読んでシーンを構築するシステム
@@ -411,13 +536,16 @@ pub fn setup_battle_scene_with_config(
     asset_server: Res<AssetServer>,
     party_state: Res<PartyState>,
     config: Res<BattleSceneConfig>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let config = BattleSceneConfig {
         enemies: config.enemies.clone(),
         initial_phase: config.initial_phase.clone(),
+        boss_battle: config.boss_battle,
     };
     commands.remove_resource::<BattleSceneConfig>();
-    setup_battle_scene_inner(&mut commands, &asset_server, &party_state, config);
+    let rng = next_battle_rng(&mut game_rng);
+    setup_battle_scene_inner(&mut commands, &asset_server, &party_state, config, rng);
 }
 
 fn setup_battle_scene_inner(
@@ -425,6 +553,7 @@ fn setup_battle_scene_inner(
     asset_server: &AssetServer,
     party_state: &PartyState,
     config: BattleSceneConfig,
+    rng: ChaCha8Rng,
 ) {
     let party = party_state.members.clone();
     let enemies = config.enemies;
@@ -435,7 +564,9 @@ fn setup_battle_scene_inner(
         .map(|e| asset_server.load(e.kind.sprite_path()))
         .collect();
 
-    let (game_state, ui_state) = init_battle_resources(party, enemies, config.initial_phase);
+    let (mut game_state, ui_state) = init_battle_resources(party, enemies, config.initial_phase, rng);
+    game_state.state.party_rows = party_state.party_rows();
+    game_state.state.can_flee = !config.boss_battle;
 
     let font: Handle<Font> = asset_server.load("fonts/NotoSansJP-Bold.ttf");
 
@@ -466,6 +597,25 @@ fn setup_battle_scene_inner(
             // === 上部 (40%): 敵表示エリア ===
             build_enemy_area(parent, &font, asset_server, &enemy_sprite_handles);
 
+            // 行動順の予測（コマンド選択中のみ表示）
+            parent.spawn((
+                TurnOrderText,
+                Text::new(""),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(8.0),
+                    left: Val::Px(12.0),
+                    ..default()
+                },
+                Visibility::Hidden,
+            ));
+
             // === 中部 (30%): メッセージエリア ===
             build_message_area(parent, &font, panel_bg, border_color);
 
@@ -788,18 +938,23 @@ pub fn cleanup_battle_scene(
     game_state: Res<BattleGameState>,
     mut party_state: ResMut<PartyState>,
     scene_state: Res<State<SceneState>>,
+    record_battles: Option<Res<RecordBattles>>,
 ) {
-    // 戦闘結果を永続状態に書き戻す
+    // 戦闘結果を永続状態に書き戻す（戦闘不能メンバーは復活するまでHP0のまま）
     for (i, member) in game_state.state.party.iter().enumerate() {
         if let Some(persistent) = party_state.members.get_mut(i) {
             persistent.sync_from_battle(member);
-            // 戦闘不能メンバーはHP=1で生存（全滅処理は別タスク）
-            if persistent.stats.hp <= 0 {
-                persistent.stats.hp = 1;
-            }
         }
     }
 
+    // 指定されていれば直近の戦闘記録を書き出す（battle::replayで再現できる）
+    if record_battles.is_some_and(|r| r.0)
+        && let Some(record) = &game_state.record
+        && let Err(e) = battle::write_record_file(Path::new(BATTLE_RECORD_PATH), record)
+    {
+        warn!("battle record write failed: {}", e);
+    }
+
     // ボス洞窟で戦闘勝利した場合、ボス撃破フラグを設定
     if *scene_state.get() == SceneState::BossCave && game_state.state.enemies.iter().all(|e| e.stats.hp <= 0) {
         commands.insert_resource(progress_state::BossDefeated);
@@ -0,0 +1,129 @@
+use enemy::EnemyKind;
+
+/// 同時に場に出せる敵の上限（召喚はこれを超えない）
+pub const MAX_ENEMIES: usize = 4;
+
+/// ボスの台本で起きる出来事
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossEvent {
+    /// 戦闘中のセリフ
+    Message(&'static str),
+    /// 手下を呼び出す（種類, 段階）
+    Summon(EnemyKind, u8),
+    /// 最終形態への変身（HPは新しい最大HPまで回復する）
+    Transform {
+        max_hp: i32,
+        attack: i32,
+        defense: i32,
+        speed: i32,
+    },
+}
+
+/// HP割合(%)がしきい値以下になったときに起きる出来事の束
+#[derive(Debug, Clone, Copy)]
+pub struct BossPhase {
+    pub hp_percent: i32,
+    pub events: &'static [BossEvent],
+}
+
+/// ボス戦の台本（フェーズはしきい値の高い順）
+#[derive(Debug, Clone, Copy)]
+pub struct BossScript {
+    pub phases: &'static [BossPhase],
+}
+
+/// 戦闘中の台本の進行状況
+#[derive(Debug, Clone, Copy)]
+pub struct BossScriptState {
+    pub script: &'static BossScript,
+    /// ボス本体の敵インデックス
+    pub boss_index: usize,
+    /// 次に発生するフェーズ
+    pub next_phase: usize,
+}
+
+impl BossScriptState {
+    /// 戦闘開始時点のHPですでに越えているフェーズは飛ばす
+    pub fn new(script: &'static BossScript, boss_index: usize, hp: i32, max_hp: i32) -> Self {
+        let next_phase = script
+            .phases
+            .iter()
+            .take_while(|phase| hp * 100 <= max_hp * phase.hp_percent)
+            .count();
+        Self {
+            script,
+            boss_index,
+            next_phase,
+        }
+    }
+
+    /// 現在のHPで新たに発生するフェーズを取り出して進める
+    pub fn advance(&mut self, hp: i32, max_hp: i32) -> Vec<&'static BossPhase> {
+        let mut reached = Vec::new();
+        while let Some(phase) = self.script.phases.get(self.next_phase) {
+            if hp * 100 > max_hp * phase.hp_percent {
+                break;
+            }
+            reached.push(phase);
+            self.next_phase += 1;
+        }
+        reached
+    }
+}
+
+static DARK_LORD_SCRIPT: BossScript = BossScript {
+    phases: &[
+        BossPhase {
+            hp_percent: 70,
+            events: &[
+                BossEvent::Message("まおう「こしゃくな… しもべたちよ いでよ！」"),
+                BossEvent::Summon(EnemyKind::Demon, 2),
+                BossEvent::Summon(EnemyKind::Demon, 2),
+            ],
+        },
+        BossPhase {
+            hp_percent: 30,
+            events: &[
+                BossEvent::Message("まおう「ぐおお… これが わが しんの すがただ！」"),
+                BossEvent::Transform {
+                    max_hp: 250,
+                    attack: 32,
+                    defense: 18,
+                    speed: 12,
+                },
+            ],
+        },
+    ],
+};
+
+/// 敵の種類に対応するボス台本
+pub fn boss_script(kind: EnemyKind) -> Option<&'static BossScript> {
+    match kind {
+        EnemyKind::DarkLord => Some(&DARK_LORD_SCRIPT),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_fires_each_phase_once() {
+        let script = boss_script(EnemyKind::DarkLord).unwrap();
+        let mut state = BossScriptState::new(script, 0, 200, 200);
+        assert!(state.advance(150, 200).is_empty());
+        assert_eq!(state.advance(140, 200).len(), 1);
+        assert!(state.advance(100, 200).is_empty());
+        // 一気に削られたら残りのフェーズもまとめて発生する
+        assert_eq!(state.advance(10, 200).len(), 1);
+        assert!(state.advance(0, 200).is_empty());
+    }
+
+    #[test]
+    fn phases_already_passed_at_start_are_skipped() {
+        let script = boss_script(EnemyKind::DarkLord).unwrap();
+        let state = BossScriptState::new(script, 0, 100, 200);
+        assert_eq!(state.next_phase, 1);
+    }
+}
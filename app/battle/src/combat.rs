@@ -1,26 +1,45 @@
-use enemy::Enemy;
-use spell::{Ailment, SpellEffect, SpellEntry, SpellTarget};
+use enemy::{Enemy, TargetPolicy, WeightedSpell};
+use crate::boss::{boss_script, BossEvent, BossScriptState, MAX_ENEMIES};
+use spell::{Affinity, Ailment, SpellEffect, SpellEntry, SpellTarget};
 use item::ItemEffect;
 use item_data::ItemKey;
-use party::{CombatStats, PartyMember};
+use party::{CombatStats, PartyMember, Row};
+use party::stats::CRITICAL_RATE;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActorId {
     Party(usize),
     Enemy(usize),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TargetId {
     Enemy(usize),
     Party(usize),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl TargetId {
+    /// 対象をアクターとして見たときのID
+    fn actor(self) -> ActorId {
+        match self {
+            TargetId::Enemy(ei) => ActorId::Enemy(ei),
+            TargetId::Party(pi) => ActorId::Party(pi),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BattleAction {
     Attack { target: TargetId },
-    Spell { spell: SpellEntry, target: TargetId },
+    Spell {
+        #[serde(with = "crate::record::spell_by_name")]
+        spell: SpellEntry,
+        target: TargetId,
+    },
     UseItem { item: ItemKey, target: TargetId },
+    /// ぼうぎょ（そのターンに受けるダメージを半減し、誰よりも先に構える）
+    Defend,
     Flee,
 }
 
@@ -31,30 +50,141 @@ pub struct BuffState {
     pub remaining_turns: u32,
 }
 
-/// 1アクターのバフ群
+/// 1アクターのバフ・デバフ群
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ActorBuffs {
     pub attack_up: Option<BuffState>,
+    pub defense_up: Option<BuffState>,
+    pub speed_up: Option<BuffState>,
+    pub attack_down: Option<BuffState>,
+    pub defense_down: Option<BuffState>,
     /// ブロック値（受けるダメージを肩代わりする。ターン終了時に半減）
     pub block: i32,
+    /// ぼうぎょ中（このターンだけ受けるダメージを半減）
+    pub defending: bool,
+}
+
+impl ActorBuffs {
+    fn slot_mut(&mut self, stat: BuffStat) -> &mut Option<BuffState> {
+        match stat {
+            BuffStat::Attack => &mut self.attack_up,
+            BuffStat::Defense => &mut self.defense_up,
+            BuffStat::Speed => &mut self.speed_up,
+            BuffStat::AttackDown => &mut self.attack_down,
+            BuffStat::DefenseDown => &mut self.defense_down,
+        }
+    }
+
+    fn amount(&self, stat: BuffStat) -> i32 {
+        let slot = match stat {
+            BuffStat::Attack => self.attack_up,
+            BuffStat::Defense => self.defense_up,
+            BuffStat::Speed => self.speed_up,
+            BuffStat::AttackDown => self.attack_down,
+            BuffStat::DefenseDown => self.defense_down,
+        };
+        slot.map(|b| b.amount).unwrap_or(0)
+    }
+
+    /// ステータス変化をかける（同じ種類なら上書きして持続ターンを戻す）
+    pub fn apply(&mut self, stat: BuffStat, amount: i32) {
+        *self.slot_mut(stat) = Some(BuffState {
+            amount,
+            remaining_turns: BUFF_DURATION,
+        });
+    }
+
+    /// 攻撃力の増減（アップ − ダウン）
+    pub fn attack_modifier(&self) -> i32 {
+        self.amount(BuffStat::Attack) - self.amount(BuffStat::AttackDown)
+    }
+
+    /// 防御力の増減（アップ − ダウン）
+    pub fn defense_modifier(&self) -> i32 {
+        self.amount(BuffStat::Defense) - self.amount(BuffStat::DefenseDown)
+    }
+
+    pub fn speed_modifier(&self) -> i32 {
+        self.amount(BuffStat::Speed)
+    }
 }
 
 pub const BUFF_DURATION: u32 = 5;
 
+/// ぼうぎょ中に受けるダメージの倍率(%)
+pub const DEFEND_DAMAGE_PERCENT: i32 = 50;
+
+/// かかっている状態異常1つ分の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AilmentState {
+    pub ailment: Ailment,
+    /// 残りターン数（Noneなら治すまで続く）
+    pub remaining_turns: Option<u32>,
+}
+
 /// 1アクターの状態異常
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ActorAilments {
-    pub sleep: bool,
-    pub poison: bool,
+    states: Vec<AilmentState>,
 }
 
 impl ActorAilments {
     pub fn has_any(&self) -> bool {
-        self.sleep || self.poison
+        !self.states.is_empty()
+    }
+
+    pub fn has(&self, ailment: Ailment) -> bool {
+        self.states.iter().any(|s| s.ailment == ailment)
+    }
+
+    /// かかっている状態異常（かかった順）
+    pub fn states(&self) -> &[AilmentState] {
+        &self.states
+    }
+
+    /// 状態異常にする（既にかかっていれば持続ターンを戻す）
+    pub fn inflict(&mut self, ailment: Ailment) {
+        let remaining_turns = ailment.duration();
+        match self.states.iter_mut().find(|s| s.ailment == ailment) {
+            Some(state) => state.remaining_turns = remaining_turns,
+            None => self.states.push(AilmentState { ailment, remaining_turns }),
+        }
+    }
+
+    /// 状態異常を治す（かかっていなければfalse）
+    pub fn cure(&mut self, ailment: Ailment) -> bool {
+        let before = self.states.len();
+        self.states.retain(|s| s.ailment != ailment);
+        self.states.len() != before
+    }
+
+    /// 行動できなくなっている状態異常（眠り・まひ）
+    pub fn incapacitating(&self) -> Option<Ailment> {
+        [Ailment::Sleep, Ailment::Paralysis]
+            .into_iter()
+            .find(|&ailment| self.has(ailment))
+    }
+
+    /// ターン終了時の経過。持続ターンが切れたか自然回復した状態異常を治して返す
+    fn tick(&mut self, recovery_random: f32) -> Vec<Ailment> {
+        let mut cured = Vec::new();
+        self.states.retain_mut(|state| {
+            if let Some(turns) = state.remaining_turns.as_mut() {
+                *turns = turns.saturating_sub(1);
+            }
+            let expired = state.remaining_turns == Some(0);
+            if expired || spell::ailment_success(state.ailment.recovery_percent(), recovery_random) {
+                cured.push(state.ailment);
+                false
+            } else {
+                true
+            }
+        });
+        cured
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TurnResult {
     Attack {
         attacker: ActorId,
@@ -64,6 +194,7 @@ pub enum TurnResult {
     },
     SpellDamage {
         caster: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
         spell: SpellEntry,
         target: TargetId,
         damage: i32,
@@ -71,12 +202,14 @@ pub enum TurnResult {
     },
     Healed {
         caster: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
         spell: SpellEntry,
         target: TargetId,
         amount: i32,
     },
     Buffed {
         caster: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
         spell: SpellEntry,
         target: TargetId,
         amount: i32,
@@ -93,6 +226,7 @@ pub enum TurnResult {
     },
     MpDrained {
         caster: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
         spell: SpellEntry,
         target: TargetId,
         amount: i32,
@@ -102,18 +236,53 @@ pub enum TurnResult {
     },
     AilmentInflicted {
         caster: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
         spell: SpellEntry,
         target: TargetId,
         ailment: Ailment,
     },
     AilmentResisted {
         caster: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
         spell: SpellEntry,
         target: TargetId,
     },
     Sleeping {
         actor: ActorId,
     },
+    /// まひで動けない
+    Paralyzed {
+        actor: ActorId,
+    },
+    /// 沈黙で呪文を唱えられなかった
+    Silenced {
+        actor: ActorId,
+        #[serde(with = "crate::record::spell_by_name")]
+        spell: SpellEntry,
+    },
+    /// 混乱して敵味方の区別なく攻撃する（直後に攻撃の結果が続く）
+    Confused {
+        actor: ActorId,
+    },
+    /// ぼうぎょの構えをとった
+    Defending {
+        actor: ActorId,
+    },
+    /// 物理攻撃がかわされた
+    Missed {
+        attacker: ActorId,
+        target: TargetId,
+    },
+    /// 会心の一撃（直後のAttackのダメージは防御を無視している）
+    Critical {
+        attacker: ActorId,
+    },
+    /// 直前のSpellDamageで属性の相性が通常でなかった（吸収ならhealedだけ回復している）
+    ElementReaction {
+        target: TargetId,
+        affinity: Affinity,
+        healed: i32,
+    },
     PoisonDamage {
         target: TargetId,
         damage: i32,
@@ -122,28 +291,208 @@ pub enum TurnResult {
         target: TargetId,
         ailment: Ailment,
     },
+    /// 呪文・アイテムで状態異常を治した（ailmentsが空なら何も治らなかった）
+    Cured {
+        user: ActorId,
+        source: SupportSource,
+        target: TargetId,
+        ailments: Vec<Ailment>,
+    },
     BlockDecayed {
         target: TargetId,
         remaining: i32,
     },
+    Revived {
+        user: ActorId,
+        source: SupportSource,
+        target: TargetId,
+        hp: i32,
+    },
+    /// ボスのセリフ
+    BossMessage {
+        message: String,
+    },
+    /// 戦闘中に敵が呼び出された
+    Summoned {
+        enemy: usize,
+    },
+    /// ボスが最終形態に変身した
+    Transformed {
+        enemy: usize,
+    },
+    /// 逃走アイテムを使った（直後にFledが続く）
+    EscapeItemUsed {
+        user: ActorId,
+        item: ItemKey,
+    },
     Fled,
     FleeFailed,
+    /// ボス戦のため逃げられなかった
+    FleeBlocked,
 }
 
-/// バフが適用されるステータスの種類
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// 蘇生・状態異常回復の手段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SupportSource {
+    Spell(#[serde(with = "crate::record::spell_by_name")] SpellEntry),
+    Item(ItemKey),
+}
+
+/// バフ・デバフが適用されるステータスの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BuffStat {
     Attack,
+    Defense,
+    Speed,
+    /// 攻撃力ダウン（デバフ）
+    AttackDown,
+    /// 防御力ダウン（デバフ）
+    DefenseDown,
+}
+
+impl BuffStat {
+    pub const ALL: [BuffStat; 5] = [
+        BuffStat::Attack,
+        BuffStat::Defense,
+        BuffStat::Speed,
+        BuffStat::AttackDown,
+        BuffStat::DefenseDown,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BuffStat::Attack => "こうげきりょくアップ",
+            BuffStat::Defense => "しゅびりょくアップ",
+            BuffStat::Speed => "すばやさアップ",
+            BuffStat::AttackDown => "こうげきりょくダウン",
+            BuffStat::DefenseDown => "しゅびりょくダウン",
+        }
+    }
+
+    /// ステータス変化の呪文効果から (種類, 量) を取り出す
+    pub fn from_effect(effect: SpellEffect) -> Option<(BuffStat, i32)> {
+        match effect {
+            SpellEffect::AttackBuff { amount } => Some((BuffStat::Attack, amount)),
+            SpellEffect::DefenseBuff { amount } => Some((BuffStat::Defense, amount)),
+            SpellEffect::SpeedBuff { amount } => Some((BuffStat::Speed, amount)),
+            SpellEffect::AttackDebuff { amount } => Some((BuffStat::AttackDown, amount)),
+            SpellEffect::DefenseDebuff { amount } => Some((BuffStat::DefenseDown, amount)),
+            _ => None,
+        }
+    }
 }
 
 /// ターン実行に必要な乱数群
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TurnRandomFactors {
     /// 各アクターのダメージ乱数(0.8~1.2)。indexはaction_order内の順番
     pub damage_randoms: Vec<f32>,
-    /// 逃走判定用の乱数(0.0~1.0)。0.5未満で成功
+    /// 逃走判定用の乱数(0.0~1.0)。逃走成功率未満で成功
     pub flee_random: f32,
-    /// 敵ごとの呪文使用判定用乱数(0.0~1.0)。0.5未満で呪文使用
+    /// 敵ごとの呪文使用判定用乱数(0.0~1.0)。AIの呪文使用率未満で呪文使用し、同じ値で呪文を選ぶ
     pub spell_randoms: Vec<f32>,
+    /// 敵ごとの攻撃対象選択用乱数(0.0~1.0)。ランダムに狙うAIが使う
+    pub target_randoms: Vec<f32>,
+    /// 各アクターの命中判定用乱数(0.0~1.0)。命中率未満で命中。indexはdamage_randomsと同じ（なければ必ず命中）
+    pub hit_randoms: Vec<f32>,
+    /// 各アクターの会心判定用乱数(0.0~1.0)。会心率未満で会心。indexはdamage_randomsと同じ（なければ会心なし）
+    pub critical_randoms: Vec<f32>,
+    /// 各アクターの混乱時の攻撃対象選択用乱数(0.0~1.0)。indexはdamage_randomsと同じ
+    pub confusion_randoms: Vec<f32>,
+    /// アクターごとの状態異常の自然回復判定用乱数(0.0~1.0)。パーティ→敵の順（なければ自然回復しない）
+    pub recovery_randoms: Vec<f32>,
+    /// アクターごとの行動順の揺らぎ用乱数(0.0~1.0)。パーティ→敵の順。0.5で揺らぎなし（なければ揺らぎなし）
+    pub speed_randoms: Vec<f32>,
+}
+
+impl TurnRandomFactors {
+    /// action_order内の順番に対応する物理攻撃の乱数
+    fn attack_rolls(&self, random_idx: usize) -> AttackRolls {
+        AttackRolls {
+            damage: self.damage_randoms.get(random_idx).copied().unwrap_or(1.0),
+            hit: self.hit_randoms.get(random_idx).copied().unwrap_or(0.0),
+            critical: self.critical_randoms.get(random_idx).copied().unwrap_or(1.0),
+        }
+    }
+}
+
+/// 1アクターの行動に使う乱数（ダメージ・命中・会心）
+#[derive(Debug, Clone, Copy)]
+struct AttackRolls {
+    damage: f32,
+    hit: f32,
+    critical: f32,
+}
+
+/// 物理攻撃の当たり方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HitOutcome {
+    Miss,
+    Hit,
+    Critical,
+}
+
+impl AttackRolls {
+    /// 暗闇の攻撃者は命中率が下がる
+    fn outcome(self, attacker_speed: i32, defender_speed: i32, blind: bool) -> HitOutcome {
+        let mut hit_rate = CombatStats::hit_rate(attacker_speed, defender_speed);
+        if blind {
+            hit_rate *= spell::BLIND_HIT_PERCENT as f32 / 100.0;
+        }
+        if self.hit >= hit_rate {
+            HitOutcome::Miss
+        } else if self.critical < CRITICAL_RATE {
+            HitOutcome::Critical
+        } else {
+            HitOutcome::Hit
+        }
+    }
+}
+
+/// 戦闘報酬の決定に必要な乱数群
+pub struct RewardRandomFactors {
+    /// 敵ごとのゴールド乱数(0.0~1.0)
+    pub gold_randoms: Vec<f32>,
+    /// 敵ごとのドロップ乱数(0.0~1.0)
+    pub drop_randoms: Vec<f32>,
+}
+
+/// 戦闘勝利で得られる報酬
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BattleRewards {
+    pub exp: u32,
+    pub gold: u32,
+    /// 敵が落としたアイテム（倒した敵の順）
+    pub items: Vec<ItemKey>,
+}
+
+/// 後列が絡む物理攻撃のダメージ倍率(%)（後列から攻撃する / 後列が攻撃される）
+pub const BACK_ROW_DAMAGE_PERCENT: i32 = 50;
+/// 敵が攻撃対象を選ぶときの前列の重み
+const FRONT_ROW_TARGET_WEIGHT: usize = 3;
+/// 敵が攻撃対象を選ぶときの後列の重み
+const BACK_ROW_TARGET_WEIGHT: usize = 1;
+
+/// 行動順を決めるときの素早さの揺らぎの最大幅（±）
+pub const SPEED_JITTER: i32 = 2;
+
+/// 乱数(0.0~1.0)を素早さの揺らぎ(-SPEED_JITTER~+SPEED_JITTER)に変換
+fn speed_jitter(random: f32) -> i32 {
+    ((random - 0.5) * 2.0 * SPEED_JITTER as f32).round() as i32
+}
+
+/// 逃走の基本成功率
+pub const FLEE_BASE_CHANCE: f32 = 0.5;
+/// パーティと敵の平均素早さの差1あたりの逃走成功率の補正
+pub const FLEE_SPEED_FACTOR: f32 = 0.05;
+/// 逃走に1回失敗するごとの成功率の上昇
+pub const FLEE_RETRY_BONUS: f32 = 0.15;
+/// 逃走成功率の下限
+pub const FLEE_MIN_CHANCE: f32 = 0.1;
+
+/// 後列による物理ダメージの軽減（最低1）
+fn back_row_damage(damage: i32) -> i32 {
+    (damage * BACK_ROW_DAMAGE_PERCENT / 100).max(1)
 }
 
 #[derive(Debug, Clone)]
@@ -152,36 +501,111 @@ pub struct BattleState {
     pub enemies: Vec<Enemy>,
     pub turn_log: Vec<TurnResult>,
     pub party_buffs: Vec<ActorBuffs>,
+    pub enemy_buffs: Vec<ActorBuffs>,
     pub party_ailments: Vec<ActorAilments>,
     pub enemy_ailments: Vec<ActorAilments>,
+    /// パーティメンバーの隊列（初期値は全員前列）
+    pub party_rows: Vec<Row>,
+    /// ボス戦の台本（ボスがいる戦闘のみ）
+    pub boss: Option<BossScriptState>,
+    /// 逃走できる戦闘か（ボス戦ではfalse）
+    pub can_flee: bool,
+    /// この戦闘で逃走に失敗した回数
+    pub flee_attempts: u32,
 }
 
 impl BattleState {
     pub fn new(party: Vec<PartyMember>, enemies: Vec<Enemy>) -> Self {
         let party_count = party.len();
         let enemy_count = enemies.len();
+        let boss = enemies.iter().enumerate().find_map(|(i, e)| {
+            boss_script(e.kind).map(|script| BossScriptState::new(script, i, e.stats.hp, e.stats.max_hp))
+        });
         Self {
             party,
             enemies,
             turn_log: Vec::new(),
             party_buffs: vec![ActorBuffs::default(); party_count],
+            enemy_buffs: vec![ActorBuffs::default(); enemy_count],
             party_ailments: vec![ActorAilments::default(); party_count],
             enemy_ailments: vec![ActorAilments::default(); enemy_count],
+            party_rows: vec![Row::Front; party_count],
+            boss,
+            can_flee: true,
+            flee_attempts: 0,
+        }
+    }
+
+    /// 逃走成功率（パーティと敵の平均素早さの差と失敗回数で決まる）
+    pub fn flee_chance(&self) -> f32 {
+        if !self.can_flee {
+            return 0.0;
         }
+        let average_speed = |actors: Vec<ActorId>| {
+            let total: i32 = actors.iter().map(|&a| self.actor_speed(a)).sum();
+            total as f32 / actors.len().max(1) as f32
+        };
+        let party_speed = average_speed(self.alive_party_indices().into_iter().map(ActorId::Party).collect());
+        let enemy_speed = average_speed(self.alive_enemy_indices().into_iter().map(ActorId::Enemy).collect());
+        let chance = FLEE_BASE_CHANCE
+            + (party_speed - enemy_speed) * FLEE_SPEED_FACTOR
+            + self.flee_attempts as f32 * FLEE_RETRY_BONUS;
+        chance.clamp(FLEE_MIN_CHANCE, 1.0)
     }
 
     /// パーティメンバーの実効攻撃力（バフ込み）
     pub fn effective_attack_with_buff(&self, party_idx: usize) -> i32 {
-        let base = self.party[party_idx].effective_attack();
-        let buff_amount = self.party_buffs[party_idx]
-            .attack_up
-            .map(|b| b.amount)
-            .unwrap_or(0);
-        base + buff_amount
+        self.actor_attack(ActorId::Party(party_idx))
+    }
+
+    fn actor_buffs(&self, actor: ActorId) -> &ActorBuffs {
+        match actor {
+            ActorId::Party(pi) => &self.party_buffs[pi],
+            ActorId::Enemy(ei) => &self.enemy_buffs[ei],
+        }
+    }
+
+    /// アクターの実効攻撃力（装備・バフ・デバフ込み、最低0）
+    fn actor_attack(&self, actor: ActorId) -> i32 {
+        let base = match actor {
+            ActorId::Party(pi) => self.party[pi].effective_attack(),
+            ActorId::Enemy(ei) => self.enemies[ei].stats.attack,
+        };
+        (base + self.actor_buffs(actor).attack_modifier()).max(0)
+    }
+
+    /// アクターの実効防御力（装備・バフ・デバフ込み、最低0）
+    fn actor_defense(&self, actor: ActorId) -> i32 {
+        let base = match actor {
+            ActorId::Party(pi) => self.party[pi].effective_defense(),
+            ActorId::Enemy(ei) => self.enemies[ei].stats.defense,
+        };
+        (base + self.actor_buffs(actor).defense_modifier()).max(0)
+    }
+
+    /// アクターの実効素早さ（装備・バフ込み）
+    fn actor_speed(&self, actor: ActorId) -> i32 {
+        let base = match actor {
+            ActorId::Party(pi) => self.party[pi].effective_speed(),
+            ActorId::Enemy(ei) => self.enemies[ei].stats.speed,
+        };
+        base + self.actor_buffs(actor).speed_modifier()
+    }
+
+    /// パーティメンバーの隊列
+    pub fn party_row(&self, party_idx: usize) -> Row {
+        self.party_rows.get(party_idx).copied().unwrap_or_default()
     }
 
     /// ブロックでダメージを吸収し、(実ダメージ, ブロック吸収量)を返す
+    ///
+    /// ぼうぎょ中なら先にダメージを半減してからブロックで吸収する。
     fn apply_block(&mut self, party_idx: usize, raw_damage: i32) -> (i32, i32) {
+        let raw_damage = if self.party_buffs[party_idx].defending {
+            (raw_damage * DEFEND_DAMAGE_PERCENT / 100).max(1)
+        } else {
+            raw_damage
+        };
         let block = self.party_buffs[party_idx].block;
         if block <= 0 {
             return (raw_damage, 0);
@@ -200,45 +624,51 @@ impl BattleState {
         party_commands: &[BattleAction],
         random_factors: &TurnRandomFactors,
     ) -> Vec<TurnResult> {
+        // 逃走アイテム: 使えば行動順に関係なく必ず逃げられる
+        if self.can_flee
+            && let Some((pi, item)) = self.escape_item_user(party_commands)
+            && self.party[pi].inventory.use_item(item)
+        {
+            let results = vec![
+                TurnResult::EscapeItemUsed {
+                    user: ActorId::Party(pi),
+                    item,
+                },
+                TurnResult::Fled,
+            ];
+            self.turn_log.extend(results.clone());
+            return results;
+        }
+
         // 逃走チェック: 誰かがFleeを選んでいたら逃走判定
         let has_flee = party_commands.iter().any(|a| matches!(a, BattleAction::Flee));
         if has_flee {
-            if random_factors.flee_random < 0.5 {
+            if !self.can_flee {
+                return self.execute_enemy_only_turn(TurnResult::FleeBlocked, random_factors);
+            }
+            if random_factors.flee_random < self.flee_chance() {
                 let result = vec![TurnResult::Fled];
                 self.turn_log.extend(result.clone());
                 return result;
-            } else {
-                // 逃走失敗: 敵だけ行動
-                let mut results = vec![TurnResult::FleeFailed];
-                for (random_idx, ei) in self.alive_enemy_indices().into_iter().enumerate() {
-                    let random = random_factors
-                        .damage_randoms
-                        .get(random_idx)
-                        .copied()
-                        .unwrap_or(1.0);
-                    let spell_random = random_factors
-                        .spell_randoms
-                        .get(ei)
-                        .copied()
-                        .unwrap_or(1.0);
-                    results.extend(self.execute_enemy_action(ei, random, spell_random));
-                }
-                self.turn_log.extend(results.clone());
-                return results;
             }
+            // 逃走失敗: 次の逃走は成功しやすくなる
+            self.flee_attempts += 1;
+            return self.execute_enemy_only_turn(TurnResult::FleeFailed, random_factors);
         }
 
         // 素早さ順の行動順序を決定
-        let action_order = self.build_action_order(party_commands);
+        let action_order = self.build_action_order(party_commands, &random_factors.speed_randoms);
 
         let mut results = Vec::new();
 
         for (random_idx, actor) in action_order.iter().enumerate() {
-            let random = random_factors
-                .damage_randoms
+            let rolls = random_factors.attack_rolls(random_idx);
+            let random = rolls.damage;
+            let confusion_random = random_factors
+                .confusion_randoms
                 .get(random_idx)
                 .copied()
-                .unwrap_or(1.0);
+                .unwrap_or(0.0);
 
             match actor {
                 ActorId::Party(pi) => {
@@ -246,10 +676,13 @@ impl BattleState {
                     if !self.party[pi].stats.is_alive() {
                         continue;
                     }
-                    if self.party_ailments[pi].sleep {
-                        results.push(TurnResult::Sleeping {
-                            actor: ActorId::Party(pi),
-                        });
+                    let actor = ActorId::Party(pi);
+                    if let Some(skipped) = self.incapacitated_result(actor) {
+                        results.push(skipped);
+                        continue;
+                    }
+                    if self.party_ailments[pi].has(Ailment::Confusion) {
+                        results.extend(self.execute_confused_attack(actor, rolls, confusion_random));
                         continue;
                     }
                     match party_commands.get(pi) {
@@ -257,67 +690,152 @@ impl BattleState {
                             let actual_target = self.retarget_enemy(*target);
                             if let Some(actual_target) = actual_target {
                                 results
-                                    .extend(self.execute_party_attack(pi, actual_target, random));
+                                    .extend(self.execute_party_attack(pi, actual_target, rolls));
                             }
                         }
+                        Some(BattleAction::Spell { spell, .. }) if self.party_ailments[pi].has(Ailment::Silence) => {
+                            results.push(TurnResult::Silenced { actor, spell: *spell });
+                        }
                         Some(BattleAction::Spell { spell, target }) => {
                             results.extend(self.execute_spell(pi, *spell, *target, random));
                         }
                         Some(BattleAction::UseItem { item, target }) => {
                             results.extend(self.execute_item(pi, *item, *target, random));
                         }
+                        Some(BattleAction::Defend) => {
+                            self.party_buffs[pi].defending = true;
+                            results.push(TurnResult::Defending {
+                                actor: ActorId::Party(pi),
+                            });
+                        }
                         _ => {}
                     }
                 }
                 ActorId::Enemy(ei) => {
-                    let ei = *ei;
-                    if !self.enemies[ei].stats.is_alive() {
-                        continue;
-                    }
-                    if self.enemy_ailments[ei].sleep {
-                        results.push(TurnResult::Sleeping {
-                            actor: ActorId::Enemy(ei),
-                        });
-                        continue;
-                    }
-                    let spell_random = random_factors
-                        .spell_randoms
-                        .get(ei)
-                        .copied()
-                        .unwrap_or(1.0);
-                    results.extend(self.execute_enemy_action(ei, random, spell_random));
+                    results.extend(self.execute_enemy_turn(*ei, random_idx, random_factors));
                 }
             }
         }
 
-        // ターン終了時: バフのtick
-        results.extend(self.tick_buffs());
+        results.extend(self.end_turn(random_factors));
 
-        // ターン終了時: 毒ダメージ
-        results.extend(self.tick_poison());
+        self.turn_log.extend(results.clone());
+        results
+    }
+
+    /// 逃走できなかったターン: 敵だけ素早さ順に行動し、ターン終了処理も行う
+    fn execute_enemy_only_turn(
+        &mut self,
+        first: TurnResult,
+        random_factors: &TurnRandomFactors,
+    ) -> Vec<TurnResult> {
+        let mut results = vec![first];
+        let action_order = self.build_action_order(&[], &random_factors.speed_randoms);
+        for (random_idx, actor) in action_order.into_iter().enumerate() {
+            if let ActorId::Enemy(ei) = actor {
+                results.extend(self.execute_enemy_turn(ei, random_idx, random_factors));
+            }
+        }
+
+        results.extend(self.end_turn(random_factors));
 
         self.turn_log.extend(results.clone());
         results
     }
 
-    /// 素早さ順の行動順序を構築
-    fn build_action_order(&self, party_commands: &[BattleAction]) -> Vec<ActorId> {
+    /// 敵1体の手番: 行動不能・混乱を判定してから行動
+    fn execute_enemy_turn(
+        &mut self,
+        ei: usize,
+        random_idx: usize,
+        random_factors: &TurnRandomFactors,
+    ) -> Vec<TurnResult> {
+        if !self.enemies[ei].stats.is_alive() {
+            return Vec::new();
+        }
+        let actor = ActorId::Enemy(ei);
+        if let Some(skipped) = self.incapacitated_result(actor) {
+            return vec![skipped];
+        }
+        let rolls = random_factors.attack_rolls(random_idx);
+        if self.enemy_ailments[ei].has(Ailment::Confusion) {
+            let confusion_random = random_factors
+                .confusion_randoms
+                .get(random_idx)
+                .copied()
+                .unwrap_or(0.0);
+            return self.execute_confused_attack(actor, rolls, confusion_random);
+        }
+        let spell_random = random_factors
+            .spell_randoms
+            .get(ei)
+            .copied()
+            .unwrap_or(1.0);
+        let target_random = random_factors
+            .target_randoms
+            .get(ei)
+            .copied()
+            .unwrap_or(0.0);
+        self.execute_enemy_action(ei, rolls, spell_random, target_random)
+    }
+
+    /// ターン終了時の処理: ぼうぎょ解除・バフ/毒/状態異常のtick・ボスの台本
+    fn end_turn(&mut self, random_factors: &TurnRandomFactors) -> Vec<TurnResult> {
+        for buffs in &mut self.party_buffs {
+            buffs.defending = false;
+        }
+
+        let mut results = self.tick_buffs();
+        results.extend(self.tick_poison());
+        results.extend(self.tick_ailments(&random_factors.recovery_randoms));
+        results.extend(self.run_boss_script());
+        results
+    }
+
+    /// 逃走アイテムを使おうとしている行動可能なメンバーと、そのアイテム（実際に持っている場合のみ）
+    fn escape_item_user(&self, party_commands: &[BattleAction]) -> Option<(usize, ItemKey)> {
+        party_commands.iter().enumerate().find_map(|(pi, action)| match action {
+            BattleAction::UseItem { item, .. }
+                if item.entry().effect == ItemEffect::Escape
+                    && self.party[pi].inventory.count(*item) > 0
+                    && self.party[pi].stats.is_alive()
+                    && self.party_ailments[pi].incapacitating().is_none() =>
+            {
+                Some((pi, *item))
+            }
+            _ => None,
+        })
+    }
+
+    /// 行動順の予測（コマンド選択中の表示用。execute_turnと同じ揺らぎを渡せば実際の行動順と一致する）
+    pub fn predicted_action_order(&self, party_commands: &[BattleAction], speed_randoms: &[f32]) -> Vec<ActorId> {
+        self.build_action_order(party_commands, speed_randoms)
+    }
+
+    /// 素早さ順の行動順序を構築（バフと揺らぎ込みの素早さ。ぼうぎょは素早さに関係なく最優先）
+    fn build_action_order(&self, party_commands: &[BattleAction], speed_randoms: &[f32]) -> Vec<ActorId> {
         let mut actors: Vec<(ActorId, i32)> = Vec::new();
+        let jitter = |random_idx: usize| speed_randoms.get(random_idx).map_or(0, |&r| speed_jitter(r));
 
         for (i, member) in self.party.iter().enumerate() {
             if member.stats.is_alive() && i < party_commands.len() {
-                actors.push((ActorId::Party(i), member.stats.speed));
+                actors.push((ActorId::Party(i), self.actor_speed(ActorId::Party(i)) + jitter(i)));
             }
         }
+        let party_count = self.party.len();
         for (i, enemy) in self.enemies.iter().enumerate() {
             if enemy.stats.is_alive() {
-                actors.push((ActorId::Enemy(i), enemy.stats.speed));
+                actors.push((ActorId::Enemy(i), self.actor_speed(ActorId::Enemy(i)) + jitter(party_count + i)));
             }
         }
 
-        // 素早さ降順（同速ならパーティ優先）
+        let is_defending = |id: ActorId| {
+            matches!(id, ActorId::Party(i) if matches!(party_commands.get(i), Some(BattleAction::Defend)))
+        };
+
+        // ぼうぎょ優先 → 素早さ降順（同速ならパーティ優先）
         actors.sort_by(|a, b| {
-            b.1.cmp(&a.1).then_with(|| {
+            is_defending(b.0).cmp(&is_defending(a.0)).then_with(|| b.1.cmp(&a.1)).then_with(|| {
                 let a_order = match a.0 {
                     ActorId::Party(_) => 0,
                     ActorId::Enemy(_) => 1,
@@ -358,6 +876,11 @@ impl BattleState {
     ) -> Vec<TurnResult> {
         let mut results = Vec::new();
 
+        // 蘇生対象がいなければMPを消費せずに終わる
+        if spell.target_type == SpellTarget::SingleDeadAlly && self.retarget_dead_ally(target).is_none() {
+            return results;
+        }
+
         // MP消費
         if !self.party[caster_idx].stats.use_mp(spell.mp_cost) {
             return results;
@@ -369,38 +892,12 @@ impl BattleState {
                     SpellTarget::SingleEnemy => {
                         let actual_target = self.retarget_enemy(target);
                         if let Some(TargetId::Enemy(ei)) = actual_target {
-                            let damage = spell::spell_damage(base_damage, self.enemies[ei].stats.defense, spell::DEFENSE_DIVISOR, random_factor);
-                            self.enemies[ei].stats.take_damage(damage);
-                            results.push(TurnResult::SpellDamage {
-                                caster: ActorId::Party(caster_idx),
-                                spell,
-                                target: TargetId::Enemy(ei),
-                                damage,
-                                blocked: 0,
-                            });
-                            if !self.enemies[ei].stats.is_alive() {
-                                results.push(TurnResult::Defeated {
-                                    target: TargetId::Enemy(ei),
-                                });
-                            }
+                            results.extend(self.spell_damage_enemy(caster_idx, spell, base_damage, ei, random_factor));
                         }
                     }
                     SpellTarget::AllEnemies => {
                         for ei in self.alive_enemy_indices() {
-                            let damage = spell::spell_damage(base_damage, self.enemies[ei].stats.defense, spell::DEFENSE_DIVISOR, random_factor);
-                            self.enemies[ei].stats.take_damage(damage);
-                            results.push(TurnResult::SpellDamage {
-                                caster: ActorId::Party(caster_idx),
-                                spell,
-                                target: TargetId::Enemy(ei),
-                                damage,
-                                blocked: 0,
-                            });
-                            if !self.enemies[ei].stats.is_alive() {
-                                results.push(TurnResult::Defeated {
-                                    target: TargetId::Enemy(ei),
-                                });
-                            }
+                            results.extend(self.spell_damage_enemy(caster_idx, spell, base_damage, ei, random_factor));
                         }
                     }
                     _ => {}
@@ -413,7 +910,7 @@ impl BattleState {
                         if let Some(TargetId::Party(pi)) = actual_target {
                             let amount = spell::heal_amount(base_heal, random_factor);
                             let member = &mut self.party[pi];
-                            member.stats.hp = (member.stats.hp + amount).min(member.stats.max_hp);
+                            member.stats.hp = (member.stats.hp + amount).min(member.effective_max_hp());
                             results.push(TurnResult::Healed {
                                 caster: ActorId::Party(caster_idx),
                                 spell,
@@ -426,7 +923,7 @@ impl BattleState {
                         for pi in self.alive_party_indices() {
                             let amount = spell::heal_amount(base_heal, random_factor);
                             let member = &mut self.party[pi];
-                            member.stats.hp = (member.stats.hp + amount).min(member.stats.max_hp);
+                            member.stats.hp = (member.stats.hp + amount).min(member.effective_max_hp());
                             results.push(TurnResult::Healed {
                                 caster: ActorId::Party(caster_idx),
                                 spell,
@@ -438,15 +935,13 @@ impl BattleState {
                     _ => {}
                 }
             }
-            SpellEffect::AttackBuff { amount } => {
+            SpellEffect::AttackBuff { .. } | SpellEffect::DefenseBuff { .. } | SpellEffect::SpeedBuff { .. } => {
+                let (stat, amount) = BuffStat::from_effect(spell.effect).expect("buff spell must change a stat");
                 match spell.target_type {
                     SpellTarget::SingleAlly => {
                         let actual_target = self.retarget_ally(target);
                         if let Some(TargetId::Party(pi)) = actual_target {
-                            self.party_buffs[pi].attack_up = Some(BuffState {
-                                amount,
-                                remaining_turns: BUFF_DURATION,
-                            });
+                            self.party_buffs[pi].apply(stat, amount);
                             results.push(TurnResult::Buffed {
                                 caster: ActorId::Party(caster_idx),
                                 spell,
@@ -457,14 +952,40 @@ impl BattleState {
                     }
                     SpellTarget::AllAllies => {
                         for pi in self.alive_party_indices() {
-                            self.party_buffs[pi].attack_up = Some(BuffState {
+                            self.party_buffs[pi].apply(stat, amount);
+                            results.push(TurnResult::Buffed {
+                                caster: ActorId::Party(caster_idx),
+                                spell,
+                                target: TargetId::Party(pi),
                                 amount,
-                                remaining_turns: BUFF_DURATION,
                             });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            SpellEffect::AttackDebuff { .. } | SpellEffect::DefenseDebuff { .. } => {
+                let (stat, amount) = BuffStat::from_effect(spell.effect).expect("debuff spell must change a stat");
+                match spell.target_type {
+                    SpellTarget::SingleEnemy => {
+                        let actual_target = self.retarget_enemy(target);
+                        if let Some(TargetId::Enemy(ei)) = actual_target {
+                            self.enemy_buffs[ei].apply(stat, amount);
                             results.push(TurnResult::Buffed {
                                 caster: ActorId::Party(caster_idx),
                                 spell,
-                                target: TargetId::Party(pi),
+                                target: TargetId::Enemy(ei),
+                                amount,
+                            });
+                        }
+                    }
+                    SpellTarget::AllEnemies => {
+                        for ei in self.alive_enemy_indices() {
+                            self.enemy_buffs[ei].apply(stat, amount);
+                            results.push(TurnResult::Buffed {
+                                caster: ActorId::Party(caster_idx),
+                                spell,
+                                target: TargetId::Enemy(ei),
                                 amount,
                             });
                         }
@@ -575,6 +1096,45 @@ impl BattleState {
                     _ => {}
                 }
             }
+            SpellEffect::Cure { ailments } => {
+                match spell.target_type {
+                    SpellTarget::SingleAlly => {
+                        let actual_target = self.retarget_ally(target);
+                        if let Some(TargetId::Party(pi)) = actual_target {
+                            let cured = self.cure_party_ailments(pi, ailments);
+                            results.push(TurnResult::Cured {
+                                user: ActorId::Party(caster_idx),
+                                source: SupportSource::Spell(spell),
+                                target: TargetId::Party(pi),
+                                ailments: cured,
+                            });
+                        }
+                    }
+                    SpellTarget::AllAllies => {
+                        for pi in self.alive_party_indices() {
+                            let cured = self.cure_party_ailments(pi, ailments);
+                            results.push(TurnResult::Cured {
+                                user: ActorId::Party(caster_idx),
+                                source: SupportSource::Spell(spell),
+                                target: TargetId::Party(pi),
+                                ailments: cured,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            SpellEffect::Revive { hp_percent } => {
+                if let Some(TargetId::Party(pi)) = self.retarget_dead_ally(target) {
+                    let hp = self.revive_member(pi, hp_percent);
+                    results.push(TurnResult::Revived {
+                        user: ActorId::Party(caster_idx),
+                        source: SupportSource::Spell(spell),
+                        target: TargetId::Party(pi),
+                        hp,
+                    });
+                }
+            }
         }
 
         results
@@ -600,7 +1160,7 @@ impl BattleState {
                 if let Some(TargetId::Party(pi)) = actual_target {
                     let amount = spell::heal_amount(power, random_factor);
                     let member = &mut self.party[pi];
-                    member.stats.hp = (member.stats.hp + amount).min(member.stats.max_hp);
+                    member.stats.hp = (member.stats.hp + amount).min(member.effective_max_hp());
                     results.push(TurnResult::ItemUsed {
                         user: ActorId::Party(user_idx),
                         item,
@@ -609,14 +1169,77 @@ impl BattleState {
                     });
                 }
             }
-            ItemEffect::KeyItem | ItemEffect::Material => {}
-
-        }
-
-        results
-    }
-
-    /// ターゲットの味方が既に倒されていたら最初の生存味方にリターゲット
+            ItemEffect::Revive { hp_percent } => {
+                let Some(TargetId::Party(pi)) = self.retarget_dead_ally(target) else {
+                    return results;
+                };
+                if !self.party[user_idx].inventory.use_item(item) {
+                    return results;
+                }
+                let hp = self.revive_member(pi, hp_percent);
+                results.push(TurnResult::Revived {
+                    user: ActorId::Party(user_idx),
+                    source: SupportSource::Item(item),
+                    target: TargetId::Party(pi),
+                    hp,
+                });
+            }
+            ItemEffect::Cure { ailments } => {
+                if !self.party[user_idx].inventory.use_item(item) {
+                    return results;
+                }
+                if let Some(TargetId::Party(pi)) = self.retarget_ally(target) {
+                    let cured = self.cure_party_ailments(pi, ailments);
+                    results.push(TurnResult::Cured {
+                        user: ActorId::Party(user_idx),
+                        source: SupportSource::Item(item),
+                        target: TargetId::Party(pi),
+                        ailments: cured,
+                    });
+                }
+            }
+            // 逃走できる戦闘ではターン開始時に処理済み。ボス戦では消費せずに失敗する
+            ItemEffect::Escape if !self.can_flee && self.party[user_idx].inventory.count(item) > 0 => {
+                results.push(TurnResult::FleeBlocked);
+            }
+            // 持っていない逃走アイテムは何も起きない
+            ItemEffect::Escape | ItemEffect::KeyItem | ItemEffect::Material | ItemEffect::Equipment => {}
+        }
+
+        results
+    }
+
+    /// 味方の状態異常のうちailmentsに含まれるものを治し、治ったものを返す
+    fn cure_party_ailments(&mut self, pi: usize, ailments: &[Ailment]) -> Vec<Ailment> {
+        ailments
+            .iter()
+            .copied()
+            .filter(|&ailment| self.party_ailments[pi].cure(ailment))
+            .collect()
+    }
+
+    /// 戦闘不能の味方を復活させ、復活後のHPを返す（状態異常とバフは消える）
+    fn revive_member(&mut self, pi: usize, hp_percent: i32) -> i32 {
+        let member = &mut self.party[pi];
+        member.stats.hp = spell::revive_hp(member.effective_max_hp(), hp_percent);
+        self.party_ailments[pi] = ActorAilments::default();
+        self.party_buffs[pi] = ActorBuffs::default();
+        member.stats.hp
+    }
+
+    /// ターゲットの味方が生きていたら最初の戦闘不能の味方にリターゲット（いなければNone）
+    fn retarget_dead_ally(&self, target: TargetId) -> Option<TargetId> {
+        if let TargetId::Party(pi) = target
+            && !self.party[pi].stats.is_alive()
+        {
+            return Some(target);
+        }
+        self.dead_party_indices()
+            .first()
+            .map(|&i| TargetId::Party(i))
+    }
+
+    /// ターゲットの味方が既に倒されていたら最初の生存味方にリターゲット
     fn retarget_ally(&self, target: TargetId) -> Option<TargetId> {
         if let TargetId::Party(pi) = target {
             if self.party[pi].stats.is_alive() {
@@ -630,23 +1253,74 @@ impl BattleState {
         }
     }
 
+    /// 味方の攻撃呪文を敵1体に当てる（属性の相性込み）
+    fn spell_damage_enemy(
+        &mut self,
+        caster_idx: usize,
+        spell: SpellEntry,
+        base_damage: i32,
+        ei: usize,
+        random_factor: f32,
+    ) -> Vec<TurnResult> {
+        let mut results = Vec::new();
+        let target = TargetId::Enemy(ei);
+        let affinity = self.enemies[ei].kind.affinity(spell.element);
+        let raw_damage = spell::spell_damage(base_damage, self.actor_defense(ActorId::Enemy(ei)), spell::DEFENSE_DIVISOR, random_factor);
+        let damage = spell::elemental_damage(raw_damage, affinity);
+        let stats = &mut self.enemies[ei].stats;
+        let healed = if damage < 0 {
+            let before = stats.hp;
+            stats.hp = (stats.hp - damage).min(stats.max_hp);
+            stats.hp - before
+        } else {
+            stats.take_damage(damage);
+            0
+        };
+        results.push(TurnResult::SpellDamage {
+            caster: ActorId::Party(caster_idx),
+            spell,
+            target,
+            damage: damage.max(0),
+            blocked: 0,
+        });
+        if affinity != Affinity::Normal {
+            results.push(TurnResult::ElementReaction { target, affinity, healed });
+        }
+        if !self.enemies[ei].stats.is_alive() {
+            results.push(TurnResult::Defeated { target });
+        }
+        results
+    }
+
     /// パーティメンバーが敵を攻撃
     fn execute_party_attack(
         &mut self,
         party_idx: usize,
         target: TargetId,
-        random_factor: f32,
+        rolls: AttackRolls,
     ) -> Vec<TurnResult> {
         let mut results = Vec::new();
         if let TargetId::Enemy(ei) = target {
-            let damage = CombatStats::calculate_damage(
-                self.effective_attack_with_buff(party_idx),
-                self.enemies[ei].stats.defense,
-                random_factor,
-            );
+            let attacker = ActorId::Party(party_idx);
+            let attack = self.effective_attack_with_buff(party_idx);
+            let blind = self.party_ailments[party_idx].has(Ailment::Blind);
+            let mut damage = match rolls.outcome(self.actor_speed(attacker), self.actor_speed(target.actor()), blind) {
+                HitOutcome::Miss => {
+                    results.push(TurnResult::Missed { attacker, target });
+                    return results;
+                }
+                HitOutcome::Critical => {
+                    results.push(TurnResult::Critical { attacker });
+                    CombatStats::calculate_critical_damage(attack, rolls.damage)
+                }
+                HitOutcome::Hit => CombatStats::calculate_damage(attack, self.actor_defense(ActorId::Enemy(ei)), rolls.damage),
+            };
+            if self.party_row(party_idx) == Row::Back {
+                damage = back_row_damage(damage);
+            }
             self.enemies[ei].stats.take_damage(damage);
             results.push(TurnResult::Attack {
-                attacker: ActorId::Party(party_idx),
+                attacker,
                 target,
                 damage,
                 blocked: 0,
@@ -660,26 +1334,91 @@ impl BattleState {
         results
     }
 
-    /// 敵の行動選択: 呪文使用可能なら50%で呪文、それ以外は物理攻撃
+    /// 敵の行動選択: HPが減れば回復呪文、それ以外はAIのspell_rateの確率で重み付き呪文、外れたら物理攻撃（沈黙中は物理攻撃のみ）
     fn execute_enemy_action(
         &mut self,
         enemy_idx: usize,
-        random_factor: f32,
+        rolls: AttackRolls,
         spell_random: f32,
+        target_random: f32,
     ) -> Vec<TurnResult> {
-        let spells = &self.enemies[enemy_idx].spells;
-        if !spells.is_empty() && spell_random < 0.5 {
-            // 使用可能な呪文からMP足りるものを選択
-            let usable: Vec<SpellEntry> = spells
+        let enemy = &self.enemies[enemy_idx];
+        let profile = enemy.kind.ai().profile(enemy.stats.hp, enemy.stats.max_hp);
+        let mp = enemy.stats.mp;
+
+        // 沈黙中は呪文を使わずに殴る
+        if self.enemy_ailments[enemy_idx].has(Ailment::Silence) {
+            return self.execute_enemy_attack(enemy_idx, rolls, target_random);
+        }
+
+        // HPが減っていれば回復呪文を優先
+        if profile.heal_below_percent > 0
+            && enemy.stats.hp * 100 <= enemy.stats.max_hp * profile.heal_below_percent
+        {
+            let heal = enemy
+                .spells
                 .iter()
-                .filter(|s| self.enemies[enemy_idx].stats.mp >= s.mp_cost)
+                .find(|s| matches!(s.effect, SpellEffect::Heal { .. }) && mp >= s.mp_cost)
+                .copied();
+            if let Some(spell) = heal {
+                return self.execute_enemy_spell(enemy_idx, spell, rolls, target_random);
+            }
+        }
+
+        if spell_random < profile.spell_rate {
+            // MPが足りる呪文から重み付きで選択
+            let usable: Vec<WeightedSpell> = profile
+                .spells
+                .iter()
+                .filter(|c| mp >= c.spell.mp_cost)
                 .copied()
                 .collect();
-            if let Some(&spell) = usable.first() {
-                return self.execute_enemy_spell(enemy_idx, spell, random_factor);
+            if let Some(spell) = enemy::pick_spell(&usable, spell_random / profile.spell_rate) {
+                return self.execute_enemy_spell(enemy_idx, spell, rolls, target_random);
+            }
+        }
+        self.execute_enemy_attack(enemy_idx, rolls, target_random)
+    }
+
+    /// 敵AIの狙い方に従って攻撃対象のパーティメンバーを決める
+    fn choose_party_target(&self, enemy_idx: usize, target_random: f32) -> Option<usize> {
+        let enemy = &self.enemies[enemy_idx];
+        let profile = enemy.kind.ai().profile(enemy.stats.hp, enemy.stats.max_hp);
+        let alive_party = self.alive_party_indices();
+        if alive_party.is_empty() {
+            return None;
+        }
+        if profile.focus_sleeping
+            && let Some(&pi) = alive_party.iter().find(|&&pi| self.party_ailments[pi].incapacitating().is_some())
+        {
+            return Some(pi);
+        }
+        match profile.targeting {
+            TargetPolicy::First => alive_party
+                .iter()
+                .copied()
+                .find(|&pi| self.party_row(pi) == Row::Front)
+                .or(Some(alive_party[0])),
+            TargetPolicy::Random => {
+                // 前列ほど狙われやすい重み付き抽選
+                let weight = |pi: usize| match self.party_row(pi) {
+                    Row::Front => FRONT_ROW_TARGET_WEIGHT,
+                    Row::Back => BACK_ROW_TARGET_WEIGHT,
+                };
+                let total: usize = alive_party.iter().map(|&pi| weight(pi)).sum();
+                let mut selected = ((target_random * total as f32) as usize).min(total - 1);
+                alive_party.iter().copied().find(|&pi| {
+                    let w = weight(pi);
+                    if selected < w {
+                        true
+                    } else {
+                        selected -= w;
+                        false
+                    }
+                })
             }
+            TargetPolicy::LowestHp => alive_party.iter().copied().min_by_key(|&pi| self.party[pi].stats.hp),
         }
-        self.execute_enemy_attack(enemy_idx, random_factor)
     }
 
     /// 敵の呪文実行（ターゲット方向を逆転: Damage→パーティ, Heal→自身）
@@ -687,23 +1426,24 @@ impl BattleState {
         &mut self,
         enemy_idx: usize,
         spell: SpellEntry,
-        random_factor: f32,
+        rolls: AttackRolls,
+        target_random: f32,
     ) -> Vec<TurnResult> {
         let mut results = Vec::new();
+        let random_factor = rolls.damage;
 
         // MP消費
         if !self.enemies[enemy_idx].stats.use_mp(spell.mp_cost) {
-            return self.execute_enemy_attack(enemy_idx, random_factor);
+            return self.execute_enemy_attack(enemy_idx, rolls, target_random);
         }
 
         match spell.effect {
             SpellEffect::Damage { base_damage } => {
                 match spell.target_type {
                     SpellTarget::SingleEnemy => {
-                        // 敵から見て「敵」= パーティメンバー → 先頭の生存メンバーを攻撃
-                        let alive_party = self.alive_party_indices();
-                        if let Some(&pi) = alive_party.first() {
-                            let raw_damage = spell::spell_damage(base_damage, self.party[pi].stats.defense, spell::DEFENSE_DIVISOR, random_factor);
+                        // 敵から見て「敵」= パーティメンバー → AIが選んだメンバーを攻撃
+                        if let Some(pi) = self.choose_party_target(enemy_idx, target_random) {
+                            let raw_damage = spell::spell_damage(base_damage, self.actor_defense(ActorId::Party(pi)), spell::DEFENSE_DIVISOR, random_factor);
                             let (damage, blocked) = self.apply_block(pi, raw_damage);
                             self.party[pi].stats.take_damage(damage);
                             results.push(TurnResult::SpellDamage {
@@ -723,7 +1463,7 @@ impl BattleState {
                     SpellTarget::AllEnemies => {
                         // 敵から見て「全体敵」= パーティ全員
                         for pi in self.alive_party_indices() {
-                            let raw_damage = spell::spell_damage(base_damage, self.party[pi].stats.defense, spell::DEFENSE_DIVISOR, random_factor);
+                            let raw_damage = spell::spell_damage(base_damage, self.actor_defense(ActorId::Party(pi)), spell::DEFENSE_DIVISOR, random_factor);
                             let (damage, blocked) = self.apply_block(pi, raw_damage);
                             self.party[pi].stats.take_damage(damage);
                             results.push(TurnResult::SpellDamage {
@@ -758,9 +1498,8 @@ impl BattleState {
             SpellEffect::MpDrain { base_drain } => {
                 match spell.target_type {
                     SpellTarget::SingleEnemy => {
-                        // 敵から見て「敵」= パーティメンバー → 先頭の生存メンバーのMP減少
-                        let alive_party = self.alive_party_indices();
-                        if let Some(&pi) = alive_party.first() {
+                        // 敵から見て「敵」= パーティメンバー → AIが選んだメンバーのMP減少
+                        if let Some(pi) = self.choose_party_target(enemy_idx, target_random) {
                             let amount = spell::mp_drain_amount(base_drain, random_factor);
                             self.party[pi].stats.drain_mp(amount);
                             results.push(TurnResult::MpDrained {
@@ -791,8 +1530,7 @@ impl BattleState {
                 let ailment = spell.ailment.expect("Ailment spell must have ailment");
                 match spell.target_type {
                     SpellTarget::SingleEnemy => {
-                        let alive_party = self.alive_party_indices();
-                        if let Some(&pi) = alive_party.first() {
+                        if let Some(pi) = self.choose_party_target(enemy_idx, target_random) {
                             if spell::ailment_success(success_rate, random_factor) {
                                 self.apply_ailment_to_party(pi, ailment);
                                 results.push(TurnResult::AilmentInflicted {
@@ -832,32 +1570,89 @@ impl BattleState {
                     _ => {}
                 }
             }
-            // バフ呪文は敵には未実装
+            SpellEffect::AttackBuff { .. } | SpellEffect::DefenseBuff { .. } | SpellEffect::SpeedBuff { .. } => {
+                // 単体なら自身、全体なら敵全員にかける
+                let (stat, amount) = BuffStat::from_effect(spell.effect).expect("buff spell must change a stat");
+                let targets = match spell.target_type {
+                    SpellTarget::AllAllies => self.alive_enemy_indices(),
+                    _ => vec![enemy_idx],
+                };
+                for ei in targets {
+                    self.enemy_buffs[ei].apply(stat, amount);
+                    results.push(TurnResult::Buffed {
+                        caster: ActorId::Enemy(enemy_idx),
+                        spell,
+                        target: TargetId::Enemy(ei),
+                        amount,
+                    });
+                }
+            }
+            SpellEffect::AttackDebuff { .. } | SpellEffect::DefenseDebuff { .. } => {
+                let (stat, amount) = BuffStat::from_effect(spell.effect).expect("debuff spell must change a stat");
+                let targets = match spell.target_type {
+                    SpellTarget::AllEnemies => self.alive_party_indices(),
+                    _ => self.choose_party_target(enemy_idx, target_random).into_iter().collect(),
+                };
+                for pi in targets {
+                    self.party_buffs[pi].apply(stat, amount);
+                    results.push(TurnResult::Buffed {
+                        caster: ActorId::Enemy(enemy_idx),
+                        spell,
+                        target: TargetId::Party(pi),
+                        amount,
+                    });
+                }
+            }
+            // ブロック・蘇生・状態異常回復は敵には未実装
             _ => {}
         }
 
         results
     }
 
-    /// 敵がランダムなパーティメンバーを攻撃（最初の生存メンバーをターゲット）
-    fn execute_enemy_attack(&mut self, enemy_idx: usize, random_factor: f32) -> Vec<TurnResult> {
+    /// 敵がAIの選んだパーティメンバーを攻撃
+    fn execute_enemy_attack(
+        &mut self,
+        enemy_idx: usize,
+        rolls: AttackRolls,
+        target_random: f32,
+    ) -> Vec<TurnResult> {
+        match self.choose_party_target(enemy_idx, target_random) {
+            Some(target_idx) => self.execute_enemy_attack_on(enemy_idx, target_idx, rolls),
+            None => Vec::new(),
+        }
+    }
+
+    /// 敵が指定のパーティメンバーを攻撃
+    fn execute_enemy_attack_on(
+        &mut self,
+        enemy_idx: usize,
+        target_idx: usize,
+        rolls: AttackRolls,
+    ) -> Vec<TurnResult> {
         let mut results = Vec::new();
-        let alive_party = self.alive_party_indices();
-        if alive_party.is_empty() {
-            return results;
+        let attacker = ActorId::Enemy(enemy_idx);
+        let target = TargetId::Party(target_idx);
+        let attack = self.actor_attack(attacker);
+        let blind = self.enemy_ailments[enemy_idx].has(Ailment::Blind);
+        let mut raw_damage = match rolls.outcome(self.actor_speed(attacker), self.actor_speed(target.actor()), blind) {
+            HitOutcome::Miss => {
+                results.push(TurnResult::Missed { attacker, target });
+                return results;
+            }
+            HitOutcome::Critical => {
+                results.push(TurnResult::Critical { attacker });
+                CombatStats::calculate_critical_damage(attack, rolls.damage)
+            }
+            HitOutcome::Hit => CombatStats::calculate_damage(attack, self.actor_defense(target.actor()), rolls.damage),
+        };
+        if self.party_row(target_idx) == Row::Back {
+            raw_damage = back_row_damage(raw_damage);
         }
-        // 簡易: 最初の生存パーティメンバーを攻撃
-        let target_idx = alive_party[0];
-        let raw_damage = CombatStats::calculate_damage(
-            self.enemies[enemy_idx].stats.attack,
-            self.party[target_idx].stats.defense,
-            random_factor,
-        );
         let (damage, blocked) = self.apply_block(target_idx, raw_damage);
         self.party[target_idx].stats.take_damage(damage);
-        let target = TargetId::Party(target_idx);
         results.push(TurnResult::Attack {
-            attacker: ActorId::Enemy(enemy_idx),
+            attacker,
             target,
             damage,
             blocked,
@@ -872,18 +1667,102 @@ impl BattleState {
 
     /// 敵に状態異常を付与
     fn apply_ailment_to_enemy(&mut self, ei: usize, ailment: Ailment) {
-        match ailment {
-            Ailment::Sleep => self.enemy_ailments[ei].sleep = true,
-            Ailment::Poison => self.enemy_ailments[ei].poison = true,
-        }
+        self.enemy_ailments[ei].inflict(ailment);
     }
 
     /// 味方に状態異常を付与
     fn apply_ailment_to_party(&mut self, pi: usize, ailment: Ailment) {
-        match ailment {
-            Ailment::Sleep => self.party_ailments[pi].sleep = true,
-            Ailment::Poison => self.party_ailments[pi].poison = true,
+        self.party_ailments[pi].inflict(ailment);
+    }
+
+    /// アクターの状態異常
+    fn actor_ailments(&self, actor: ActorId) -> &ActorAilments {
+        match actor {
+            ActorId::Party(pi) => &self.party_ailments[pi],
+            ActorId::Enemy(ei) => &self.enemy_ailments[ei],
+        }
+    }
+
+    /// 眠り・まひで行動できなければ、その旨の結果を返す
+    fn incapacitated_result(&self, actor: ActorId) -> Option<TurnResult> {
+        self.actor_ailments(actor).incapacitating().map(|ailment| match ailment {
+            Ailment::Sleep => TurnResult::Sleeping { actor },
+            _ => TurnResult::Paralyzed { actor },
+        })
+    }
+
+    /// 混乱したアクターが敵味方の区別なく、自分以外の誰かを攻撃する
+    fn execute_confused_attack(
+        &mut self,
+        actor: ActorId,
+        rolls: AttackRolls,
+        confusion_random: f32,
+    ) -> Vec<TurnResult> {
+        let mut results = vec![TurnResult::Confused { actor }];
+        let candidates: Vec<TargetId> = self
+            .alive_enemy_indices()
+            .into_iter()
+            .filter(|&ei| actor != ActorId::Enemy(ei))
+            .map(TargetId::Enemy)
+            .chain(
+                self.alive_party_indices()
+                    .into_iter()
+                    .filter(|&pi| actor != ActorId::Party(pi))
+                    .map(TargetId::Party),
+            )
+            .collect();
+        if candidates.is_empty() {
+            return results;
+        }
+        let target = candidates[((confusion_random * candidates.len() as f32) as usize).min(candidates.len() - 1)];
+        results.extend(match (actor, target) {
+            (ActorId::Party(pi), TargetId::Enemy(_)) => self.execute_party_attack(pi, target, rolls),
+            (ActorId::Enemy(ei), TargetId::Party(pi)) => self.execute_enemy_attack_on(ei, pi, rolls),
+            _ => self.execute_friendly_fire(actor, target, rolls),
+        });
+        results
+    }
+
+    /// 混乱したアクターが同じ陣営の仲間を攻撃する
+    fn execute_friendly_fire(&mut self, attacker: ActorId, target: TargetId, rolls: AttackRolls) -> Vec<TurnResult> {
+        let mut results = Vec::new();
+        let attack = self.actor_attack(attacker);
+        let defense = self.actor_defense(target.actor());
+        let blind = self.actor_ailments(attacker).has(Ailment::Blind);
+        let raw_damage = match rolls.outcome(self.actor_speed(attacker), self.actor_speed(target.actor()), blind) {
+            HitOutcome::Miss => {
+                results.push(TurnResult::Missed { attacker, target });
+                return results;
+            }
+            HitOutcome::Critical => {
+                results.push(TurnResult::Critical { attacker });
+                CombatStats::calculate_critical_damage(attack, rolls.damage)
+            }
+            HitOutcome::Hit => CombatStats::calculate_damage(attack, defense, rolls.damage),
+        };
+        let (damage, blocked, alive) = match target {
+            TargetId::Party(pi) => {
+                let (damage, blocked) = self.apply_block(pi, raw_damage);
+                self.party[pi].stats.take_damage(damage);
+                (damage, blocked, self.party[pi].stats.is_alive())
+            }
+            TargetId::Enemy(ei) => {
+                self.enemies[ei].stats.take_damage(raw_damage);
+                (raw_damage, 0, self.enemies[ei].stats.is_alive())
+            }
+        };
+        results.push(TurnResult::Attack {
+            attacker,
+            target,
+            damage,
+            blocked,
+        });
+        if !alive {
+            results.push(TurnResult::Defeated { target });
+        } else if let Some(cure) = self.wake_up_if_sleeping(&target) {
+            results.push(cure);
         }
+        results
     }
 
     /// ターン終了時の毒ダメージ処理
@@ -892,7 +1771,7 @@ impl BattleState {
         let poison_damage = spell::POISON_DAMAGE;
 
         for pi in 0..self.party.len() {
-            if self.party[pi].stats.is_alive() && self.party_ailments[pi].poison {
+            if self.party[pi].stats.is_alive() && self.party_ailments[pi].has(Ailment::Poison) {
                 self.party[pi].stats.take_damage(poison_damage);
                 results.push(TurnResult::PoisonDamage {
                     target: TargetId::Party(pi),
@@ -907,7 +1786,7 @@ impl BattleState {
         }
 
         for ei in 0..self.enemies.len() {
-            if self.enemies[ei].stats.is_alive() && self.enemy_ailments[ei].poison {
+            if self.enemies[ei].stats.is_alive() && self.enemy_ailments[ei].has(Ailment::Poison) {
                 self.enemies[ei].stats.take_damage(poison_damage);
                 results.push(TurnResult::PoisonDamage {
                     target: TargetId::Enemy(ei),
@@ -924,49 +1803,103 @@ impl BattleState {
         results
     }
 
-    /// 攻撃を受けた対象の眠りを解除
-    fn wake_up_if_sleeping(&mut self, target: &TargetId) -> Option<TurnResult> {
-        match target {
-            TargetId::Enemy(ei) => {
-                if self.enemy_ailments[*ei].sleep {
-                    self.enemy_ailments[*ei].sleep = false;
-                    Some(TurnResult::AilmentCured {
-                        target: *target,
-                        ailment: Ailment::Sleep,
-                    })
-                } else {
-                    None
-                }
+    /// ターン終了時の状態異常の経過（持続ターン切れ・自然回復）
+    fn tick_ailments(&mut self, recovery_randoms: &[f32]) -> Vec<TurnResult> {
+        let mut results = Vec::new();
+        let party_count = self.party.len();
+
+        for pi in 0..party_count {
+            if !self.party[pi].stats.is_alive() {
+                continue;
             }
-            TargetId::Party(pi) => {
-                if self.party_ailments[*pi].sleep {
-                    self.party_ailments[*pi].sleep = false;
-                    Some(TurnResult::AilmentCured {
-                        target: *target,
-                        ailment: Ailment::Sleep,
-                    })
-                } else {
-                    None
+            let random = recovery_randoms.get(pi).copied().unwrap_or(1.0);
+            for ailment in self.party_ailments[pi].tick(random) {
+                results.push(TurnResult::AilmentCured {
+                    target: TargetId::Party(pi),
+                    ailment,
+                });
+            }
+        }
+
+        for ei in 0..self.enemies.len() {
+            if !self.enemies[ei].stats.is_alive() {
+                continue;
+            }
+            let random = recovery_randoms.get(party_count + ei).copied().unwrap_or(1.0);
+            for ailment in self.enemy_ailments[ei].tick(random) {
+                results.push(TurnResult::AilmentCured {
+                    target: TargetId::Enemy(ei),
+                    ailment,
+                });
+            }
+        }
+
+        results
+    }
+
+    /// ボスのHPがしきい値を下回っていたら台本の出来事を起こす
+    fn run_boss_script(&mut self) -> Vec<TurnResult> {
+        let mut results = Vec::new();
+        let Some(boss) = self.boss.as_mut() else {
+            return results;
+        };
+        let boss_index = boss.boss_index;
+        let stats = &self.enemies[boss_index].stats;
+        if !stats.is_alive() {
+            return results;
+        }
+
+        for phase in boss.advance(stats.hp, stats.max_hp) {
+            for event in phase.events {
+                match *event {
+                    BossEvent::Message(message) => {
+                        results.push(TurnResult::BossMessage { message: message.to_string() });
+                    }
+                    BossEvent::Summon(kind, tier) => {
+                        if self.enemies.len() < MAX_ENEMIES {
+                            self.enemies.push(Enemy::new(kind, tier));
+                            self.enemy_ailments.push(ActorAilments::default());
+                            self.enemy_buffs.push(ActorBuffs::default());
+                            results.push(TurnResult::Summoned {
+                                enemy: self.enemies.len() - 1,
+                            });
+                        }
+                    }
+                    BossEvent::Transform { max_hp, attack, defense, speed } => {
+                        let boss_stats = &mut self.enemies[boss_index].stats;
+                        boss_stats.max_hp = max_hp;
+                        boss_stats.hp = max_hp;
+                        boss_stats.attack = attack;
+                        boss_stats.defense = defense;
+                        boss_stats.speed = speed;
+                        self.enemy_ailments[boss_index] = ActorAilments::default();
+                        self.enemy_buffs[boss_index] = ActorBuffs::default();
+                        results.push(TurnResult::Transformed { enemy: boss_index });
+                    }
                 }
             }
         }
+        results
+    }
+
+    /// 攻撃を受けた対象の眠りを解除
+    fn wake_up_if_sleeping(&mut self, target: &TargetId) -> Option<TurnResult> {
+        let ailments = match target {
+            TargetId::Enemy(ei) => &mut self.enemy_ailments[*ei],
+            TargetId::Party(pi) => &mut self.party_ailments[*pi],
+        };
+        ailments.cure(Ailment::Sleep).then_some(TurnResult::AilmentCured {
+            target: *target,
+            ailment: Ailment::Sleep,
+        })
     }
 
-    /// ターン終了時にバフの残りターンをデクリメント、0になったバフを除去
+    /// ターン終了時にバフ・デバフの残りターンをデクリメント、0になったものを除去
     fn tick_buffs(&mut self) -> Vec<TurnResult> {
         let mut results = Vec::new();
 
         for pi in 0..self.party_buffs.len() {
-            if let Some(ref mut buff) = self.party_buffs[pi].attack_up {
-                buff.remaining_turns = buff.remaining_turns.saturating_sub(1);
-                if buff.remaining_turns == 0 {
-                    self.party_buffs[pi].attack_up = None;
-                    results.push(TurnResult::BuffExpired {
-                        target: TargetId::Party(pi),
-                        stat: BuffStat::Attack,
-                    });
-                }
-            }
+            results.extend(Self::tick_stat_changes(&mut self.party_buffs[pi], TargetId::Party(pi)));
             if self.party_buffs[pi].block > 0 {
                 self.party_buffs[pi].block /= 2;
                 results.push(TurnResult::BlockDecayed {
@@ -976,6 +1909,28 @@ impl BattleState {
             }
         }
 
+        for ei in 0..self.enemy_buffs.len() {
+            if self.enemies[ei].stats.is_alive() {
+                results.extend(Self::tick_stat_changes(&mut self.enemy_buffs[ei], TargetId::Enemy(ei)));
+            }
+        }
+
+        results
+    }
+
+    /// バフ・デバフの残りターンをデクリメントし、切れたものを除去
+    fn tick_stat_changes(buffs: &mut ActorBuffs, target: TargetId) -> Vec<TurnResult> {
+        let mut results = Vec::new();
+        for stat in BuffStat::ALL {
+            let slot = buffs.slot_mut(stat);
+            if let Some(buff) = slot.as_mut() {
+                buff.remaining_turns = buff.remaining_turns.saturating_sub(1);
+                if buff.remaining_turns == 0 {
+                    *slot = None;
+                    results.push(TurnResult::BuffExpired { target, stat });
+                }
+            }
+        }
         results
     }
 
@@ -1011,6 +1966,15 @@ impl BattleState {
             .collect()
     }
 
+    pub fn dead_party_indices(&self) -> Vec<usize> {
+        self.party
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.stats.is_alive())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     /// 倒した敵の合計経験値を計算（段階補正込み）
     pub fn total_exp_reward(&self) -> u32 {
         self.enemies
@@ -1019,6 +1983,29 @@ impl BattleState {
             .map(|e| e.exp_reward())
             .sum()
     }
+
+    /// 倒した敵から経験値・ゴールド・ドロップアイテムを決める
+    pub fn calculate_rewards(&self, random: &RewardRandomFactors) -> BattleRewards {
+        let mut rewards = BattleRewards {
+            exp: self.total_exp_reward(),
+            ..Default::default()
+        };
+        for (i, enemy) in self.enemies.iter().enumerate() {
+            if enemy.stats.is_alive() {
+                continue;
+            }
+            let gold_random = random.gold_randoms.get(i).copied().unwrap_or(0.0);
+            rewards.gold += enemy.roll_gold(gold_random);
+            if let Some(item) = random
+                .drop_randoms
+                .get(i)
+                .and_then(|&r| enemy.roll_drop(r))
+            {
+                rewards.items.push(item);
+            }
+        }
+        rewards
+    }
 }
 
 #[cfg(test)]
@@ -1026,7 +2013,7 @@ mod tests {
     use super::*;
     use enemy::{Enemy, EnemyKind};
     
-    use party::{default_party, CharacterParamTable, CharacterEntry, StatGrowth, RecruitmentPath, PartyMember, PartyMemberKind};
+    use party::{default_party, CharacterParamTable, CharacterEntry, EquipRule, StatGrowth, RecruitmentPath, PartyMember, PartyMemberKind};
 
     fn char_table() -> CharacterParamTable {
         CharacterParamTable::from_fn(|kind| match kind {
@@ -1048,6 +2035,7 @@ mod tests {
                     (1, spell_data::SLEEP1), (1, spell_data::SLEEPALL1),
                     (1, spell_data::POISON1), (1, spell_data::POISONALL1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Marcille => CharacterEntry {
                 initial_stats: CombatStats::new(20, 2, 2, 7, 15),
@@ -1062,6 +2050,7 @@ mod tests {
                     (9, spell_data::DRAIN1),
                     (10, spell_data::SLEEPALL1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Falin => CharacterEntry {
                 initial_stats: CombatStats::new(25, 5, 4, 4, 12),
@@ -1075,6 +2064,7 @@ mod tests {
                     (9, spell_data::HEALALL2),
                     (10, spell_data::BARRIER2),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Senshi => CharacterEntry {
                 initial_stats: CombatStats::new(40, 7, 6, 2, 3),
@@ -1083,6 +2073,7 @@ mod tests {
                 spell_learn_table: const { &[
                     (4, spell_data::SHIELD1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Rinsha => CharacterEntry {
                 initial_stats: CombatStats::new(24, 5, 3, 6, 8),
@@ -1097,12 +2088,14 @@ mod tests {
                     (8, spell_data::POISON1),
                     (9, spell_data::RALLY2),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             _ => CharacterEntry {
                 initial_stats: CombatStats::new(20, 5, 2, 5, 0),
                 stat_growth: StatGrowth { hp: 3, mp: 0, attack: 1, defense: 1, speed: 1 },
                 recruit_method: RecruitmentPath::TavernBond,
                 spell_learn_table: &[],
+                equip_rule: EquipRule::Any,
             },
         })
     }
@@ -1113,9 +2106,42 @@ mod tests {
             damage_randoms,
             flee_random,
             spell_randoms: vec![1.0; 10], // 1.0 = 呪文不使用（既存テスト互換）
+            ..Default::default()
         }
     }
 
+    /// パーティ1人と敵1体の戦闘で1ターン実行する（`prepare`でターン前の状態を整える）
+    fn duel_turn(
+        member: PartyMember,
+        enemy: Enemy,
+        actions: &[BattleAction],
+        random_factors: &TurnRandomFactors,
+        prepare: impl FnOnce(&mut BattleState),
+    ) -> (Vec<TurnResult>, BattleState) {
+        let mut battle = BattleState::new(vec![member], vec![enemy]);
+        prepare(&mut battle);
+        let results = battle.execute_turn(actions, random_factors);
+        (results, battle)
+    }
+
+    /// 敵の攻撃のダメージとブロック量
+    fn enemy_attack_of(results: &[TurnResult]) -> (i32, i32) {
+        results
+            .iter()
+            .find_map(|r| match r {
+                TurnResult::Attack { attacker: ActorId::Enemy(0), damage, blocked, .. } => Some((*damage, *blocked)),
+                _ => None,
+            })
+            .expect("敵が攻撃するはず")
+    }
+
+    /// 倒れないように HP を 999 にした敵
+    fn sturdy(mut enemy: Enemy) -> Enemy {
+        enemy.stats.hp = 999;
+        enemy.stats.max_hp = 999;
+        enemy
+    }
+
     fn make_random_with_spells(
         damage_randoms: Vec<f32>,
         flee_random: f32,
@@ -1125,6 +2151,7 @@ mod tests {
             damage_randoms,
             flee_random,
             spell_randoms,
+            ..Default::default()
         }
     }
 
@@ -1240,7 +2267,7 @@ mod tests {
                 target: TargetId::Enemy(0),
             },
         ];
-        let randoms = make_random(vec![1.0], 0.3); // 0.3 < 0.5 → 成功
+        let randoms = make_random(vec![1.0], 0.3); // 0.3 < 逃走成功率 → 成功
         let results = battle.execute_turn(&commands, &randoms);
 
         assert_eq!(results, vec![TurnResult::Fled]);
@@ -1263,7 +2290,7 @@ mod tests {
                 target: TargetId::Enemy(0),
             },
         ];
-        let randoms = make_random(vec![1.0], 0.7); // 0.7 >= 0.5 → 失敗
+        let randoms = make_random(vec![1.0], 0.7); // 0.7 >= 逃走成功率 → 失敗
         let results = battle.execute_turn(&commands, &randoms);
 
         assert!(matches!(results[0], TurnResult::FleeFailed));
@@ -1282,47 +2309,194 @@ mod tests {
             .count();
         assert!(enemy_attacks > 0);
         assert!(!battle.is_over());
+        assert_eq!(battle.flee_attempts, 1);
     }
 
     #[test]
-    fn victory_detection() {
+    fn flee_chance_depends_on_speed_and_failures() {
         let table = char_table();
-        let party = default_party(&table);
-        let enemies = vec![Enemy::slime()];
-        let mut battle = BattleState::new(party, enemies);
-
-        // 敵を倒す
-        battle.enemies[0].stats.hp = 0;
-        assert!(battle.is_victory());
-        assert!(battle.is_over());
-        assert!(!battle.is_party_wiped());
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut wolf = Enemy::wolf();
+        wolf.stats.speed = party[0].stats.speed;
+        let mut battle = BattleState::new(party, vec![wolf]);
+        assert_eq!(battle.flee_chance(), FLEE_BASE_CHANCE);
+
+        // 敵が速いほど逃げにくい
+        battle.enemies[0].stats.speed += 4;
+        let slow_chance = battle.flee_chance();
+        assert!((slow_chance - (FLEE_BASE_CHANCE - 4.0 * FLEE_SPEED_FACTOR)).abs() < 1e-6);
+
+        // 失敗するたびに逃げやすくなる
+        battle.flee_attempts = 2;
+        assert!((battle.flee_chance() - (slow_chance + 2.0 * FLEE_RETRY_BONUS)).abs() < 1e-6);
+
+        // 圧倒的に遅くても下限は残る
+        battle.flee_attempts = 0;
+        battle.enemies[0].stats.speed += 100;
+        assert_eq!(battle.flee_chance(), FLEE_MIN_CHANCE);
     }
 
     #[test]
-    fn party_wipe_detection() {
+    fn cannot_flee_from_boss_battle() {
         let table = char_table();
-        let party = default_party(&table);
-        let enemies = vec![Enemy::slime()];
-        let mut battle = BattleState::new(party, enemies);
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut battle = BattleState::new(party, vec![Enemy::slime()]);
+        battle.can_flee = false;
 
-        // 全員倒す
-        for member in &mut battle.party {
-            member.stats.hp = 0;
-        }
-        assert!(battle.is_party_wiped());
-        assert!(battle.is_over());
-        assert!(!battle.is_victory());
+        let results = battle.execute_turn(&[BattleAction::Flee], &make_random(vec![1.0], 0.0));
+
+        assert_eq!(results[0], TurnResult::FleeBlocked);
+        assert!(!battle.is_over());
+        assert_eq!(battle.flee_chance(), 0.0);
     }
 
     #[test]
-    fn zola_spell_damages_enemy() {
+    fn enemy_only_turn_checks_ailments_and_ends_turn() {
         let table = char_table();
-        let party = default_party(&table);
-        let enemies = vec![Enemy::slime()];
-        let mut battle = BattleState::new(party, enemies);
-
-        let mage_mp_before = battle.party[1].stats.mp;
-        let commands = vec![
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.can_flee = false;
+        battle.enemy_ailments[0].inflict(Ailment::Sleep);
+        battle.enemy_ailments[0].inflict(Ailment::Poison);
+
+        let results = battle.execute_turn(&[BattleAction::Flee], &make_random(vec![1.0], 0.0));
+
+        assert_eq!(results[0], TurnResult::FleeBlocked);
+        assert!(results.contains(&TurnResult::Sleeping { actor: ActorId::Enemy(0) }));
+        assert!(
+            !results.iter().any(|r| matches!(r, TurnResult::Attack { attacker: ActorId::Enemy(0), .. })),
+            "眠っている敵は逃走失敗ターンでも行動しないはず"
+        );
+        assert!(results.contains(&TurnResult::PoisonDamage {
+            target: TargetId::Enemy(0),
+            damage: spell::POISON_DAMAGE,
+        }));
+    }
+
+    #[test]
+    fn smoke_ball_always_escapes() {
+        let table = char_table();
+        let mut party = default_party(&table);
+        party[2].inventory.add(ItemKey::SmokeBall, 1);
+        let mut battle = BattleState::new(party, vec![Enemy::slime()]);
+        let commands = vec![
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::UseItem { item: ItemKey::SmokeBall, target: TargetId::Party(2) },
+        ];
+
+        // 逃走判定の乱数に関係なく成功する
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 4], 1.0));
+
+        assert_eq!(
+            results,
+            vec![
+                TurnResult::EscapeItemUsed { user: ActorId::Party(2), item: ItemKey::SmokeBall },
+                TurnResult::Fled,
+            ]
+        );
+        assert!(battle.is_over());
+        assert_eq!(battle.party[2].inventory.count(ItemKey::SmokeBall), 0);
+    }
+
+    #[test]
+    fn smoke_ball_is_kept_in_boss_battle() {
+        let table = char_table();
+        let mut party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        party[0].inventory.add(ItemKey::SmokeBall, 1);
+        let mut battle = BattleState::new(party, vec![Enemy::slime()]);
+        battle.can_flee = false;
+        let commands = vec![BattleAction::UseItem { item: ItemKey::SmokeBall, target: TargetId::Party(0) }];
+
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 0.0));
+
+        assert!(results.contains(&TurnResult::FleeBlocked));
+        assert!(!battle.is_over());
+        assert_eq!(battle.party[0].inventory.count(ItemKey::SmokeBall), 1);
+    }
+
+    #[test]
+    fn smoke_ball_not_held_does_nothing() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut battle = BattleState::new(party, vec![Enemy::slime()]);
+        let commands = vec![BattleAction::UseItem { item: ItemKey::SmokeBall, target: TargetId::Party(0) }];
+
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 0.0));
+
+        assert!(!results.contains(&TurnResult::Fled));
+        assert!(!results.contains(&TurnResult::FleeBlocked));
+        assert!(!results.iter().any(|r| matches!(r, TurnResult::EscapeItemUsed { .. })));
+        assert!(!battle.is_over());
+    }
+
+    #[test]
+    fn victory_detection() {
+        let table = char_table();
+        let party = default_party(&table);
+        let enemies = vec![Enemy::slime()];
+        let mut battle = BattleState::new(party, enemies);
+
+        // 敵を倒す
+        battle.enemies[0].stats.hp = 0;
+        assert!(battle.is_victory());
+        assert!(battle.is_over());
+        assert!(!battle.is_party_wiped());
+    }
+
+    #[test]
+    fn rewards_come_from_defeated_enemies_only() {
+        let table = char_table();
+        let party = default_party(&table);
+        let enemies = vec![
+            Enemy::new(EnemyKind::Goblin, 1),
+            Enemy::new(EnemyKind::Dragon, 1),
+            Enemy::new(EnemyKind::Goblin, 1),
+        ];
+        let mut battle = BattleState::new(party, enemies);
+        battle.enemies[0].stats.hp = 0;
+        battle.enemies[1].stats.hp = 0;
+
+        let random = RewardRandomFactors {
+            gold_randoms: vec![0.0, 1.0, 1.0],
+            drop_randoms: vec![0.0, 0.75, 1.0],
+        };
+        let rewards = battle.calculate_rewards(&random);
+
+        assert_eq!(rewards.exp, battle.total_exp_reward());
+        // ゴブリン最小5 + ドラゴン最大40（生存しているゴブリンは含まない）
+        assert_eq!(rewards.gold, 45);
+        assert_eq!(rewards.items, vec![ItemKey::DragonScale]);
+    }
+
+    #[test]
+    fn party_wipe_detection() {
+        let table = char_table();
+        let party = default_party(&table);
+        let enemies = vec![Enemy::slime()];
+        let mut battle = BattleState::new(party, enemies);
+
+        // 全員倒す
+        for member in &mut battle.party {
+            member.stats.hp = 0;
+        }
+        assert!(battle.is_party_wiped());
+        assert!(battle.is_over());
+        assert!(!battle.is_victory());
+    }
+
+    #[test]
+    fn zola_spell_damages_enemy() {
+        let table = char_table();
+        let party = default_party(&table);
+        let enemies = vec![Enemy::slime()];
+        let mut battle = BattleState::new(party, enemies);
+
+        let mage_mp_before = battle.party[1].stats.mp;
+        let commands = vec![
             BattleAction::Attack {
                 target: TargetId::Enemy(0),
             },
@@ -1348,6 +2522,74 @@ mod tests {
         assert_eq!(battle.party[1].stats.mp, mage_mp_before - 3);
     }
 
+    /// マルシルが敵1体に呪文を1回唱えた結果（敵は眠らせて行動させない）
+    fn marcille_casts_on(enemy: Enemy, spell: SpellEntry) -> (Vec<TurnResult>, BattleState) {
+        let table = char_table();
+        let mut marcille = PartyMember::from_kind(PartyMemberKind::Marcille, &table);
+        marcille.stats.mp = 99;
+        let commands = [BattleAction::Spell { spell, target: TargetId::Enemy(0) }];
+        duel_turn(marcille, enemy, &commands, &make_random(vec![1.0; 2], 1.0), |battle| {
+            battle.enemies[0].stats.hp = battle.enemies[0].stats.max_hp - 20;
+            battle.enemy_ailments[0].inflict(Ailment::Sleep);
+        })
+    }
+
+    fn spell_damage_of(results: &[TurnResult]) -> i32 {
+        results
+            .iter()
+            .find_map(|r| match r {
+                TurnResult::SpellDamage { damage, .. } => Some(*damage),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn fire_damage_depends_on_enemy_affinity() {
+        let mut goblin = Enemy::new(EnemyKind::Goblin, 1);
+        let mut golem = Enemy::new(EnemyKind::Golem, 1);
+        let mut slime = Enemy::slime();
+        // 防御をそろえて相性だけを比べる
+        for enemy in [&mut goblin, &mut golem, &mut slime] {
+            enemy.stats.defense = 0;
+            enemy.stats.max_hp = 999;
+        }
+        let (weak, _) = marcille_casts_on(goblin, spell_data::FIRE1);
+        let (normal, _) = marcille_casts_on(slime, spell_data::FIRE1);
+        let (resist, _) = marcille_casts_on(golem, spell_data::FIRE1);
+
+        assert_eq!(spell_damage_of(&weak), 18);
+        assert_eq!(spell_damage_of(&normal), 12);
+        assert_eq!(spell_damage_of(&resist), 6);
+        assert!(weak.contains(&TurnResult::ElementReaction {
+            target: TargetId::Enemy(0),
+            affinity: Affinity::Weak,
+            healed: 0,
+        }));
+        assert!(!normal.iter().any(|r| matches!(r, TurnResult::ElementReaction { .. })));
+    }
+
+    #[test]
+    fn dragon_absorbs_fire_and_ice_immune_wraith_takes_nothing() {
+        let dragon = Enemy::new(EnemyKind::Dragon, 1);
+        let (results, battle) = marcille_casts_on(dragon, spell_data::FIRE1);
+        let healed = results
+            .iter()
+            .find_map(|r| match r {
+                TurnResult::ElementReaction { affinity: Affinity::Absorb, healed, .. } => Some(*healed),
+                _ => None,
+            })
+            .unwrap();
+        assert!(healed > 0);
+        assert_eq!(spell_damage_of(&results), 0);
+        assert_eq!(battle.enemies[0].stats.hp, battle.enemies[0].stats.max_hp - 20 + healed);
+
+        let wraith = Enemy::new(EnemyKind::Wraith, 1);
+        let (results, battle) = marcille_casts_on(wraith, spell_data::ICE1);
+        assert_eq!(spell_damage_of(&results), 0);
+        assert_eq!(battle.enemies[0].stats.hp, battle.enemies[0].stats.max_hp - 20);
+    }
+
     #[test]
     fn luna_spell_restores_hp() {
         let table = char_table();
@@ -1663,6 +2905,106 @@ mod tests {
         assert!(hp_lost < total_raw, "ブロックによりダメージが軽減されるはず");
     }
 
+    /// 攻撃力の高い狼1体とライオス1人の戦闘で、ライオスが行動したときの敵の攻撃結果
+    fn wolf_attack_on_laios(
+        action: BattleAction,
+        prepare: impl FnOnce(&mut BattleState),
+    ) -> (Vec<TurnResult>, i32, i32) {
+        let table = char_table();
+        let laios = PartyMember::from_kind(PartyMemberKind::Laios, &table);
+        let mut wolf = sturdy(Enemy::wolf());
+        wolf.stats.attack = 40;
+
+        let (results, battle) = duel_turn(laios, wolf, &[action], &make_random(vec![1.0; 2], 1.0), prepare);
+        let (damage, blocked) = enemy_attack_of(&results);
+        assert!(!battle.party_buffs[0].defending, "ぼうぎょはターン終了時に解ける");
+        (results, damage, blocked)
+    }
+
+    #[test]
+    fn defend_acts_first_and_halves_damage() {
+        let attack = BattleAction::Attack { target: TargetId::Enemy(0) };
+        let (_, normal_damage, _) = wolf_attack_on_laios(attack, |_| {});
+        let (results, defended_damage, _) = wolf_attack_on_laios(BattleAction::Defend, |_| {});
+
+        assert_eq!(results[0], TurnResult::Defending { actor: ActorId::Party(0) });
+        assert_eq!(defended_damage, normal_damage * DEFEND_DAMAGE_PERCENT / 100);
+    }
+
+    #[test]
+    fn defend_halves_damage_before_block_absorbs() {
+        let attack = BattleAction::Attack { target: TargetId::Enemy(0) };
+        let (_, raw_damage, _) = wolf_attack_on_laios(attack, |_| {});
+        let (_, damage, blocked) = wolf_attack_on_laios(BattleAction::Defend, |battle| battle.party_buffs[0].block = 5);
+
+        // 半減後のダメージをブロックが吸収し、残りだけHPが減る
+        assert_eq!(blocked, 5);
+        assert_eq!(damage + blocked, raw_damage * DEFEND_DAMAGE_PERCENT / 100);
+
+        // ブロックが半減後のダメージを上回れば無傷
+        let (_, damage, blocked) =
+            wolf_attack_on_laios(BattleAction::Defend, |battle| battle.party_buffs[0].block = raw_damage);
+        assert_eq!(damage, 0);
+        assert_eq!(blocked, raw_damage * DEFEND_DAMAGE_PERCENT / 100);
+    }
+
+    /// ライオスがスライムを1回たたいた結果（敵は行動させない）
+    fn laios_attacks_slime(hit: f32, critical: f32) -> Vec<TurnResult> {
+        let table = char_table();
+        let laios = PartyMember::from_kind(PartyMemberKind::Laios, &table);
+        let mut slime = sturdy(Enemy::slime());
+        slime.stats.defense = 20;
+
+        let randoms = TurnRandomFactors {
+            hit_randoms: vec![hit; 2],
+            critical_randoms: vec![critical; 2],
+            ..make_random(vec![1.0; 2], 1.0)
+        };
+        let commands = [BattleAction::Attack { target: TargetId::Enemy(0) }];
+        duel_turn(laios, slime, &commands, &randoms, |battle| battle.enemy_ailments[0].inflict(Ailment::Sleep)).0
+    }
+
+    #[test]
+    fn attack_misses_when_hit_random_exceeds_hit_rate() {
+        let results = laios_attacks_slime(0.99, 1.0);
+        assert_eq!(
+            results[0],
+            TurnResult::Missed { attacker: ActorId::Party(0), target: TargetId::Enemy(0) }
+        );
+        assert!(!results.iter().any(|r| matches!(r, TurnResult::Attack { .. })));
+    }
+
+    #[test]
+    fn critical_hit_ignores_defense() {
+        let table = char_table();
+        let attack = PartyMember::from_kind(PartyMemberKind::Laios, &table).effective_attack();
+
+        let normal = laios_attacks_slime(0.0, 1.0);
+        let critical = laios_attacks_slime(0.0, 0.0);
+
+        assert_eq!(critical[0], TurnResult::Critical { attacker: ActorId::Party(0) });
+        let damage_of = |results: &[TurnResult]| {
+            results.iter().find_map(|r| match r {
+                TurnResult::Attack { damage, .. } => Some(*damage),
+                _ => None,
+            })
+        };
+        assert_eq!(damage_of(&critical), Some(attack));
+        assert!(damage_of(&normal).unwrap() < attack);
+    }
+
+    #[test]
+    fn missing_hit_and_critical_randoms_always_hit_normally() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut battle = BattleState::new(party, vec![Enemy::slime()]);
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 1.0));
+        assert!(!results
+            .iter()
+            .any(|r| matches!(r, TurnResult::Missed { .. } | TurnResult::Critical { .. })));
+    }
+
     #[test]
     fn block_stacks_additively() {
         let table = char_table();
@@ -1784,6 +3126,116 @@ mod tests {
         assert_eq!(buff.remaining_turns, 4, "上書き後の持続ターンは4(5-1tick)");
     }
 
+    #[test]
+    fn defense_buff_reduces_damage_taken() {
+        let damage_with_buff = |stat: BuffStat, amount: i32| {
+            let attack = BattleAction::Attack { target: TargetId::Enemy(0) };
+            wolf_attack_on_laios(attack, |battle| battle.party_buffs[0].apply(stat, amount)).1
+        };
+        let normal = damage_with_buff(BuffStat::Defense, 0);
+        let guarded = damage_with_buff(BuffStat::Defense, 8);
+        let broken = damage_with_buff(BuffStat::DefenseDown, 3);
+        assert!(guarded < normal, "DEF↑でダメージが減るはず");
+        assert!(broken > normal, "DEF↓でダメージが増えるはず");
+    }
+
+    #[test]
+    fn speed_buff_changes_action_order() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut wolf = Enemy::wolf();
+        wolf.stats.speed = 8;
+        let mut battle = BattleState::new(party, vec![wolf]);
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+
+        assert_eq!(battle.predicted_action_order(&commands, &[])[0], ActorId::Enemy(0));
+        battle.party_buffs[0].apply(BuffStat::Speed, 5);
+        assert_eq!(battle.predicted_action_order(&commands, &[])[0], ActorId::Party(0));
+    }
+
+    #[test]
+    fn speed_jitter_can_swap_close_actors() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.speed = party[0].stats.speed + 1;
+        let mut battle = BattleState::new(party, vec![slime]);
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+
+        assert_eq!(speed_jitter(0.5), 0);
+        assert_eq!(speed_jitter(0.0), -SPEED_JITTER);
+        assert_eq!(speed_jitter(1.0), SPEED_JITTER);
+
+        // 揺らぎなしなら速い敵が先
+        assert_eq!(battle.build_action_order(&commands, &[0.5, 0.5]), battle.predicted_action_order(&commands, &[]));
+        assert_eq!(battle.predicted_action_order(&commands, &[])[0], ActorId::Enemy(0));
+        // 素早さの差が揺らぎ以内なら逆転しうる
+        assert_eq!(battle.build_action_order(&commands, &[1.0, 0.0])[0], ActorId::Party(0));
+
+        // 予測と同じ揺らぎで実行すれば予測どおりの順に行動する
+        let predicted = battle.predicted_action_order(&commands, &[1.0, 0.0]);
+        let randoms = TurnRandomFactors { speed_randoms: vec![1.0, 0.0], ..make_random(vec![1.0; 2], 1.0) };
+        let first_actor = battle.execute_turn(&commands, &randoms).iter().find_map(|r| match r {
+            TurnResult::Attack { attacker, .. } | TurnResult::Missed { attacker, .. } => Some(*attacker),
+            _ => None,
+        });
+        assert_eq!(first_actor, Some(predicted[0]));
+    }
+
+    #[test]
+    fn defense_debuff_spell_weakens_enemy() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        let base_defense = battle.actor_defense(ActorId::Enemy(0));
+
+        let commands = vec![BattleAction::Spell {
+            spell: spell_data::BREAK1,
+            target: TargetId::Enemy(0),
+        }];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 0.0));
+
+        assert!(results.contains(&TurnResult::Buffed {
+            caster: ActorId::Party(0),
+            spell: spell_data::BREAK1,
+            target: TargetId::Enemy(0),
+            amount: 4,
+        }));
+        assert!(battle.party_buffs[0].defense_down.is_none(), "味方にはかからない");
+        assert_eq!(
+            battle.actor_defense(ActorId::Enemy(0)),
+            (base_defense - 4).max(0)
+        );
+    }
+
+    #[test]
+    fn enemy_debuff_expires() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.enemy_buffs[0].apply(BuffStat::AttackDown, 2);
+
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+        let mut expired = false;
+        for _ in 0..BUFF_DURATION {
+            let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 0.0));
+            expired |= results.contains(&TurnResult::BuffExpired {
+                target: TargetId::Enemy(0),
+                stat: BuffStat::AttackDown,
+            });
+        }
+        assert!(expired, "敵のデバフも時間切れになるはず");
+        assert!(battle.enemy_buffs[0].attack_down.is_none());
+    }
+
     #[test]
     fn enemy_spell_damages_party() {
         let table = char_table();
@@ -1855,7 +3307,7 @@ mod tests {
         // usable.first()はBlaze2を返す → Damage呪文
         // Heal呪文のテストは直接実行
         let hp_before = battle.enemies[0].stats.hp;
-        let results = battle.execute_enemy_spell(0, spell_data::HEAL2, 1.0);
+        let results = battle.execute_enemy_spell(0, spell_data::HEAL2, make_random(Vec::new(), 1.0).attack_rolls(0), 0.0);
         assert!(
             results.iter().any(|r| matches!(r, TurnResult::Healed { .. })),
             "Heal2で回復イベントが発生するはず"
@@ -1887,6 +3339,197 @@ mod tests {
         assert_eq!(spell_hits.len(), 3, "Blaze2でパーティ全員にヒットするはず");
     }
 
+    /// 敵だけが行動するターンを実行し、敵0の攻撃対象を返す
+    fn enemy_attack_target(battle: &mut BattleState, target_random: f32) -> Option<TargetId> {
+        let randoms = TurnRandomFactors {
+            damage_randoms: vec![1.0; 4],
+            flee_random: 1.0,
+            spell_randoms: vec![1.0],
+            target_randoms: vec![target_random],
+            ..Default::default()
+        };
+        battle.execute_turn(&[], &randoms).iter().find_map(|r| match r {
+            TurnResult::Attack { attacker: ActorId::Enemy(0), target, .. } => Some(*target),
+            _ => None,
+        })
+    }
+
+    fn three_member_party(table: &CharacterParamTable) -> Vec<PartyMember> {
+        vec![
+            PartyMember::from_kind(PartyMemberKind::Laios, table),
+            PartyMember::from_kind(PartyMemberKind::Marcille, table),
+            PartyMember::from_kind(PartyMemberKind::Falin, table),
+        ]
+    }
+
+    #[test]
+    fn random_targeting_uses_target_random() {
+        let table = char_table();
+        let mut battle = BattleState::new(three_member_party(&table), vec![Enemy::slime()]);
+        assert_eq!(enemy_attack_target(&mut battle, 0.99), Some(TargetId::Party(2)));
+
+        let mut battle = BattleState::new(three_member_party(&table), vec![Enemy::slime()]);
+        assert_eq!(enemy_attack_target(&mut battle, 0.0), Some(TargetId::Party(0)));
+    }
+
+    #[test]
+    fn random_targeting_prefers_front_row() {
+        let table = char_table();
+        // 前列ライオス(重み3)・後列マルシル(重み1)・前列ファリン(重み3)
+        let target_at = |r: f32| {
+            let mut battle = BattleState::new(three_member_party(&table), vec![Enemy::slime()]);
+            battle.party_rows[1] = Row::Back;
+            enemy_attack_target(&mut battle, r)
+        };
+        assert_eq!(target_at(0.0), Some(TargetId::Party(0)));
+        assert_eq!(target_at(0.45), Some(TargetId::Party(1)));
+        assert_eq!(target_at(0.6), Some(TargetId::Party(2)));
+        assert_eq!(target_at(0.99), Some(TargetId::Party(2)));
+    }
+
+    #[test]
+    fn front_targeting_skips_back_row() {
+        let table = char_table();
+        let mut battle = BattleState::new(three_member_party(&table), vec![Enemy::new(EnemyKind::Golem, 1)]);
+        battle.party_rows[0] = Row::Back;
+        assert_eq!(enemy_attack_target(&mut battle, 0.0), Some(TargetId::Party(1)));
+    }
+
+    #[test]
+    fn back_row_halves_physical_damage_both_ways() {
+        let table = char_table();
+        let attack_damages = |rows: Vec<Row>| {
+            let mut battle = BattleState::new(three_member_party(&table), vec![Enemy::slime()]);
+            battle.enemies[0].stats.hp = 999;
+            battle.party_rows = rows;
+            let commands = vec![
+                BattleAction::Attack { target: TargetId::Enemy(0) },
+                BattleAction::Attack { target: TargetId::Enemy(0) },
+                BattleAction::Attack { target: TargetId::Enemy(0) },
+            ];
+            let randoms = TurnRandomFactors {
+                damage_randoms: vec![1.0; 4],
+                flee_random: 1.0,
+                spell_randoms: vec![1.0],
+                target_randoms: vec![0.0],
+                ..Default::default()
+            };
+            let results = battle.execute_turn(&commands, &randoms);
+            let dealt = results.iter().find_map(|r| match r {
+                TurnResult::Attack { attacker: ActorId::Party(0), damage, .. } => Some(*damage),
+                _ => None,
+            });
+            let taken = results.iter().find_map(|r| match r {
+                TurnResult::Attack { attacker: ActorId::Enemy(0), damage, .. } => Some(*damage),
+                _ => None,
+            });
+            (dealt.unwrap(), taken.unwrap())
+        };
+        let (front_dealt, front_taken) = attack_damages(vec![Row::Front; 3]);
+        let (back_dealt, back_taken) = attack_damages(vec![Row::Back; 3]);
+        assert_eq!(back_dealt, back_row_damage(front_dealt));
+        assert_eq!(back_taken, back_row_damage(front_taken));
+        assert!(back_dealt < front_dealt);
+    }
+
+    #[test]
+    fn hunter_targets_lowest_hp_member() {
+        let table = char_table();
+        let mut battle = BattleState::new(three_member_party(&table), vec![Enemy::wolf()]);
+        battle.party[1].stats.hp = 15;
+        battle.party[2].stats.hp = 12;
+        assert_eq!(enemy_attack_target(&mut battle, 0.0), Some(TargetId::Party(2)));
+    }
+
+    #[test]
+    fn focusing_enemy_attacks_sleeping_member() {
+        let table = char_table();
+        let demon = Enemy::new(EnemyKind::Demon, 1);
+        let mut battle = BattleState::new(three_member_party(&table), vec![demon]);
+        battle.party_ailments[1].inflict(Ailment::Sleep);
+        assert_eq!(enemy_attack_target(&mut battle, 0.0), Some(TargetId::Party(1)));
+    }
+
+    #[test]
+    fn dark_lord_heals_when_hp_is_low() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut dark_lord = Enemy::dark_lord();
+        dark_lord.stats.hp = 40; // 200の20%
+        let mut battle = BattleState::new(party, vec![dark_lord]);
+
+        // spell_random=1.0でも回復を優先する
+        let randoms = make_random_with_spells(vec![1.0; 2], 1.0, vec![1.0]);
+        let results = battle.execute_turn(&[], &randoms);
+        assert!(results.iter().any(|r| matches!(
+            r,
+            TurnResult::Healed { caster: ActorId::Enemy(0), spell, .. } if spell.name == "Heal2"
+        )));
+    }
+
+    fn sturdy_laios(table: &CharacterParamTable) -> Vec<PartyMember> {
+        let mut laios = PartyMember::from_kind(PartyMemberKind::Laios, table);
+        laios.stats.hp = 999;
+        laios.stats.max_hp = 999;
+        vec![laios]
+    }
+
+    #[test]
+    fn dark_lord_script_summons_and_transforms() {
+        let table = char_table();
+        let mut battle = BattleState::new(sturdy_laios(&table), vec![Enemy::dark_lord()]);
+        let randoms = make_random_with_spells(vec![1.0; 4], 1.0, vec![1.0]);
+
+        // HP70%以下: セリフと手下2体の召喚
+        battle.enemies[0].stats.hp = 130;
+        let results = battle.execute_turn(&[], &randoms);
+        assert!(results.iter().any(|r| matches!(r, TurnResult::BossMessage { .. })));
+        let summoned: Vec<_> = results
+            .iter()
+            .filter_map(|r| match r {
+                TurnResult::Summoned { enemy } => Some(*enemy),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(summoned, vec![1, 2]);
+        assert_eq!(battle.enemies.len(), 3);
+        assert_eq!(battle.enemy_ailments.len(), 3);
+        assert_eq!(battle.enemies[1].kind, EnemyKind::Demon);
+
+        // 同じフェーズは二度起きない
+        let results = battle.execute_turn(&[], &randoms);
+        assert!(!results.iter().any(|r| matches!(r, TurnResult::Summoned { .. })));
+
+        // HP30%以下: 最終形態に変身してHP全快
+        battle.enemies[0].stats.hp = 50;
+        battle.enemies[0].stats.mp = 0; // 回復呪文でしきい値を戻さないように
+        let results = battle.execute_turn(&[], &randoms);
+        assert!(results.contains(&TurnResult::Transformed { enemy: 0 }));
+        assert_eq!(battle.enemies[0].stats.max_hp, 250);
+        assert_eq!(battle.enemies[0].stats.hp, 250);
+        assert_eq!(battle.enemies[0].stats.attack, 32);
+    }
+
+    #[test]
+    fn summon_is_capped_by_max_enemies() {
+        let table = char_table();
+        let enemies = vec![Enemy::dark_lord(), Enemy::slime(), Enemy::slime()];
+        let mut battle = BattleState::new(sturdy_laios(&table), enemies);
+        battle.enemies[0].stats.hp = 130;
+        let randoms = make_random_with_spells(vec![1.0; 4], 1.0, vec![1.0; 3]);
+        let results = battle.execute_turn(&[], &randoms);
+        let summoned = results.iter().filter(|r| matches!(r, TurnResult::Summoned { .. })).count();
+        assert_eq!(summoned, 1);
+        assert_eq!(battle.enemies.len(), MAX_ENEMIES);
+    }
+
+    #[test]
+    fn non_boss_battle_has_no_script() {
+        let table = char_table();
+        let battle = BattleState::new(sturdy_laios(&table), vec![Enemy::slime()]);
+        assert!(battle.boss.is_none());
+    }
+
     #[test]
     fn sleeping_actor_skips_turn() {
         let table = char_table();
@@ -1899,7 +3542,7 @@ mod tests {
         let mut battle = BattleState::new(party, enemies);
 
         // ライオスを眠り状態にする
-        battle.party_ailments[0].sleep = true;
+        battle.party_ailments[0].inflict(Ailment::Sleep);
 
         let commands = vec![BattleAction::Attack {
             target: TargetId::Enemy(0),
@@ -1931,7 +3574,7 @@ mod tests {
         let mut battle = BattleState::new(party, enemies);
 
         // 敵を眠り状態にする
-        battle.enemy_ailments[0].sleep = true;
+        battle.enemy_ailments[0].inflict(Ailment::Sleep);
 
         let commands = vec![BattleAction::Attack {
             target: TargetId::Enemy(0),
@@ -1963,7 +3606,7 @@ mod tests {
         let mut battle = BattleState::new(party, enemies);
 
         // ライオスを毒状態にする
-        battle.party_ailments[0].poison = true;
+        battle.party_ailments[0].inflict(Ailment::Poison);
 
         let commands = vec![BattleAction::Attack {
             target: TargetId::Enemy(0),
@@ -1995,7 +3638,7 @@ mod tests {
         let mut battle = BattleState::new(party, enemies);
 
         // 敵を毒状態にする
-        battle.enemy_ailments[0].poison = true;
+        battle.enemy_ailments[0].inflict(Ailment::Poison);
 
         let commands = vec![BattleAction::Attack {
             target: TargetId::Enemy(0),
@@ -2021,7 +3664,7 @@ mod tests {
         let mut battle = BattleState::new(party, enemies);
 
         // 敵を眠り状態にする
-        battle.enemy_ailments[0].sleep = true;
+        battle.enemy_ailments[0].inflict(Ailment::Sleep);
 
         let commands = vec![BattleAction::Attack {
             target: TargetId::Enemy(0),
@@ -2039,7 +3682,7 @@ mod tests {
             )
         });
         assert!(cured, "攻撃で眠りが解除されるはず");
-        assert!(!battle.enemy_ailments[0].sleep, "眠りフラグが解除されるはず");
+        assert!(!battle.enemy_ailments[0].has(Ailment::Sleep), "眠りフラグが解除されるはず");
     }
 
     #[test]
@@ -2083,7 +3726,7 @@ mod tests {
         assert!(inflicted, "成功率内なら状態異常が付与されるはず");
 
         // リセット
-        battle.enemy_ailments[0].sleep = false;
+        battle.enemy_ailments[0].cure(Ailment::Sleep);
 
         // 失敗ケース (random=0.9 → 0.9*100=90 >= 70 → 失敗)
         let commands = vec![
@@ -2102,6 +3745,322 @@ mod tests {
             .iter()
             .any(|r| matches!(r, TurnResult::AilmentResisted { .. }));
         assert!(resisted, "成功率外なら状態異常が抵抗されるはず");
-        assert!(!battle.enemy_ailments[0].sleep, "抵抗時は眠りフラグがfalseのまま");
+        assert!(!battle.enemy_ailments[0].has(Ailment::Sleep), "抵抗時は眠りフラグがfalseのまま");
+    }
+
+    /// ライオスが倒れ、スライム（無害）1体と戦っている状態
+    #[test]
+    fn paralysis_skips_turns_until_it_wears_off() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.party_ailments[0].inflict(Ailment::Paralysis);
+
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+        let duration = Ailment::Paralysis.duration().unwrap();
+        for turn in 1..=duration {
+            let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 1.0));
+            assert!(results.contains(&TurnResult::Paralyzed { actor: ActorId::Party(0) }));
+            let cured = results.contains(&TurnResult::AilmentCured {
+                target: TargetId::Party(0),
+                ailment: Ailment::Paralysis,
+            });
+            assert_eq!(cured, turn == duration, "持続ターンが切れたときだけ治る");
+        }
+
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 1.0));
+        assert!(results.iter().any(|r| matches!(r, TurnResult::Attack { attacker: ActorId::Party(0), .. })));
+    }
+
+    #[test]
+    fn recovery_roll_cures_ailments_early() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.party_ailments[0].inflict(Ailment::Paralysis);
+        battle.party_ailments[0].inflict(Ailment::Poison);
+        battle.enemy_ailments[0].inflict(Ailment::Blind);
+
+        // パーティ→敵の順。0.1はまひ(20%)も暗闇(15%)も回復するが、毒(0%)は治らない
+        let mut randoms = make_random(vec![1.0; 2], 1.0);
+        randoms.recovery_randoms = vec![0.1, 0.1];
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+        let results = battle.execute_turn(&commands, &randoms);
+
+        assert!(results.contains(&TurnResult::AilmentCured { target: TargetId::Party(0), ailment: Ailment::Paralysis }));
+        assert!(results.contains(&TurnResult::AilmentCured { target: TargetId::Enemy(0), ailment: Ailment::Blind }));
+        assert!(battle.party_ailments[0].has(Ailment::Poison), "毒は自然回復しない");
+        assert!(!battle.enemy_ailments[0].has_any());
+    }
+
+    #[test]
+    fn silence_blocks_spells_without_using_mp() {
+        let table = char_table();
+        let mut marcille = PartyMember::from_kind(PartyMemberKind::Marcille, &table);
+        marcille.stats.mp = 20;
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(vec![marcille], vec![slime]);
+        battle.party_ailments[0].inflict(Ailment::Silence);
+
+        let commands = vec![BattleAction::Spell { spell: spell_data::FIRE1, target: TargetId::Enemy(0) }];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 2], 1.0));
+
+        assert!(results.contains(&TurnResult::Silenced { actor: ActorId::Party(0), spell: spell_data::FIRE1 }));
+        assert!(!results.iter().any(|r| matches!(r, TurnResult::SpellDamage { .. })));
+        assert_eq!(battle.party[0].stats.mp, 20);
+        assert_eq!(battle.enemies[0].stats.hp, 999);
+    }
+
+    #[test]
+    fn silenced_enemy_falls_back_to_attack() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut ghost = Enemy::new(EnemyKind::Ghost, 1);
+        ghost.stats.speed = 99;
+        let mut battle = BattleState::new(party, vec![ghost]);
+        battle.enemy_ailments[0].inflict(Ailment::Silence);
+
+        let commands = vec![BattleAction::Defend];
+        let results = battle.execute_turn(&commands, &make_random_with_spells(vec![1.0; 2], 1.0, vec![0.0]));
+
+        assert!(!results.iter().any(|r| matches!(r, TurnResult::SpellDamage { .. } | TurnResult::MpDrained { .. } | TurnResult::AilmentInflicted { .. })));
+        assert!(results.iter().any(|r| matches!(r, TurnResult::Attack { attacker: ActorId::Enemy(0), .. })));
+    }
+
+    #[test]
+    fn confused_member_attacks_a_random_combatant() {
+        let table = char_table();
+        let party = default_party(&table);
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.party_ailments[0].inflict(Ailment::Confusion);
+
+        // 候補は 敵0 → 味方1 → 味方2 の順。0.9なら最後の味方2を殴る
+        let mut randoms = make_random(vec![1.0; 4], 1.0);
+        randoms.confusion_randoms = vec![0.9; 4];
+        let commands = vec![
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Defend,
+            BattleAction::Defend,
+        ];
+        let hp_before = battle.party[2].stats.hp;
+        let results = battle.execute_turn(&commands, &randoms);
+
+        assert!(results.contains(&TurnResult::Confused { actor: ActorId::Party(0) }));
+        assert!(results.iter().any(|r| matches!(
+            r,
+            TurnResult::Attack { attacker: ActorId::Party(0), target: TargetId::Party(2), .. }
+        )));
+        assert!(battle.party[2].stats.hp < hp_before);
+        assert_eq!(battle.enemies[0].stats.hp, 999);
+    }
+
+    #[test]
+    fn blind_attacker_misses_more_often() {
+        let table = char_table();
+        let party = vec![PartyMember::from_kind(PartyMemberKind::Laios, &table)];
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+
+        let mut randoms = make_random(vec![1.0; 2], 1.0);
+        randoms.hit_randoms = vec![0.6; 2];
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+        let results = battle.execute_turn(&commands, &randoms);
+        assert!(results.iter().any(|r| matches!(r, TurnResult::Attack { attacker: ActorId::Party(0), .. })));
+
+        battle.party_ailments[0].inflict(Ailment::Blind);
+        let results = battle.execute_turn(&commands, &randoms);
+        assert!(results.contains(&TurnResult::Missed { attacker: ActorId::Party(0), target: TargetId::Enemy(0) }));
+    }
+
+    #[test]
+    fn cure_spell_and_item_remove_ailments() {
+        let table = char_table();
+        let mut party = default_party(&table);
+        party[2].stats.mp = 99;
+        party[1].inventory.add(ItemKey::EyeDrops, 1);
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.party_ailments[0].inflict(Ailment::Poison);
+        battle.party_ailments[0].inflict(Ailment::Silence);
+        battle.party_ailments[2].inflict(Ailment::Blind);
+
+        let commands = vec![
+            BattleAction::Defend,
+            BattleAction::UseItem { item: ItemKey::EyeDrops, target: TargetId::Party(2) },
+            BattleAction::Spell { spell: spell_data::CURE1, target: TargetId::Party(0) },
+        ];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 4], 1.0));
+
+        assert!(results.contains(&TurnResult::Cured {
+            user: ActorId::Party(1),
+            source: SupportSource::Item(ItemKey::EyeDrops),
+            target: TargetId::Party(2),
+            ailments: vec![Ailment::Blind],
+        }));
+        assert!(results.contains(&TurnResult::Cured {
+            user: ActorId::Party(2),
+            source: SupportSource::Spell(spell_data::CURE1),
+            target: TargetId::Party(0),
+            ailments: vec![Ailment::Poison, Ailment::Silence],
+        }));
+        assert!(battle.party_ailments.iter().all(|a| !a.has_any()));
+        assert_eq!(battle.party[1].inventory.count(ItemKey::EyeDrops), 0);
+        assert_eq!(battle.party[2].stats.mp, 99 - spell_data::CURE1.mp_cost);
+    }
+
+    fn battle_with_fallen_hero() -> BattleState {
+        let table = char_table();
+        let party = default_party(&table);
+        let mut slime = Enemy::slime();
+        slime.stats.hp = 999;
+        slime.stats.max_hp = 999;
+        slime.stats.attack = 0;
+        let mut battle = BattleState::new(party, vec![slime]);
+        battle.party[0].stats.hp = 0;
+        battle.party_ailments[0].inflict(Ailment::Poison);
+        battle.party[2].stats.mp = 99;
+        battle
+    }
+
+    #[test]
+    fn revive_spell_restores_fallen_ally() {
+        let mut battle = battle_with_fallen_hero();
+        let max_hp = battle.party[0].stats.max_hp;
+
+        let commands = vec![
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Spell { spell: spell_data::REVIVE1, target: TargetId::Party(0) },
+        ];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 4], 0.0));
+
+        assert!(results.contains(&TurnResult::Revived {
+            user: ActorId::Party(2),
+            source: SupportSource::Spell(spell_data::REVIVE1),
+            target: TargetId::Party(0),
+            hp: max_hp / 2,
+        }));
+        assert!(battle.party[0].stats.is_alive());
+        assert!(!battle.party_ailments[0].has(Ailment::Poison), "復活時に状態異常は消える");
+        assert_eq!(battle.party[2].stats.mp, 99 - spell_data::REVIVE1.mp_cost);
+    }
+
+    #[test]
+    fn revive_retargets_to_fallen_ally() {
+        let mut battle = battle_with_fallen_hero();
+
+        let commands = vec![
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Spell { spell: spell_data::REVIVE1, target: TargetId::Party(1) },
+        ];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 4], 0.0));
+
+        let revived: Vec<_> = results
+            .iter()
+            .filter_map(|r| match r {
+                TurnResult::Revived { target, .. } => Some(*target),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(revived, vec![TargetId::Party(0)], "生きている味方ではなく倒れた味方が復活する");
+    }
+
+    #[test]
+    fn revive_without_fallen_ally_keeps_mp() {
+        let mut battle = battle_with_fallen_hero();
+        battle.party[0].stats.hp = 10;
+
+        let commands = vec![
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::Spell { spell: spell_data::REVIVE1, target: TargetId::Party(0) },
+        ];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 4], 0.0));
+
+        assert!(!results.iter().any(|r| matches!(r, TurnResult::Revived { .. })));
+        assert_eq!(battle.party[2].stats.mp, 99, "蘇生対象がいなければMPは減らない");
+    }
+
+    #[test]
+    fn revive_item_is_consumed_on_use() {
+        let mut battle = battle_with_fallen_hero();
+        battle.party[1].inventory.add(ItemKey::LifeLeaf, 1);
+
+        let commands = vec![
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+            BattleAction::UseItem { item: ItemKey::LifeLeaf, target: TargetId::Party(0) },
+            BattleAction::Attack { target: TargetId::Enemy(0) },
+        ];
+        let results = battle.execute_turn(&commands, &make_random(vec![1.0; 4], 0.0));
+
+        assert!(results.iter().any(|r| matches!(
+            r,
+            TurnResult::Revived {
+                source: SupportSource::Item(ItemKey::LifeLeaf),
+                target: TargetId::Party(0),
+                ..
+            }
+        )));
+        assert!(battle.party[0].stats.is_alive());
+        assert_eq!(battle.party[1].inventory.count(ItemKey::LifeLeaf), 0);
+    }
+
+    /// ライオス単独でスライムの攻撃を1回受け、受けたダメージを返す
+    fn damage_taken_by_laios(armor: Option<ItemKey>) -> i32 {
+        let table = char_table();
+        let mut laios = PartyMember::from_kind(PartyMemberKind::Laios, &table);
+        if let Some(armor) = armor {
+            laios.inventory.add(armor, 1);
+            laios.equip(armor);
+        }
+        let mut slime = sturdy(Enemy::slime());
+        slime.stats.attack = 20;
+
+        let commands = [BattleAction::Attack { target: TargetId::Enemy(0) }];
+        let (results, _) = duel_turn(laios, slime, &commands, &make_random(vec![1.0; 2], 0.0), |_| {});
+        enemy_attack_of(&results).0
+    }
+
+    #[test]
+    fn armor_reduces_damage_taken() {
+        assert!(damage_taken_by_laios(Some(ItemKey::ChainMail)) < damage_taken_by_laios(None));
+    }
+
+    #[test]
+    fn speed_accessory_changes_turn_order() {
+        let table = char_table();
+        let mut laios = PartyMember::from_kind(PartyMemberKind::Laios, &table);
+        let mut slime = Enemy::slime();
+        slime.stats.speed = laios.stats.speed + 1;
+        let commands = vec![BattleAction::Attack { target: TargetId::Enemy(0) }];
+
+        let battle = BattleState::new(vec![laios.clone()], vec![slime.clone()]);
+        assert_eq!(battle.predicted_action_order(&commands, &[])[0], ActorId::Enemy(0));
+
+        laios.inventory.add(ItemKey::SwiftRing, 1);
+        laios.equip(ItemKey::SwiftRing);
+        let battle = BattleState::new(vec![laios], vec![slime]);
+        assert_eq!(battle.predicted_action_order(&commands, &[])[0], ActorId::Party(0));
     }
 }
@@ -1,3 +1,7 @@
+pub mod boss;
 pub mod combat;
+pub mod record;
 
-pub use combat::{ActorId, ActorBuffs, BattleAction, BattleState, BuffStat, BuffState, TargetId, TurnRandomFactors, TurnResult};
+pub use boss::{boss_script, BossEvent, BossPhase, BossScript, BossScriptState, MAX_ENEMIES};
+pub use combat::{ActorAilments, ActorId, ActorBuffs, AilmentState, BattleAction, BattleRewards, BattleState, BuffStat, BuffState, RewardRandomFactors, SupportSource, TargetId, TurnRandomFactors, TurnResult};
+pub use record::{read_record_file, replay, write_record_file, BattleRecord, EnemyRecord, RecordError, ReplayMismatch, TurnRecord, BATTLE_RECORD_PATH};
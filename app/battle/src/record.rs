@@ -0,0 +1,288 @@
+use std::fmt;
+use std::path::Path;
+
+use enemy::{Enemy, EnemyKind};
+use party::{CombatStats, PartyMember, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::combat::{BattleAction, BattleState, TurnRandomFactors, TurnResult};
+
+/// デバッグ用に戦闘記録を書き出す先
+pub const BATTLE_RECORD_PATH: &str = "battle_record.ron";
+
+/// 戦闘開始時の敵（種類と段階から作り直し、能力値だけ記録の値にする）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyRecord {
+    pub kind: EnemyKind,
+    pub tier: u8,
+    pub stats: CombatStats,
+}
+
+/// 1ターン分の入力と結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnRecord {
+    pub commands: Vec<BattleAction>,
+    pub random_factors: TurnRandomFactors,
+    /// 再実行時の比較用
+    pub results: Vec<TurnResult>,
+}
+
+/// 戦闘の初期状態と全ターンの入力を保持する記録
+///
+/// 乱数もすべて記録するので、replayで同じ戦闘を再現できる。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleRecord {
+    pub party: Vec<PartyMember>,
+    pub party_rows: Vec<Row>,
+    pub enemies: Vec<EnemyRecord>,
+    pub can_flee: bool,
+    pub turns: Vec<TurnRecord>,
+}
+
+impl BattleRecord {
+    /// 戦闘開始時の状態から記録を始める
+    pub fn start(state: &BattleState) -> Self {
+        Self {
+            party: state.party.clone(),
+            party_rows: state.party_rows.clone(),
+            enemies: state
+                .enemies
+                .iter()
+                .map(|e| EnemyRecord {
+                    kind: e.kind,
+                    tier: e.tier,
+                    stats: e.stats.clone(),
+                })
+                .collect(),
+            can_flee: state.can_flee,
+            turns: Vec::new(),
+        }
+    }
+
+    /// 1ターン分の入力と結果を追記する
+    pub fn push_turn(
+        &mut self,
+        commands: &[BattleAction],
+        random_factors: &TurnRandomFactors,
+        results: &[TurnResult],
+    ) {
+        self.turns.push(TurnRecord {
+            commands: commands.to_vec(),
+            random_factors: random_factors.clone(),
+            results: results.to_vec(),
+        });
+    }
+
+    /// 記録から戦闘開始時のBattleStateを作る
+    pub fn initial_state(&self) -> BattleState {
+        let enemies = self
+            .enemies
+            .iter()
+            .map(|record| {
+                let mut enemy = Enemy::new(record.kind, record.tier);
+                enemy.stats = record.stats.clone();
+                enemy
+            })
+            .collect();
+        let mut state = BattleState::new(self.party.clone(), enemies);
+        state.party_rows = self.party_rows.clone();
+        state.can_flee = self.can_flee;
+        state
+    }
+
+    /// RON文字列に変換する
+    pub fn to_ron(&self) -> Result<String, RecordError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| RecordError::Format(e.to_string()))
+    }
+
+    /// RON文字列から復元する
+    pub fn from_ron(text: &str) -> Result<Self, RecordError> {
+        ron::from_str(text).map_err(|e| RecordError::Format(e.to_string()))
+    }
+}
+
+/// 戦闘記録の読み書きの失敗理由
+#[derive(Debug)]
+pub enum RecordError {
+    Io(std::io::Error),
+    Format(String),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Io(e) => write!(f, "io error: {}", e),
+            RecordError::Format(e) => write!(f, "format error: {}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for RecordError {
+    fn from(e: std::io::Error) -> Self {
+        RecordError::Io(e)
+    }
+}
+
+/// 戦闘記録をファイルに書き込む
+pub fn write_record_file(path: &Path, record: &BattleRecord) -> Result<(), RecordError> {
+    std::fs::write(path, record.to_ron()?)?;
+    Ok(())
+}
+
+/// 戦闘記録をファイルから読み込む
+pub fn read_record_file(path: &Path) -> Result<BattleRecord, RecordError> {
+    let text = std::fs::read_to_string(path)?;
+    BattleRecord::from_ron(&text)
+}
+
+/// 再実行した結果が記録と食い違ったターン
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayMismatch {
+    /// 0始まりのターン番号
+    pub turn: usize,
+    pub expected: Vec<TurnResult>,
+    pub actual: Vec<TurnResult>,
+}
+
+impl fmt::Display for ReplayMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "turn {} differs", self.turn)?;
+        writeln!(f, "expected: {:#?}", self.expected)?;
+        write!(f, "actual: {:#?}", self.actual)
+    }
+}
+
+/// 記録を初期状態から再実行し、全ターンの結果が記録と一致するか確かめる
+///
+/// 一致すれば最終状態を、食い違えば最初に食い違ったターンを返す。
+pub fn replay(record: &BattleRecord) -> Result<BattleState, ReplayMismatch> {
+    let mut state = record.initial_state();
+    for (turn, turn_record) in record.turns.iter().enumerate() {
+        let results = state.execute_turn(&turn_record.commands, &turn_record.random_factors);
+        if results != turn_record.results {
+            return Err(ReplayMismatch {
+                turn,
+                expected: turn_record.results.clone(),
+                actual: results,
+            });
+        }
+    }
+    Ok(state)
+}
+
+/// 呪文を名前で読み書きする（呪文データは静的なので名前から引き直す）
+pub(crate) mod spell_by_name {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use spell::SpellEntry;
+
+    pub fn serialize<S: Serializer>(spell: &SpellEntry, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(spell.name)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SpellEntry, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        spell_data::all_spells()
+            .iter()
+            .find(|spell| spell.name == name)
+            .copied()
+            .ok_or_else(|| D::Error::custom(format!("unknown spell: {}", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::TargetId;
+    use item_data::ItemKey;
+    use party::{default_party, CharacterEntry, CharacterParamTable, EquipRule, RecruitmentPath, StatGrowth};
+
+    fn char_table() -> CharacterParamTable {
+        CharacterParamTable::from_fn(|_| CharacterEntry {
+            initial_stats: CombatStats::new(30, 8, 3, 5, 20),
+            stat_growth: StatGrowth { hp: 5, mp: 1, attack: 2, defense: 1, speed: 1 },
+            recruit_method: RecruitmentPath::TavernBond,
+            spell_learn_table: &[],
+            equip_rule: EquipRule::Any,
+        })
+    }
+
+    fn randoms(seed: f32) -> TurnRandomFactors {
+        TurnRandomFactors {
+            damage_randoms: vec![0.8 + seed * 0.4; 6],
+            flee_random: 1.0,
+            spell_randoms: vec![seed; 3],
+            target_randoms: vec![seed; 3],
+            hit_randoms: vec![seed * 0.5; 6],
+            critical_randoms: vec![1.0 - seed; 6],
+            confusion_randoms: Vec::new(),
+            recovery_randoms: vec![seed; 6],
+            speed_randoms: vec![seed; 6],
+        }
+    }
+
+    /// 呪文・アイテム・ぼうぎょを含む数ターンの戦闘を記録する
+    fn recorded_battle() -> BattleRecord {
+        let table = char_table();
+        let mut party = default_party(&table);
+        party[2].inventory.add(ItemKey::Herb, 1);
+        let mut state = BattleState::new(party, vec![Enemy::new(EnemyKind::Ghost, 2), Enemy::new(EnemyKind::Goblin, 1)]);
+        state.party_rows[2] = Row::Back;
+        let mut record = BattleRecord::start(&state);
+
+        let turns = [
+            vec![
+                BattleAction::Attack { target: TargetId::Enemy(0) },
+                BattleAction::Spell { spell: spell_data::FIRE1, target: TargetId::Enemy(1) },
+                BattleAction::Defend,
+            ],
+            vec![
+                BattleAction::Attack { target: TargetId::Enemy(1) },
+                BattleAction::Spell { spell: spell_data::BLAZE1, target: TargetId::Enemy(0) },
+                BattleAction::UseItem { item: ItemKey::Herb, target: TargetId::Party(0) },
+            ],
+            vec![BattleAction::Attack { target: TargetId::Enemy(0) }; 3],
+        ];
+        for (i, commands) in turns.iter().enumerate() {
+            let random_factors = randoms(i as f32 * 0.3);
+            let results = state.execute_turn(commands, &random_factors);
+            record.push_turn(commands, &random_factors, &results);
+        }
+        record
+    }
+
+    #[test]
+    fn replay_reproduces_recorded_battle() {
+        let record = recorded_battle();
+        assert_eq!(record.turns.len(), 3);
+        let state = replay(&record).unwrap();
+        assert_eq!(state.turn_log.len(), record.turns.iter().map(|t| t.results.len()).sum::<usize>());
+    }
+
+    #[test]
+    fn record_survives_ron_round_trip() {
+        let record = recorded_battle();
+        let restored = BattleRecord::from_ron(&record.to_ron().unwrap()).unwrap();
+        assert_eq!(restored.turns[0].commands, record.turns[0].commands);
+        assert_eq!(restored.turns[1].results, record.turns[1].results);
+        assert_eq!(restored.party_rows, record.party_rows);
+        replay(&restored).unwrap();
+    }
+
+    #[test]
+    fn replay_reports_first_mismatched_turn() {
+        let mut record = recorded_battle();
+        record.turns[1].random_factors.damage_randoms = vec![1.2; 6];
+        let mismatch = replay(&record).unwrap_err();
+        assert_eq!(mismatch.turn, 1);
+        assert_ne!(mismatch.expected, mismatch.actual);
+    }
+
+    #[test]
+    fn spells_are_looked_up_by_name() {
+        let action: BattleAction = ron::from_str("Spell(spell: \"Fire1\", target: Enemy(0))").unwrap();
+        assert_eq!(action, BattleAction::Spell { spell: spell_data::FIRE1, target: TargetId::Enemy(0) });
+        assert!(ron::from_str::<BattleAction>("Spell(spell: \"NoSuchSpell\", target: Enemy(0))").is_err());
+    }
+}
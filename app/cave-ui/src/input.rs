@@ -1,15 +1,33 @@
 use bevy::prelude::*;
 
+use cave::{floor_object_id, ChestTrap};
+use party::{CombatStats, PartyMemberKind};
 use terrain::Structure;
 
-use progress_state::{BossBattlePending, OpenedChests};
-use party_state::PartyState;
+use progress_state::{BossBattlePending, MimicBattlePending, OpenedChests, OpenedDoors};
+use party_state::{CharacterParams, PartyState};
+use world_state::{ContinentCavePositions, EncounterZone, WorldSeed};
 use field_core::{ActiveMap, Player, TilePosition};
-use field_walk_ui::{FieldMessageState, SimpleTileMap, TileEnteredEvent};
+use field_walk_ui::{FieldMessageState, MovementBlockedEvent, SimpleTileMap, SymbolEnemy, TileEnteredEvent};
 
-use crate::scene::{BossCaveState, BossEntity, CaveTreasures};
+use crate::scene::{
+    build_cave_floor, cave_guaranteed_items, BossCaveState, BossEntity, CaveDoors, CaveTreasures,
+    FieldReturnState,
+};
+
+/// わなの解除とミミックの見破りが得意なキャラ
+const TRAP_EXPERT: PartyMemberKind = PartyMemberKind::Chilchuck;
+
+/// どくガスで全員が受けるダメージ（最大HPに対する割合の分母）
+const POISON_GAS_DIVISOR: i32 = 5;
+
+/// 矢で先頭の1人が受けるダメージ（最大HPに対する割合の分母）
+const ARROW_DIVISOR: i32 = 3;
 
 /// 宝箱取得システム: プレイヤーが宝箱タイルに入ったらアイテムを取得
+///
+/// わなの宝箱は開けたときに発動する。パーティにチルチャックがいれば
+/// わなを外し、ミミックを見破る。
 #[allow(clippy::too_many_arguments)]
 pub fn check_chest_system(
     mut commands: Commands,
@@ -21,6 +39,7 @@ pub fn check_chest_system(
     mut cave_message: ResMut<FieldMessageState>,
     mut active_map: ResMut<ActiveMap>,
     mut tile_map: ResMut<SimpleTileMap>,
+    mut next_battle_state: ResMut<NextState<scene_state::BattleState>>,
 ) {
     let Some(cave_treasures) = cave_treasures else {
         return;
@@ -43,49 +62,86 @@ pub fn check_chest_system(
 
             // 取得済みチェック
             let cave_pos = cave_treasures.cave_pos;
+            let id = floor_object_id(cave_treasures.floor, i);
             if opened_chests
                 .chests
                 .get(&cave_pos)
-                .is_some_and(|set| set.contains(&i))
+                .is_some_and(|set| set.contains(&id))
             {
                 continue;
             }
 
-            let chest_item = treasure.content;
+            let expert_in_party = party_state
+                .members
+                .iter()
+                .any(|m| m.kind == TRAP_EXPERT && m.stats.is_alive());
+
+            if treasure.trap == Some(ChestTrap::Mimic) {
+                if expert_in_party {
+                    // 見破った宝箱は開けずに残す
+                    cave_message.message = Some(format!(
+                        "{}が ミミックを みやぶった！ ちかよらない ほうが よさそうだ。",
+                        TRAP_EXPERT.name(),
+                    ));
+                    continue;
+                }
+                // 宝箱ごと魔物だったので中身は手に入らない
+                opened_chests.chests.entry(cave_pos).or_default().insert(id);
+                commands.insert_resource(MimicBattlePending);
+                next_battle_state.set(scene_state::BattleState::Active);
+            } else {
+                let chest_item = treasure.content;
 
-            // 先頭メンバーから順にインベントリ追加を試みる
-            let mut added = false;
-            let mut receiver_name = String::new();
-            for member in &mut party_state.members {
-                if member.inventory.try_add(chest_item, 1) {
-                    receiver_name = member.kind.name().to_string();
+                // 先頭メンバーから順にインベントリ追加を試みる
+                let mut added = false;
+                let mut receiver_name = String::new();
+                for member in &mut party_state.members {
+                    if member.inventory.try_add(chest_item, 1) {
+                        receiver_name = member.kind.name().to_string();
+                        added = true;
+                        break;
+                    }
+                }
+                // 全員満杯 → 袋にフォールバック
+                if !added && party_state.bag.try_add(chest_item, 1) {
+                    receiver_name = "ふくろ".to_string();
                     added = true;
-                    break;
                 }
-            }
-            // 全員満杯 → 袋にフォールバック
-            if !added && party_state.bag.try_add(chest_item, 1) {
-                receiver_name = "ふくろ".to_string();
-                added = true;
-            }
 
-            if added {
-                cave_message.message = Some(format!(
+                if !added {
+                    cave_message.message =
+                        Some("もちものも ふくろも いっぱいだ！".to_string());
+                    // 取得失敗 → 宝箱は残す（取得済みに記録しない）
+                    continue;
+                }
+
+                let obtained = format!(
                     "たからばこから {}を てにいれた！（{}）",
                     chest_item.name(),
                     receiver_name,
-                ));
+                );
+                let trap_message = match treasure.trap {
+                    Some(_) if expert_in_party => {
+                        Some(format!("{}が わなを はずした！", TRAP_EXPERT.name()))
+                    }
+                    Some(trap) => {
+                        if trap == ChestTrap::Alarm {
+                            next_battle_state.set(scene_state::BattleState::Active);
+                        }
+                        Some(spring_chest_trap(trap, &mut party_state))
+                    }
+                    None => None,
+                };
+                cave_message.message = Some(match trap_message {
+                    Some(trap_message) => format!("{} {}", trap_message, obtained),
+                    None => obtained,
+                });
                 // 取得済みに記録
                 opened_chests
                     .chests
                     .entry(cave_pos)
                     .or_default()
-                    .insert(i);
-            } else {
-                cave_message.message =
-                    Some("もちものも ふくろも いっぱいだ！".to_string());
-                // 取得失敗 → 宝箱は残す（取得済みに記録しない）
-                continue;
+                    .insert(id);
             }
 
             // structures を ChestOpen に変更し、タイルを再描画
@@ -102,6 +158,192 @@ pub fn check_chest_system(
     }
 }
 
+/// 宝箱のわなを発動させ、結果のメッセージを返す
+///
+/// フィールドのわなでは倒れず、HPは1残る。
+fn spring_chest_trap(trap: ChestTrap, party_state: &mut PartyState) -> String {
+    let trap_damage = |stats: &mut CombatStats, divisor: i32| {
+        let damage = (stats.max_hp / divisor).max(1).min(stats.hp - 1);
+        stats.take_damage(damage);
+        damage
+    };
+    match trap {
+        ChestTrap::PoisonGas => {
+            for member in party_state.members.iter_mut().filter(|m| m.stats.is_alive()) {
+                trap_damage(&mut member.stats, POISON_GAS_DIVISOR);
+            }
+            "どくガスが ふきだした！ みんなは ダメージを うけた。".to_string()
+        }
+        ChestTrap::Arrow => {
+            let Some(member) = party_state.members.iter_mut().find(|m| m.stats.is_alive()) else {
+                return "やが とんできた！".to_string();
+            };
+            let damage = trap_damage(&mut member.stats, ARROW_DIVISOR);
+            format!("やが とんできた！ {}は {}の ダメージを うけた。", member.kind.name(), damage)
+        }
+        ChestTrap::Alarm => "けたたましい おとが なりひびいた！ まものが あつまってくる！".to_string(),
+        ChestTrap::Mimic => "たからばこは ミミックだった！".to_string(),
+    }
+}
+
+/// カギのかかった扉に入ったら扉を開け、開けた扉を記録するシステム
+///
+/// 扉へはカギを持っているときだけ移動できるので、到着した時点で開ける。
+#[allow(clippy::too_many_arguments)]
+pub fn check_locked_door_system(
+    mut commands: Commands,
+    mut events: MessageReader<TileEnteredEvent>,
+    player_query: Query<&TilePosition, With<Player>>,
+    cave_doors: Option<Res<CaveDoors>>,
+    mut opened_doors: ResMut<OpenedDoors>,
+    mut cave_message: ResMut<FieldMessageState>,
+    mut active_map: ResMut<ActiveMap>,
+    mut tile_map: ResMut<SimpleTileMap>,
+) {
+    let Some(cave_doors) = cave_doors else {
+        return;
+    };
+
+    for _event in events.read() {
+        let Ok(tile_pos) = player_query.single() else {
+            continue;
+        };
+
+        if active_map.structure_at(tile_pos.x, tile_pos.y) != Structure::LockedDoor {
+            continue;
+        }
+        let Some(i) = cave_doors
+            .doors
+            .iter()
+            .position(|&(x, y)| x == tile_pos.x && y == tile_pos.y)
+        else {
+            continue;
+        };
+
+        opened_doors
+            .doors
+            .entry(cave_doors.cave_pos)
+            .or_default()
+            .insert(floor_object_id(cave_doors.floor, i));
+        cave_message.message = Some("どうのカギで とびらを あけた！".to_string());
+
+        // structures から扉を取り除き、タイルを再描画
+        active_map.structures[tile_pos.y][tile_pos.x] = Structure::None;
+        let key = (tile_pos.x as i32, tile_pos.y as i32);
+        if let Some(entity) = tile_map.active_tiles.remove(&key) {
+            commands.entity(entity).despawn();
+        }
+        if let Some(entity) = tile_map.structure_overlays.remove(&key) {
+            commands.entity(entity).despawn();
+        }
+        tile_map.last_player_pos = None;
+    }
+}
+
+/// カギを持たずに扉へ進もうとしたら知らせるシステム
+pub fn check_door_blocked_system(
+    mut events: MessageReader<MovementBlockedEvent>,
+    player_query: Query<&TilePosition, With<Player>>,
+    mut cave_message: ResMut<FieldMessageState>,
+    active_map: Res<ActiveMap>,
+) {
+    for event in events.read() {
+        let Ok(tile_pos) = player_query.single() else {
+            continue;
+        };
+        let x = tile_pos.x as i32 + event.direction.0;
+        let y = tile_pos.y as i32 + event.direction.1;
+        if x < 0 || y < 0 || x >= active_map.width as i32 || y >= active_map.height as i32 {
+            continue;
+        }
+        if active_map.structure_at(x as usize, y as usize) == Structure::LockedDoor {
+            cave_message.message = Some("とびらには カギが かかっている。".to_string());
+        }
+    }
+}
+
+/// 下り階段に乗ったら次の階を生成して移動するシステム
+#[allow(clippy::too_many_arguments)]
+pub fn check_stairs_system(
+    mut commands: Commands,
+    mut events: MessageReader<TileEnteredEvent>,
+    mut player_query: Query<(&mut TilePosition, &mut Transform), With<Player>>,
+    symbol_query: Query<Entity, With<SymbolEnemy>>,
+    field_return: Option<ResMut<FieldReturnState>>,
+    mut encounter_zone: ResMut<EncounterZone>,
+    opened_chests: Res<OpenedChests>,
+    opened_doors: Res<OpenedDoors>,
+    continent_caves: Res<ContinentCavePositions>,
+    party_state: Res<PartyState>,
+    char_params: Res<CharacterParams>,
+    world_seed: Res<WorldSeed>,
+    mut cave_message: ResMut<FieldMessageState>,
+    mut active_map: ResMut<ActiveMap>,
+    mut tile_map: ResMut<SimpleTileMap>,
+) {
+    let Some(mut field_return) = field_return else {
+        return;
+    };
+
+    for _event in events.read() {
+        let Ok((mut tile_pos, mut transform)) = player_query.single_mut() else {
+            continue;
+        };
+
+        if active_map.structure_at(tile_pos.x, tile_pos.y) != Structure::StairsDown {
+            continue;
+        }
+        if field_return.floor + 1 >= field_return.floor_count {
+            continue;
+        }
+
+        field_return.floor += 1;
+        encounter_zone.floor = field_return.floor;
+
+        let cave_world_pos = (field_return.player_tile_x, field_return.player_tile_y);
+        let guaranteed_items =
+            cave_guaranteed_items(cave_world_pos, &continent_caves, &party_state, &char_params);
+        let (floor_map, cave_treasures, cave_doors, (spawn_x, spawn_y)) = build_cave_floor(
+            &world_seed,
+            cave_world_pos,
+            encounter_zone.continent_id,
+            field_return.floor,
+            field_return.floor_count,
+            &guaranteed_items,
+            &opened_chests,
+            &opened_doors,
+        );
+        *active_map = floor_map;
+        commands.insert_resource(cave_treasures);
+        commands.insert_resource(cave_doors);
+
+        // 前の階のタイルとシンボル敵を破棄して再描画させる
+        for entity in &symbol_query {
+            commands.entity(entity).despawn();
+        }
+        for (_, entity) in tile_map.active_tiles.drain() {
+            commands.entity(entity).despawn();
+        }
+        for (_, entity) in tile_map.structure_overlays.drain() {
+            commands.entity(entity).despawn();
+        }
+        tile_map.last_player_pos = None;
+
+        tile_pos.x = spawn_x;
+        tile_pos.y = spawn_y;
+        let (world_x, world_y) = active_map.to_world(spawn_x, spawn_y);
+        transform.translation.x = world_x;
+        transform.translation.y = world_y;
+
+        cave_message.message = Some(if field_return.floor + 1 == field_return.floor_count {
+            format!("ちか {}かいに おりた。ここが さいかそうの ようだ。", field_return.floor + 1)
+        } else {
+            format!("ちか {}かいに おりた。", field_return.floor + 1)
+        });
+        return;
+    }
+}
+
 /// ボスに隣接したら戦闘を開始するシステム
 pub fn check_boss_proximity_system(
     mut commands: Commands,
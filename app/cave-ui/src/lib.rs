@@ -6,25 +6,38 @@ use scene_state::{BattleState, SceneState};
 use input_ui::InputSystemSet;
 use field_walk_ui::{start_bounce, start_smooth_move, update_bounce, update_smooth_move};
 use field_walk_ui::{camera_follow, check_encounter_system, handle_simple_move_completed, player_movement, reset_map_mode_system, toggle_map_mode_system, update_simple_tiles};
+use field_walk_ui::{
+    cave_random_encounter_enabled, cave_symbol_encounter_enabled, despawn_symbol_enemies,
+    move_symbol_enemies_system, spawn_symbol_enemies_system, CaveEncounterMode,
+};
 use field_walk_ui::{field_message_not_active, field_message_input_system, field_message_display_system};
 
-pub use input::{check_boss_proximity_system, check_chest_system};
+pub use input::{
+    check_boss_proximity_system, check_chest_system, check_door_blocked_system, check_locked_door_system,
+    check_stairs_system,
+};
 pub use scene::{
     despawn_cave_entities, restore_field_from_cave, setup_boss_cave_scene, setup_cave_scene,
-    BossCaveState,
+    BossCaveState, CaveDoors, CaveTreasures, FieldReturnState,
 };
 
 pub struct CavePlugin;
 
 impl Plugin for CavePlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<CaveEncounterMode>();
+
         // OnEnter
         app.add_systems(OnEnter(SceneState::Cave), setup_cave_scene);
         app.add_systems(OnEnter(SceneState::BossCave), setup_boss_cave_scene);
 
         // Cave/BossCave 共通Updateシステム（1チェーンに統合）
         // - check_chest_system: BossCaveでは宝箱エンティティがないためno-op
-        // - check_encounter_system: BossCaveではencounter_rate=0.0のためno-op
+        // - check_locked_door_system: BossCaveには扉がないためno-op
+        // - check_stairs_system: BossCaveには下り階段がないためno-op
+        // - エンカウントはCaveEncounterModeで切り替える（既定はシンボル）
+        //   BossCaveではencounter_rate=0.0のため、どちらの方式でも敵は出ない
+        // - spawn_symbol_enemies_system: 階段で階が変わった後の古いシンボル敵の削除を反映してから出現させる
         // - check_boss_proximity_system: CaveではBossEntityがないためno-op
         app.add_systems(
             Update,
@@ -39,10 +52,15 @@ impl Plugin for CavePlugin {
                 update_bounce,
                 update_simple_tiles,
                 camera_follow,
+                move_symbol_enemies_system.run_if(cave_symbol_encounter_enabled),
                 check_chest_system,
+                check_locked_door_system,
+                check_door_blocked_system,
+                check_stairs_system,
+                (ApplyDeferred, spawn_symbol_enemies_system.run_if(cave_symbol_encounter_enabled)).chain(),
                 field_message_input_system.in_set(InputSystemSet::MessageInput),
                 field_message_display_system,
-                check_encounter_system,
+                check_encounter_system.run_if(cave_random_encounter_enabled),
                 check_boss_proximity_system,
             )
                 .chain()
@@ -55,11 +73,11 @@ impl Plugin for CavePlugin {
         // OnExit
         app.add_systems(
             OnExit(SceneState::Cave),
-            (reset_map_mode_system, despawn_cave_entities, restore_field_from_cave).chain(),
+            (reset_map_mode_system, despawn_cave_entities, despawn_symbol_enemies, restore_field_from_cave).chain(),
         );
         app.add_systems(
             OnExit(SceneState::BossCave),
-            (reset_map_mode_system, despawn_cave_entities, restore_field_from_cave).chain(),
+            (reset_map_mode_system, despawn_cave_entities, despawn_symbol_enemies, restore_field_from_cave).chain(),
         );
     }
 }
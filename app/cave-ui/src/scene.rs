@@ -1,37 +1,51 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-use rand::SeedableRng;
-use rand_chacha::ChaCha8Rng;
-
-use cave::{generate_boss_cave_map, generate_cave_map, TreasureChest, CAVE_HEIGHT, CAVE_WIDTH};
+use cave::{
+    cave_floor_count, floor_object_id, generate_boss_cave_map, generate_cave_floor, TreasureChest, CAVE_HEIGHT,
+    CAVE_WIDTH,
+};
 use item_data::ItemKey;
 use party::{RecruitmentPath, RecruitmentStatus};
 use terrain::Structure;
 
 use party_state::{CharacterParams, PartyState};
-use world_state::{ContinentCavePositions, ContinentMap, EncounterZone};
-use progress_state::{BossDefeated, OpenedChests};
+use world_state::{ContinentCavePositions, ContinentMap, EncounterZone, RngStream, WorldSeed};
+use progress_state::{BossDefeated, OpenedChests, OpenedDoors};
 use field_core::{ActiveMap, Boat, Player, TilePosition, WorldMapData, TILE_SIZE};
 use field_walk_ui::{FieldMessageState, FieldMessageUI, MovementState};
 
 use field_walk_ui::{spawn_boat_entities, BoatSpawnsResource, BossCaveWorldPos, MapModeState, TileTextures};
 use field_walk_ui::{create_tile_pool, PooledTile, SimpleTile, SimpleTileMap, StructureOverlay, TilePool};
 
-/// 洞窟進入前のフィールド座標を保存
+/// 洞窟進入前のフィールド座標と、洞窟内の現在の階を保存
 #[derive(Resource)]
 pub struct FieldReturnState {
     pub player_tile_x: usize,
     pub player_tile_y: usize,
+    /// 現在の階（0=1階）
+    pub floor: u8,
+    /// この洞窟の階層数
+    pub floor_count: u8,
 }
 
 /// 現在入っている洞窟の宝箱情報
 #[derive(Resource)]
 pub struct CaveTreasures {
     pub cave_pos: (usize, usize),
+    /// 宝箱がある階（開封済み記録のIDに使う）
+    pub floor: u8,
     pub treasures: Vec<TreasureChest>,
 }
 
+/// 現在いる階のカギのかかった扉
+#[derive(Resource)]
+pub struct CaveDoors {
+    pub cave_pos: (usize, usize),
+    pub floor: u8,
+    pub doors: Vec<(usize, usize)>,
+}
+
 /// ボスエンティティのマーカー
 #[derive(Component)]
 pub struct BossEntity {
@@ -56,12 +70,14 @@ pub fn setup_cave_scene(
     mut move_state: ResMut<MovementState>,
     active_map: Res<ActiveMap>,
     opened_chests: Res<OpenedChests>,
+    opened_doors: Res<OpenedDoors>,
     mut boat_spawns: ResMut<BoatSpawnsResource>,
     mut map_mode_state: ResMut<MapModeState>,
     continent_caves: Res<ContinentCavePositions>,
     continent_map: Option<Res<ContinentMap>>,
     party_state: Res<PartyState>,
     char_params: Res<CharacterParams>,
+    world_seed: Res<WorldSeed>,
 ) {
     // ワールドでマップモードがONのまま洞窟に入った場合にリセット
     map_mode_state.enabled = false;
@@ -70,16 +86,10 @@ pub fn setup_cave_scene(
     for entity in &structure_overlay_query {
         commands.entity(entity).despawn();
     }
-    // フィールド座標を保存
     let Ok((mut tile_pos, mut transform)) = player_query.single_mut() else {
         return;
     };
 
-    commands.insert_resource(FieldReturnState {
-        player_tile_x: tile_pos.x,
-        player_tile_y: tile_pos.y,
-    });
-
     // ワールドマップを退避
     commands.insert_resource(WorldMapData(active_map.clone()));
 
@@ -105,54 +115,122 @@ pub fn setup_cave_scene(
     commands.insert_resource(EncounterZone {
         continent_id: cave_continent_id,
         is_cave: true,
+        floor: 0,
     });
 
-    // 洞窟マップ生成（ワールドマップ座標からシードを決定し、同じ洞窟は常に同じ形にする）
-    let seed = tile_pos.x as u64 * 10007 + tile_pos.y as u64;
-    let mut rng = ChaCha8Rng::seed_from_u64(seed);
-    let guaranteed_items: Vec<ItemKey> =
-        if let Some((continent_idx, caves)) = continent_caves
-            .caves_by_continent
-            .iter()
-            .enumerate()
-            .find(|(_, caves)| caves.contains(&cave_world_pos))
-        {
-            let num_caves = caves.len();
-            let cave_idx = caves.iter().position(|&c| c == cave_world_pos).unwrap();
-            let base = 3 / num_caves;
-            let remainder = 3 % num_caves;
-            let count = base + if cave_idx < remainder { 1 } else { 0 };
-            let mut items = vec![ItemKey::MoonFragment; count];
-
-            // 未加入の ItemTrade キャラ用アイテムを確定スポーン（所属大陸のみ）
-            for (cand_idx, candidate) in party_state.candidates.iter().enumerate() {
-                if candidate.status == RecruitmentStatus::Recruited {
-                    continue;
-                }
-                if cand_idx / 3 != continent_idx {
-                    continue;
-                }
-                if let RecruitmentPath::ItemTrade { item } = char_params.recruit_method(candidate.kind) {
-                    // 大陸内の洞窟間で分散配置（候補インデックスを洞窟数で割る）
-                    let candidate_hash = candidate.kind as usize;
-                    if num_caves > 0 && candidate_hash % num_caves == cave_idx {
-                        items.push(*item);
-                    }
-                }
+    // フィールド座標を保存し、1階から探索を始める
+    let floor_count = cave_floor_count(cave_continent_id);
+    commands.insert_resource(FieldReturnState {
+        player_tile_x: tile_pos.x,
+        player_tile_y: tile_pos.y,
+        floor: 0,
+        floor_count,
+    });
+
+    let guaranteed_items =
+        cave_guaranteed_items(cave_world_pos, &continent_caves, &party_state, &char_params);
+    let (active_map_resource, cave_treasures, cave_doors, (spawn_x, spawn_y)) = build_cave_floor(
+        &world_seed,
+        cave_world_pos,
+        cave_continent_id,
+        0,
+        floor_count,
+        &guaranteed_items,
+        &opened_chests,
+        &opened_doors,
+    );
+
+    // 宝箱・扉情報をリソースとして保存
+    commands.insert_resource(cave_treasures);
+    commands.insert_resource(cave_doors);
+    commands.insert_resource(FieldMessageState::default());
+
+    // プレイヤーを洞窟のスポーン位置に移動（insert_resourceでmoveされる前に座標計算）
+    tile_pos.x = spawn_x;
+    tile_pos.y = spawn_y;
+    let (world_x, world_y) = active_map_resource.to_world(spawn_x, spawn_y);
+    transform.translation.x = world_x;
+    transform.translation.y = world_y;
+
+    commands.insert_resource(active_map_resource);
+
+    // 洞窟用タイルプールを初期化
+    commands.insert_resource(SimpleTileMap {
+        active_tiles: HashMap::new(),
+        structure_overlays: HashMap::new(),
+        last_player_pos: None,
+    });
+
+    // MovementStateをリセット
+    *move_state = MovementState::default();
+}
+
+/// 洞窟の最下層に確定で置くアイテムを求める
+pub(crate) fn cave_guaranteed_items(
+    cave_world_pos: (usize, usize),
+    continent_caves: &ContinentCavePositions,
+    party_state: &PartyState,
+    char_params: &CharacterParams,
+) -> Vec<ItemKey> {
+    let Some((continent_idx, caves)) = continent_caves
+        .caves_by_continent
+        .iter()
+        .enumerate()
+        .find(|(_, caves)| caves.contains(&cave_world_pos))
+    else {
+        return vec![];
+    };
+
+    let num_caves = caves.len();
+    let cave_idx = caves.iter().position(|&c| c == cave_world_pos).unwrap();
+    let base = 3 / num_caves;
+    let remainder = 3 % num_caves;
+    let count = base + if cave_idx < remainder { 1 } else { 0 };
+    let mut items = vec![ItemKey::MoonFragment; count];
+
+    // 未加入の ItemTrade キャラ用アイテムを確定スポーン（所属大陸のみ）
+    for (cand_idx, candidate) in party_state.candidates.iter().enumerate() {
+        if candidate.status == RecruitmentStatus::Recruited {
+            continue;
+        }
+        if cand_idx / 3 != continent_idx {
+            continue;
+        }
+        if let RecruitmentPath::ItemTrade { item } = char_params.recruit_method(candidate.kind) {
+            // 大陸内の洞窟間で分散配置（候補インデックスを洞窟数で割る）
+            let candidate_hash = candidate.kind as usize;
+            if num_caves > 0 && candidate_hash % num_caves == cave_idx {
+                items.push(*item);
             }
+        }
+    }
 
-            items
-        } else {
-            vec![]
-        };
-    let cave_data = generate_cave_map(&mut rng, &guaranteed_items);
-    let (spawn_x, spawn_y) = cave_data.spawn_position;
+    items
+}
+
+/// 洞窟の指定した階のActiveMapと宝箱・扉情報を作り、スポーン位置とともに返す
+///
+/// 洞窟マップはワールドシードと座標から決まるため、同じ洞窟の同じ階は常に同じ形になる。
+/// 地形生成アルゴリズムは大陸に応じて洞窟ごとに選ばれる。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_cave_floor(
+    world_seed: &WorldSeed,
+    cave_world_pos: (usize, usize),
+    continent_id: u8,
+    floor: u8,
+    floor_count: u8,
+    guaranteed_items: &[ItemKey],
+    opened_chests: &OpenedChests,
+    opened_doors: &OpenedDoors,
+) -> (ActiveMap, CaveTreasures, CaveDoors, (usize, usize)) {
+    let mut rng = world_seed.location_rng(RngStream::Cave, cave_world_pos.0, cave_world_pos.1);
+    let cave_data = generate_cave_floor(&mut rng, continent_id, floor, floor_count, guaranteed_items);
 
     // 洞窟用ActiveMapを作成
     let cave_origin_x = -(CAVE_WIDTH as f32 * TILE_SIZE) / 2.0 + TILE_SIZE / 2.0;
     let cave_origin_y = -(CAVE_HEIGHT as f32 * TILE_SIZE) / 2.0 + TILE_SIZE / 2.0;
 
-    let mut active_map_resource = ActiveMap {
+    let mut active_map = ActiveMap {
         grid: cave_data.grid,
         structures: cave_data.structures,
         width: cave_data.width,
@@ -165,36 +243,30 @@ pub fn setup_cave_scene(
     // 開封済み宝箱を structures に反映
     let opened_set = opened_chests.chests.get(&cave_world_pos);
     for (i, treasure) in cave_data.treasures.iter().enumerate() {
-        if opened_set.is_some_and(|set| set.contains(&i)) {
-            active_map_resource.structures[treasure.y][treasure.x] = Structure::ChestOpen;
+        if opened_set.is_some_and(|set| set.contains(&floor_object_id(floor, i))) {
+            active_map.structures[treasure.y][treasure.x] = Structure::ChestOpen;
         }
     }
 
-    // 宝箱情報をリソースとして保存
-    commands.insert_resource(CaveTreasures {
+    // 開けた扉は取り除く
+    let opened_door_set = opened_doors.doors.get(&cave_world_pos);
+    for (i, &(x, y)) in cave_data.doors.iter().enumerate() {
+        if opened_door_set.is_some_and(|set| set.contains(&floor_object_id(floor, i))) {
+            active_map.structures[y][x] = Structure::None;
+        }
+    }
+
+    let treasures = CaveTreasures {
         cave_pos: cave_world_pos,
+        floor,
         treasures: cave_data.treasures,
-    });
-    commands.insert_resource(FieldMessageState::default());
-
-    // プレイヤーを洞窟のスポーン位置に移動（insert_resourceでmoveされる前に座標計算）
-    tile_pos.x = spawn_x;
-    tile_pos.y = spawn_y;
-    let (world_x, world_y) = active_map_resource.to_world(spawn_x, spawn_y);
-    transform.translation.x = world_x;
-    transform.translation.y = world_y;
-
-    commands.insert_resource(active_map_resource);
-
-    // 洞窟用タイルプールを初期化
-    commands.insert_resource(SimpleTileMap {
-        active_tiles: HashMap::new(),
-        structure_overlays: HashMap::new(),
-        last_player_pos: None,
-    });
-
-    // MovementStateをリセット
-    *move_state = MovementState::default();
+    };
+    let doors = CaveDoors {
+        cave_pos: cave_world_pos,
+        floor,
+        doors: cave_data.doors,
+    };
+    (active_map, treasures, doors, cave_data.spawn_position)
 }
 
 /// ボス洞窟シーンのセットアップ
@@ -211,6 +283,7 @@ pub fn setup_boss_cave_scene(
     _boss_cave_world_pos: Res<BossCaveWorldPos>,
     boss_defeated: Option<Res<BossDefeated>>,
     mut boat_spawns: ResMut<BoatSpawnsResource>,
+    world_seed: Res<WorldSeed>,
 ) {
     let Ok((mut tile_pos, mut transform)) = player_query.single_mut() else {
         return;
@@ -221,10 +294,12 @@ pub fn setup_boss_cave_scene(
         commands.entity(entity).despawn();
     }
 
-    // フィールド座標を保存
+    // フィールド座標を保存（ボス洞窟は1階のみ）
     commands.insert_resource(FieldReturnState {
         player_tile_x: tile_pos.x,
         player_tile_y: tile_pos.y,
+        floor: 0,
+        floor_count: 1,
     });
 
     // ワールドマップを退避
@@ -245,8 +320,7 @@ pub fn setup_boss_cave_scene(
 
     // ボス洞窟マップ生成
     let cave_world_pos = (tile_pos.x, tile_pos.y);
-    let seed = tile_pos.x as u64 * 10007 + tile_pos.y as u64 + 999;
-    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut rng = world_seed.location_rng(RngStream::BossCave, tile_pos.x, tile_pos.y);
     let cave_data = generate_boss_cave_map(&mut rng);
     let (spawn_x, spawn_y) = cave_data.spawn_position;
 
@@ -329,6 +403,7 @@ pub fn despawn_cave_entities(
     }
     commands.remove_resource::<SimpleTileMap>();
     commands.remove_resource::<CaveTreasures>();
+    commands.remove_resource::<CaveDoors>();
     commands.remove_resource::<FieldMessageState>();
     commands.remove_resource::<BossCaveState>();
 }
@@ -0,0 +1,313 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use item_data::ItemKey;
+use terrain::Terrain;
+
+use crate::{floor_distances, furnish_cave, CaveMapData, CAVE_HEIGHT, CAVE_WIDTH};
+
+pub(crate) const RANDOM_WALK_STEPS: usize = 400;
+
+/// 部屋分割を止める区画の最小サイズ
+const BSP_MIN_LEAF: usize = 8;
+
+/// セルオートマトンの初期の壁の割合
+const CAVERN_WALL_CHANCE: f64 = 0.45;
+const CAVERN_SMOOTH_STEPS: usize = 4;
+/// これより狭い洞窟になったら作り直す
+const CAVERN_MIN_FLOOR: usize = 150;
+const CAVERN_MAX_ATTEMPTS: usize = 10;
+
+/// 迷路に追加で開ける壁の数（行き止まりを減らして回り道を作る）
+const MAZE_EXTRA_OPENINGS: usize = 20;
+
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// 洞窟の地形生成アルゴリズム
+///
+/// `carve` で通路を掘り、梯子・階段・宝箱の配置は共通の `generate` で行う。
+pub trait CaveGenerator {
+    /// 床（CaveFloor）と壁（CaveWall）からなる地形とスポーン位置を返す
+    ///
+    /// 外周1マスは壁のまま残し、すべての床はスポーンから歩いて行けること。
+    fn carve(&self, rng: &mut impl Rng) -> (Vec<Vec<Terrain>>, (usize, usize));
+
+    /// 洞窟の1階層分を生成する
+    fn generate(
+        &self,
+        rng: &mut impl Rng,
+        floor: u8,
+        floor_count: u8,
+        guaranteed_items: &[ItemKey],
+    ) -> CaveMapData {
+        let (grid, spawn_position) = self.carve(rng);
+        furnish_cave(rng, grid, spawn_position, floor, floor_count, guaranteed_items)
+    }
+}
+
+/// 中央からのランダムウォークで掘る（丸みのある塊状の洞窟）
+pub struct RandomWalkGenerator {
+    pub steps: usize,
+}
+
+impl CaveGenerator for RandomWalkGenerator {
+    fn carve(&self, rng: &mut impl Rng) -> (Vec<Vec<Terrain>>, (usize, usize)) {
+        let mut grid = vec![vec![Terrain::CaveWall; CAVE_WIDTH]; CAVE_HEIGHT];
+
+        let mut x = CAVE_WIDTH / 2;
+        let mut y = CAVE_HEIGHT / 2;
+        grid[y][x] = Terrain::CaveFloor;
+
+        for _ in 0..self.steps {
+            let (dx, dy) = DIRECTIONS[rng.gen_range(0..4)];
+            let nx = x as i32 + dx;
+            let ny = y as i32 + dy;
+
+            // 外周1マスは壁として残す
+            if nx >= 1 && nx < (CAVE_WIDTH as i32 - 1) && ny >= 1 && ny < (CAVE_HEIGHT as i32 - 1) {
+                x = nx as usize;
+                y = ny as usize;
+                grid[y][x] = Terrain::CaveFloor;
+            }
+        }
+
+        (grid, (CAVE_WIDTH / 2, CAVE_HEIGHT / 2))
+    }
+}
+
+/// 区画を再帰的に分割し、部屋を通路でつなぐ
+pub struct BspRoomsGenerator;
+
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+impl CaveGenerator for BspRoomsGenerator {
+    fn carve(&self, rng: &mut impl Rng) -> (Vec<Vec<Terrain>>, (usize, usize)) {
+        let mut grid = vec![vec![Terrain::CaveWall; CAVE_WIDTH]; CAVE_HEIGHT];
+        let interior = Rect { x: 1, y: 1, w: CAVE_WIDTH - 2, h: CAVE_HEIGHT - 2 };
+        let spawn = carve_bsp(&mut grid, interior, rng);
+        (grid, spawn)
+    }
+}
+
+/// 区画を分割して部屋を掘り、兄弟区画の部屋同士を通路でつなぐ（区画内の部屋の中心を返す）
+fn carve_bsp(grid: &mut [Vec<Terrain>], rect: Rect, rng: &mut impl Rng) -> (usize, usize) {
+    let can_split_x = rect.w >= BSP_MIN_LEAF * 2;
+    let can_split_y = rect.h >= BSP_MIN_LEAF * 2;
+    if !can_split_x && !can_split_y {
+        return carve_room(grid, rect, rng);
+    }
+
+    let split_x = if can_split_x && can_split_y { rng.gen_bool(0.5) } else { can_split_x };
+    let (a, b) = if split_x {
+        let cut = rng.gen_range(BSP_MIN_LEAF..=rect.w - BSP_MIN_LEAF);
+        (
+            Rect { w: cut, ..rect },
+            Rect { x: rect.x + cut, w: rect.w - cut, ..rect },
+        )
+    } else {
+        let cut = rng.gen_range(BSP_MIN_LEAF..=rect.h - BSP_MIN_LEAF);
+        (
+            Rect { h: cut, ..rect },
+            Rect { y: rect.y + cut, h: rect.h - cut, ..rect },
+        )
+    };
+
+    let center_a = carve_bsp(grid, a, rng);
+    let center_b = carve_bsp(grid, b, rng);
+    carve_corridor(grid, center_a, center_b, rng);
+    if rng.gen_bool(0.5) { center_a } else { center_b }
+}
+
+/// 区画の内側（1マスの余白を残す）に部屋を掘る
+fn carve_room(grid: &mut [Vec<Terrain>], rect: Rect, rng: &mut impl Rng) -> (usize, usize) {
+    let w = rng.gen_range(3..=rect.w - 2);
+    let h = rng.gen_range(3..=rect.h - 2);
+    let x = rng.gen_range(rect.x + 1..=rect.x + rect.w - 1 - w);
+    let y = rng.gen_range(rect.y + 1..=rect.y + rect.h - 1 - h);
+    for row in grid.iter_mut().skip(y).take(h) {
+        for tile in row.iter_mut().skip(x).take(w) {
+            *tile = Terrain::CaveFloor;
+        }
+    }
+    (x + w / 2, y + h / 2)
+}
+
+/// 2点をL字の通路でつなぐ
+fn carve_corridor(grid: &mut [Vec<Terrain>], from: (usize, usize), to: (usize, usize), rng: &mut impl Rng) {
+    let corner = if rng.gen_bool(0.5) { (to.0, from.1) } else { (from.0, to.1) };
+    for (a, b) in [(from, corner), (corner, to)] {
+        for row in &mut grid[a.1.min(b.1)..=a.1.max(b.1)] {
+            for tile in &mut row[a.0.min(b.0)..=a.0.max(b.0)] {
+                *tile = Terrain::CaveFloor;
+            }
+        }
+    }
+}
+
+/// セルオートマトンで自然な空洞を作る（つながっていない小部屋は埋める）
+pub struct CellularCavernGenerator;
+
+impl CaveGenerator for CellularCavernGenerator {
+    fn carve(&self, rng: &mut impl Rng) -> (Vec<Vec<Terrain>>, (usize, usize)) {
+        for _ in 0..CAVERN_MAX_ATTEMPTS {
+            let mut grid = vec![vec![Terrain::CaveWall; CAVE_WIDTH]; CAVE_HEIGHT];
+            for row in grid.iter_mut().take(CAVE_HEIGHT - 1).skip(1) {
+                for tile in row.iter_mut().take(CAVE_WIDTH - 1).skip(1) {
+                    if !rng.gen_bool(CAVERN_WALL_CHANCE) {
+                        *tile = Terrain::CaveFloor;
+                    }
+                }
+            }
+            for _ in 0..CAVERN_SMOOTH_STEPS {
+                grid = smooth_cavern(&grid);
+            }
+
+            let Some(spawn) = nearest_floor_to_center(&grid) else {
+                continue;
+            };
+            // スポーンから届かない床は壁で埋める
+            let dist = floor_distances(&grid, spawn);
+            let mut floor_count = 0;
+            for (y, row) in grid.iter_mut().enumerate() {
+                for (x, tile) in row.iter_mut().enumerate() {
+                    if *tile == Terrain::CaveFloor {
+                        if dist[y][x] == u32::MAX {
+                            *tile = Terrain::CaveWall;
+                        } else {
+                            floor_count += 1;
+                        }
+                    }
+                }
+            }
+            if floor_count >= CAVERN_MIN_FLOOR {
+                return (grid, spawn);
+            }
+        }
+
+        // 狭い洞窟しかできなかったらランダムウォークで代用
+        RandomWalkGenerator { steps: RANDOM_WALK_STEPS }.carve(rng)
+    }
+}
+
+/// 周囲8マスの壁の数で壁・床を決め直す（外周は壁のまま）
+fn smooth_cavern(grid: &[Vec<Terrain>]) -> Vec<Vec<Terrain>> {
+    let mut next = grid.to_vec();
+    for y in 1..CAVE_HEIGHT - 1 {
+        for x in 1..CAVE_WIDTH - 1 {
+            let walls = (-1i32..=1)
+                .flat_map(|dy| (-1i32..=1).map(move |dx| (dx, dy)))
+                .filter(|&(dx, dy)| (dx, dy) != (0, 0))
+                .filter(|&(dx, dy)| {
+                    grid[(y as i32 + dy) as usize][(x as i32 + dx) as usize] == Terrain::CaveWall
+                })
+                .count();
+            if walls > 4 {
+                next[y][x] = Terrain::CaveWall;
+            } else if walls < 4 {
+                next[y][x] = Terrain::CaveFloor;
+            }
+        }
+    }
+    next
+}
+
+/// 中央に最も近い床タイル
+fn nearest_floor_to_center(grid: &[Vec<Terrain>]) -> Option<(usize, usize)> {
+    let (cx, cy) = (CAVE_WIDTH as i32 / 2, CAVE_HEIGHT as i32 / 2);
+    (0..CAVE_HEIGHT)
+        .flat_map(|y| (0..CAVE_WIDTH).map(move |x| (x, y)))
+        .filter(|&(x, y)| grid[y][x] == Terrain::CaveFloor)
+        .min_by_key(|&(x, y)| (x as i32 - cx).abs() + (y as i32 - cy).abs())
+}
+
+/// 穴掘り法の迷路（いくつか壁を抜いて回り道も作る）
+pub struct MazeGenerator;
+
+impl CaveGenerator for MazeGenerator {
+    fn carve(&self, rng: &mut impl Rng) -> (Vec<Vec<Terrain>>, (usize, usize)) {
+        let mut grid = vec![vec![Terrain::CaveWall; CAVE_WIDTH]; CAVE_HEIGHT];
+        // 奇数座標をセルとし、外周に触れない範囲で迷路を掘る
+        let max_cell = |size: usize| if (size - 2) % 2 == 1 { size - 2 } else { size - 3 };
+        let (max_x, max_y) = (max_cell(CAVE_WIDTH), max_cell(CAVE_HEIGHT));
+        let is_cell = |x: i32, y: i32| x >= 1 && y >= 1 && x <= max_x as i32 && y <= max_y as i32;
+
+        let start = ((CAVE_WIDTH / 2) | 1, (CAVE_HEIGHT / 2) | 1);
+        grid[start.1][start.0] = Terrain::CaveFloor;
+        let mut stack = vec![start];
+        while let Some(&(x, y)) = stack.last() {
+            let unvisited: Vec<(usize, usize)> = DIRECTIONS
+                .iter()
+                .map(|&(dx, dy)| (x as i32 + dx * 2, y as i32 + dy * 2))
+                .filter(|&(nx, ny)| is_cell(nx, ny))
+                .map(|(nx, ny)| (nx as usize, ny as usize))
+                .filter(|&(nx, ny)| grid[ny][nx] == Terrain::CaveWall)
+                .collect();
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+            let (nx, ny) = unvisited[rng.gen_range(0..unvisited.len())];
+            grid[(y + ny) / 2][(x + nx) / 2] = Terrain::CaveFloor;
+            grid[ny][nx] = Terrain::CaveFloor;
+            stack.push((nx, ny));
+        }
+
+        // セル間の壁をいくつか抜く
+        for _ in 0..MAZE_EXTRA_OPENINGS {
+            let x = rng.gen_range(1..=max_x);
+            let y = rng.gen_range(1..=max_y);
+            // 片方の座標だけが偶数なら、2つのセルに挟まれた壁
+            if (x % 2 == 0) != (y % 2 == 0) {
+                grid[y][x] = Terrain::CaveFloor;
+            }
+        }
+
+        (grid, start)
+    }
+}
+
+/// 洞窟ごとに選ばれる地形生成アルゴリズム
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaveLayout {
+    RandomWalk,
+    Rooms,
+    Cavern,
+    Maze,
+}
+
+impl CaveLayout {
+    pub const ALL: [CaveLayout; 4] =
+        [CaveLayout::RandomWalk, CaveLayout::Rooms, CaveLayout::Cavern, CaveLayout::Maze];
+
+    /// 大陸に応じた重みで選ぶ（奥の大陸ほど部屋型や迷路が増える）
+    pub fn choose(rng: &mut impl Rng, continent_id: u8) -> CaveLayout {
+        let c = continent_id.min(5) as u32;
+        let table = [
+            (CaveLayout::RandomWalk, 5 - c),
+            (CaveLayout::Cavern, 3),
+            (CaveLayout::Rooms, 1 + c),
+            (CaveLayout::Maze, c / 2),
+        ];
+        table
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .map(|(layout, _)| *layout)
+            .unwrap_or(CaveLayout::RandomWalk)
+    }
+}
+
+impl CaveGenerator for CaveLayout {
+    fn carve(&self, rng: &mut impl Rng) -> (Vec<Vec<Terrain>>, (usize, usize)) {
+        match self {
+            CaveLayout::RandomWalk => RandomWalkGenerator { steps: RANDOM_WALK_STEPS }.carve(rng),
+            CaveLayout::Rooms => BspRoomsGenerator.carve(rng),
+            CaveLayout::Cavern => CellularCavernGenerator.carve(rng),
+            CaveLayout::Maze => MazeGenerator.carve(rng),
+        }
+    }
+}
@@ -1,21 +1,58 @@
+mod generator;
+
 use rand::seq::SliceRandom;
 use rand::Rng;
 
 use item_data::ItemKey;
 use terrain::{Structure, Terrain};
 
+pub use generator::{
+    BspRoomsGenerator, CaveGenerator, CaveLayout, CellularCavernGenerator, MazeGenerator,
+    RandomWalkGenerator,
+};
+use generator::RANDOM_WALK_STEPS;
+
 pub const CAVE_WIDTH: usize = 30;
 pub const CAVE_HEIGHT: usize = 30;
 
-const RANDOM_WALK_STEPS: usize = 400;
 const MAX_TREASURES: usize = 3;
 
+/// 洞窟の最大階層数
+pub const MAX_CAVE_FLOORS: u8 = 4;
+
+/// 階ごとの宝箱・扉IDの間隔（1階の宝箱は従来どおり0始まりの番号になる）
+const FLOOR_OBJECT_STRIDE: usize = 100;
+
+/// 宝箱の行き止まりに扉を置く確率
+const LOCKED_DOOR_CHANCE: f64 = 0.6;
+
+/// 1階の宝箱にわなが仕掛けられている確率
+const TRAP_CHANCE: f64 = 0.2;
+
+/// 1階下がるごとに増えるわなの確率
+const TRAP_CHANCE_PER_FLOOR: f64 = 0.1;
+
+/// 宝箱のわな
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChestTrap {
+    /// どくガス（パーティ全員にダメージ）
+    PoisonGas,
+    /// 矢（先頭の1人に大きなダメージ）
+    Arrow,
+    /// 警報（魔物を呼び寄せる）
+    Alarm,
+    /// 宝箱そのものがミミック
+    Mimic,
+}
+
 /// 宝箱の定義（位置と中身）
 #[derive(Debug, Clone)]
 pub struct TreasureChest {
     pub x: usize,
     pub y: usize,
     pub content: ItemKey,
+    /// 仕掛けられたわな（確定アイテムの宝箱には仕掛けない）
+    pub trap: Option<ChestTrap>,
 }
 
 pub struct CaveMapData {
@@ -24,33 +61,66 @@ pub struct CaveMapData {
     pub width: usize,
     pub height: usize,
     pub spawn_position: (usize, usize),
+    /// 下り階段の位置（最下層ではNone）
+    pub stairs_position: Option<(usize, usize)>,
     pub treasures: Vec<TreasureChest>,
+    /// カギのかかった扉の位置
+    pub doors: Vec<(usize, usize)>,
 }
 
-pub fn generate_cave_map(rng: &mut impl Rng, guaranteed_items: &[ItemKey]) -> CaveMapData {
-    let mut grid = vec![vec![Terrain::CaveWall; CAVE_WIDTH]; CAVE_HEIGHT];
-
-    // ランダムウォークで通路を掘る
-    let mut x = CAVE_WIDTH / 2;
-    let mut y = CAVE_HEIGHT / 2;
-    grid[y][x] = Terrain::CaveFloor;
-
-    let directions: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+/// 大陸ごとの洞窟の階層数（奥の大陸ほど深い）
+pub fn cave_floor_count(continent_id: u8) -> u8 {
+    (2 + continent_id / 2).min(MAX_CAVE_FLOORS)
+}
 
-    for _ in 0..RANDOM_WALK_STEPS {
-        let (dx, dy) = directions[rng.gen_range(0..4)];
-        let nx = x as i32 + dx;
-        let ny = y as i32 + dy;
+/// 開封済み記録に使う宝箱・扉のID（階と階内の番号から決まる）
+pub fn floor_object_id(floor: u8, index: usize) -> usize {
+    floor as usize * FLOOR_OBJECT_STRIDE + index
+}
 
-        // 外周1マスは壁として残す
-        if nx >= 1 && nx < (CAVE_WIDTH as i32 - 1) && ny >= 1 && ny < (CAVE_HEIGHT as i32 - 1) {
-            x = nx as usize;
-            y = ny as usize;
-            grid[y][x] = Terrain::CaveFloor;
-        }
+/// 指定した階の洞窟マップを生成する
+///
+/// 地形生成アルゴリズムは洞窟ごとに乱数と大陸から選び、全階で共通にする。
+/// 1階から順に同じ乱数生成器で生成するため、同じ洞窟の各階は常に同じ形になる。
+pub fn generate_cave_floor(
+    rng: &mut impl Rng,
+    continent_id: u8,
+    floor: u8,
+    floor_count: u8,
+    guaranteed_items: &[ItemKey],
+) -> CaveMapData {
+    let layout = CaveLayout::choose(rng, continent_id);
+    let mut map = layout.generate(rng, 0, floor_count, guaranteed_items);
+    for f in 1..=floor {
+        map = layout.generate(rng, f, floor_count, guaranteed_items);
     }
+    map
+}
 
-    let spawn_position = (CAVE_WIDTH / 2, CAVE_HEIGHT / 2);
+/// ランダムウォークの洞窟を1階層分生成する
+pub fn generate_cave_map(
+    rng: &mut impl Rng,
+    floor: u8,
+    floor_count: u8,
+    guaranteed_items: &[ItemKey],
+) -> CaveMapData {
+    RandomWalkGenerator { steps: RANDOM_WALK_STEPS }.generate(rng, floor, floor_count, guaranteed_items)
+}
+
+/// 掘り終えた地形に梯子・下り階段・宝箱を配置する
+///
+/// 最下層以外には下り階段を置き、確定アイテムは最下層の宝箱にだけ入れる。
+/// 深い階ほど宝箱の中身が良くなる。
+pub(crate) fn furnish_cave(
+    rng: &mut impl Rng,
+    mut grid: Vec<Vec<Terrain>>,
+    spawn_position: (usize, usize),
+    floor: u8,
+    floor_count: u8,
+    guaranteed_items: &[ItemKey],
+) -> CaveMapData {
+    let is_last_floor = floor + 1 >= floor_count;
+    let guaranteed_items = if is_last_floor { guaranteed_items } else { &[] };
 
     // スポーン地点に梯子を配置（structuresレイヤー）
     let mut structures = vec![vec![Structure::None; CAVE_WIDTH]; CAVE_HEIGHT];
@@ -67,13 +137,25 @@ pub fn generate_cave_map(rng: &mut impl Rng, guaranteed_items: &[ItemKey]) -> Ca
         |x, y, dx, dy| terrain::bounded_offset(x, y, dx, dy, CAVE_WIDTH, CAVE_HEIGHT),
     );
 
-    // 宝箱配置: 床タイルからスポーン地点を除いた候補を収集
+    // 最下層以外はスポーンから最も遠い床に下り階段を置く
+    let stairs_position = if is_last_floor {
+        None
+    } else {
+        let stairs = find_farthest_floor(&grid, spawn_position);
+        structures[stairs.1][stairs.0] = Structure::StairsDown;
+        Some(stairs)
+    };
+
+    // 宝箱配置: 床タイルからスポーン地点と階段を除いた候補を収集
     let mut floor_positions: Vec<(usize, usize)> = grid
         .iter()
         .enumerate()
         .flat_map(|(cy, row)| {
             row.iter().enumerate().filter_map(move |(cx, terrain)| {
-                if *terrain == Terrain::CaveFloor && (cx, cy) != spawn_position {
+                if *terrain == Terrain::CaveFloor
+                    && (cx, cy) != spawn_position
+                    && Some((cx, cy)) != stairs_position
+                {
                     Some((cx, cy))
                 } else {
                     None
@@ -90,7 +172,7 @@ pub fn generate_cave_map(rng: &mut impl Rng, guaranteed_items: &[ItemKey]) -> Ca
     };
     let treasure_count = random_count.max(guaranteed_items.len()).min(floor_positions.len());
 
-    let treasures: Vec<TreasureChest> = floor_positions[..treasure_count]
+    let mut treasures: Vec<TreasureChest> = floor_positions[..treasure_count]
         .iter()
         .enumerate()
         .map(|(i, &(tx, ty))| {
@@ -101,22 +183,87 @@ pub fn generate_cave_map(rng: &mut impl Rng, guaranteed_items: &[ItemKey]) -> Ca
                 content: if i < guaranteed_items.len() {
                     guaranteed_items[i]
                 } else {
-                    random_treasure_content(rng)
+                    random_treasure_content(rng, floor)
                 },
+                trap: None,
             }
         })
         .collect();
 
+    // 宝箱の部屋の入口をカギのかかった扉でふさぐ
+    let mut doors = Vec::new();
+    if rng.gen_bool(LOCKED_DOOR_CHANCE) {
+        let candidates = door_candidates(&mut grid, &structures, spawn_position, &treasures, guaranteed_items.len());
+        if !candidates.is_empty() {
+            let (dx, dy) = candidates[rng.gen_range(0..candidates.len())];
+            structures[dy][dx] = Structure::LockedDoor;
+            doors.push((dx, dy));
+        }
+    }
+
+    for treasure in treasures.iter_mut().skip(guaranteed_items.len()) {
+        treasure.trap = random_chest_trap(rng, floor);
+    }
+
     CaveMapData {
         grid,
         structures,
         width: CAVE_WIDTH,
         height: CAVE_HEIGHT,
         spawn_position,
+        stairs_position,
         treasures,
+        doors,
     }
 }
 
+/// 扉を置ける通路のタイル
+///
+/// ふさぐとスポーンから宝箱にだけ行けなくなる場所を選ぶ。
+/// 下り階段や確定アイテムの宝箱（先頭 `guaranteed_count` 個）は扉の奥に入れない。
+fn door_candidates(
+    grid: &mut [Vec<Terrain>],
+    structures: &[Vec<Structure>],
+    spawn: (usize, usize),
+    treasures: &[TreasureChest],
+    guaranteed_count: usize,
+) -> Vec<(usize, usize)> {
+    let mut candidates = Vec::new();
+    for y in 1..CAVE_HEIGHT - 1 {
+        for x in 1..CAVE_WIDTH - 1 {
+            if grid[y][x] != Terrain::CaveFloor || structures[y][x] != Structure::None {
+                continue;
+            }
+            // 前後だけが開いた1マス幅の通路に限る
+            let open = |nx: usize, ny: usize| grid[ny][nx].is_walkable();
+            let horizontal = open(x - 1, y) && open(x + 1, y) && !open(x, y - 1) && !open(x, y + 1);
+            let vertical = open(x, y - 1) && open(x, y + 1) && !open(x - 1, y) && !open(x + 1, y);
+            if !horizontal && !vertical {
+                continue;
+            }
+
+            grid[y][x] = Terrain::CaveWall;
+            let dist = floor_distances(grid, spawn);
+            grid[y][x] = Terrain::CaveFloor;
+
+            let reachable = |(tx, ty): (usize, usize)| dist[ty][tx] != u32::MAX;
+            let stairs_cut = structures
+                .iter()
+                .enumerate()
+                .flat_map(|(sy, row)| row.iter().enumerate().map(move |(sx, s)| (sx, sy, *s)))
+                .any(|(sx, sy, s)| s == Structure::StairsDown && !reachable((sx, sy)));
+            let guaranteed_cut = treasures[..guaranteed_count.min(treasures.len())]
+                .iter()
+                .any(|t| !reachable((t.x, t.y)));
+            let gates_chest = treasures.iter().any(|t| !reachable((t.x, t.y)));
+            if gates_chest && !stairs_cut && !guaranteed_cut {
+                candidates.push((x, y));
+            }
+        }
+    }
+    candidates
+}
+
 pub struct BossCaveMapData {
     pub grid: Vec<Vec<Terrain>>,
     pub structures: Vec<Vec<Structure>>,
@@ -129,28 +276,16 @@ pub struct BossCaveMapData {
 const BOSS_CAVE_WALK_STEPS: usize = 600;
 
 pub fn generate_boss_cave_map(rng: &mut impl Rng) -> BossCaveMapData {
-    let mut grid = vec![vec![Terrain::BossCaveWall; CAVE_WIDTH]; CAVE_HEIGHT];
-
     // ランダムウォークで通路を掘る（ステップ数が多いのでより広い洞窟）
-    let mut x = CAVE_WIDTH / 2;
-    let mut y = CAVE_HEIGHT / 2;
-    grid[y][x] = Terrain::BossCaveFloor;
-
-    let directions: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
-
-    for _ in 0..BOSS_CAVE_WALK_STEPS {
-        let (dx, dy) = directions[rng.gen_range(0..4)];
-        let nx = x as i32 + dx;
-        let ny = y as i32 + dy;
-
-        if nx >= 1 && nx < (CAVE_WIDTH as i32 - 1) && ny >= 1 && ny < (CAVE_HEIGHT as i32 - 1) {
-            x = nx as usize;
-            y = ny as usize;
-            grid[y][x] = Terrain::BossCaveFloor;
-        }
-    }
-
-    let spawn_position = (CAVE_WIDTH / 2, CAVE_HEIGHT / 2);
+    let (grid, spawn_position) = RandomWalkGenerator { steps: BOSS_CAVE_WALK_STEPS }.carve(rng);
+    let mut grid: Vec<Vec<Terrain>> = grid
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|t| if t == Terrain::CaveFloor { Terrain::BossCaveFloor } else { Terrain::BossCaveWall })
+                .collect()
+        })
+        .collect();
 
     // スポーン地点に梯子を配置（structuresレイヤー）
     let mut structures = vec![vec![Structure::None; CAVE_WIDTH]; CAVE_HEIGHT];
@@ -168,7 +303,7 @@ pub fn generate_boss_cave_map(rng: &mut impl Rng) -> BossCaveMapData {
     );
 
     // ボス位置: スポーンから最も遠い床タイル
-    let boss_position = find_boss_position(&grid, spawn_position);
+    let boss_position = find_farthest_floor(&grid, spawn_position);
 
     BossCaveMapData {
         grid,
@@ -180,18 +315,33 @@ pub fn generate_boss_cave_map(rng: &mut impl Rng) -> BossCaveMapData {
     }
 }
 
-/// スポーンから最も遠い床タイルをボス位置とする（BFS距離）
-fn find_boss_position(grid: &[Vec<Terrain>], spawn: (usize, usize)) -> (usize, usize) {
+/// スポーンから最も遠い床タイル（BFS距離。ボスや下り階段の位置に使う）
+fn find_farthest_floor(grid: &[Vec<Terrain>], spawn: (usize, usize)) -> (usize, usize) {
+    let dist = floor_distances(grid, spawn);
+
+    let mut farthest = spawn;
+    let mut max_dist = 0u32;
+    for (y, row) in dist.iter().enumerate() {
+        for (x, &d) in row.iter().enumerate() {
+            if d != u32::MAX && d > max_dist {
+                max_dist = d;
+                farthest = (x, y);
+            }
+        }
+    }
+
+    farthest
+}
+
+/// 起点から歩行可能なタイルへのBFS距離（届かないタイルはu32::MAX）
+pub(crate) fn floor_distances(grid: &[Vec<Terrain>], start: (usize, usize)) -> Vec<Vec<u32>> {
     use std::collections::VecDeque;
 
     let mut dist = vec![vec![u32::MAX; CAVE_WIDTH]; CAVE_HEIGHT];
     let mut queue = VecDeque::new();
 
-    dist[spawn.1][spawn.0] = 0;
-    queue.push_back(spawn);
-
-    let mut farthest = spawn;
-    let mut max_dist = 0u32;
+    dist[start.1][start.0] = 0;
+    queue.push_back(start);
 
     while let Some((cx, cy)) = queue.pop_front() {
         let d = dist[cy][cx];
@@ -209,40 +359,50 @@ fn find_boss_position(grid: &[Vec<Terrain>], spawn: (usize, usize)) -> (usize, u
                 continue;
             }
             dist[uy][ux] = d + 1;
-            if d + 1 > max_dist {
-                max_dist = d + 1;
-                farthest = (ux, uy);
-            }
             queue.push_back((ux, uy));
         }
     }
 
-    farthest
+    dist
 }
 
-fn random_treasure_content(rng: &mut impl Rng) -> ItemKey {
-    // (中身, 重み)
-    let table: &[(ItemKey, u32)] = &[
-        (ItemKey::Herb, 25),
-        (ItemKey::HighHerb, 15),
-        (ItemKey::MagicStone, 25),
-        (ItemKey::SilverOre, 15),
-        (ItemKey::AncientCoin, 10),
-        (ItemKey::DragonScale, 3),
-        (ItemKey::MoonFragment, 10),
-        (ItemKey::WoodenSword, 2),
+fn random_treasure_content(rng: &mut impl Rng, floor: u8) -> ItemKey {
+    // (中身, 1階の重み, 1階下がるごとの増減)
+    let table: &[(ItemKey, i32, i32)] = &[
+        (ItemKey::Herb, 25, -6),
+        (ItemKey::HighHerb, 15, 3),
+        (ItemKey::MagicStone, 25, -4),
+        (ItemKey::SilverOre, 15, 2),
+        (ItemKey::AncientCoin, 10, 3),
+        (ItemKey::DragonScale, 3, 3),
+        (ItemKey::MoonFragment, 10, 0),
+        (ItemKey::WoodenSword, 2, -1),
+        (ItemKey::IronSword, 0, 2),
     ];
-    let total: u32 = table.iter().map(|(_, w)| w).sum();
-    let mut roll = rng.gen_range(0..total);
-    for (content, weight) in table {
-        if roll < *weight {
-            return *content;
-        }
-        roll -= weight;
-    }
-    table.last().unwrap().0
+    let weights: Vec<(ItemKey, u32)> = table
+        .iter()
+        .map(|&(content, base, per_floor)| (content, (base + per_floor * floor as i32).max(0) as u32))
+        .collect();
+    weights
+        .choose_weighted(rng, |(_, weight)| *weight)
+        .map(|(content, _)| *content)
+        .unwrap_or(ItemKey::Herb)
 }
 
+/// 階に応じてわなを抽選する（深い階ほど仕掛けられやすい）
+fn random_chest_trap(rng: &mut impl Rng, floor: u8) -> Option<ChestTrap> {
+    let chance = (TRAP_CHANCE + TRAP_CHANCE_PER_FLOOR * floor as f64).min(1.0);
+    if !rng.gen_bool(chance) {
+        return None;
+    }
+    let traps = [
+        (ChestTrap::PoisonGas, 3),
+        (ChestTrap::Arrow, 3),
+        (ChestTrap::Alarm, 2),
+        (ChestTrap::Mimic, 2),
+    ];
+    traps.choose_weighted(rng, |(_, weight)| *weight).ok().map(|(trap, _)| *trap)
+}
 
 #[cfg(test)]
 mod tests {
@@ -257,7 +417,7 @@ mod tests {
     #[test]
     fn generate_cave_map_correct_size() {
         let mut rng = create_rng(42);
-        let map = generate_cave_map(&mut rng, &[]);
+        let map = generate_cave_map(&mut rng, 0, 1, &[]);
         assert_eq!(map.grid.len(), CAVE_HEIGHT);
         for row in &map.grid {
             assert_eq!(row.len(), CAVE_WIDTH);
@@ -269,7 +429,7 @@ mod tests {
     #[test]
     fn generate_cave_map_spawn_is_ladder() {
         let mut rng = create_rng(42);
-        let map = generate_cave_map(&mut rng, &[]);
+        let map = generate_cave_map(&mut rng, 0, 1, &[]);
         let (sx, sy) = map.spawn_position;
         assert_eq!(map.structures[sy][sx], Structure::Ladder);
     }
@@ -277,7 +437,7 @@ mod tests {
     #[test]
     fn generate_cave_map_has_floor_tiles() {
         let mut rng = create_rng(42);
-        let map = generate_cave_map(&mut rng, &[]);
+        let map = generate_cave_map(&mut rng, 0, 1, &[]);
         let floor_count = map
             .grid
             .iter()
@@ -290,7 +450,7 @@ mod tests {
     #[test]
     fn generate_cave_map_edges_are_walls() {
         let mut rng = create_rng(42);
-        let map = generate_cave_map(&mut rng, &[]);
+        let map = generate_cave_map(&mut rng, 0, 1, &[]);
         // 外周は壁（ワープゾーンは外周に置かれない）
         for x in 0..CAVE_WIDTH {
             assert_ne!(
@@ -321,7 +481,7 @@ mod tests {
     #[test]
     fn generate_cave_map_treasures_in_structures() {
         let mut rng = create_rng(42);
-        let map = generate_cave_map(&mut rng, &[]);
+        let map = generate_cave_map(&mut rng, 0, 1, &[]);
         assert!(!map.treasures.is_empty(), "Cave should have treasures");
         for treasure in &map.treasures {
             assert_eq!(
@@ -338,10 +498,198 @@ mod tests {
     fn generate_cave_map_deterministic() {
         let mut rng1 = create_rng(123);
         let mut rng2 = create_rng(123);
-        let map1 = generate_cave_map(&mut rng1, &[]);
-        let map2 = generate_cave_map(&mut rng2, &[]);
+        let map1 = generate_cave_map(&mut rng1, 0, 1, &[]);
+        let map2 = generate_cave_map(&mut rng2, 0, 1, &[]);
         assert_eq!(map1.spawn_position, map2.spawn_position);
         assert_eq!(map1.grid, map2.grid);
     }
 
+    #[test]
+    fn upper_floors_have_stairs_down() {
+        let mut rng = create_rng(42);
+        let map = generate_cave_map(&mut rng, 0, 3, &[]);
+        let (sx, sy) = map.stairs_position.expect("upper floor should have stairs");
+        assert_eq!(map.structures[sy][sx], Structure::StairsDown);
+        assert!(map.treasures.iter().all(|t| (t.x, t.y) != (sx, sy)));
+
+        let mut rng = create_rng(42);
+        let last = generate_cave_floor(&mut rng, 0, 2, 3, &[]);
+        assert!(last.stairs_position.is_none());
+        assert!(!last.structures.iter().flatten().any(|s| *s == Structure::StairsDown));
+    }
+
+    #[test]
+    fn guaranteed_items_only_on_last_floor() {
+        let items = [ItemKey::MoonFragment, ItemKey::DragonScale];
+        for floor in 0..3 {
+            let mut rng = create_rng(7);
+            let map = generate_cave_floor(&mut rng, 0, floor, 3, &items);
+            let has_guaranteed = map.treasures.len() >= 2
+                && map.treasures[0].content == ItemKey::MoonFragment
+                && map.treasures[1].content == ItemKey::DragonScale;
+            assert_eq!(has_guaranteed, floor == 2, "floor {}", floor);
+        }
+    }
+
+    #[test]
+    fn generate_cave_floor_is_deterministic_per_floor() {
+        let mut rng1 = create_rng(99);
+        let mut rng2 = create_rng(99);
+        let a = generate_cave_floor(&mut rng1, 0, 1, 3, &[]);
+        let b = generate_cave_floor(&mut rng2, 0, 1, 3, &[]);
+        assert_eq!(a.grid, b.grid);
+        assert_eq!(a.stairs_position, b.stairs_position);
+
+        let mut rng3 = create_rng(99);
+        let first = generate_cave_floor(&mut rng3, 0, 0, 3, &[]);
+        assert_ne!(first.grid, a.grid, "each floor should have its own layout");
+    }
+
+    #[test]
+    fn deeper_floors_have_better_treasure() {
+        let average_value = |floor: u8| {
+            let mut rng = create_rng(5);
+            let total: u32 = (0..500)
+                .map(|_| random_treasure_content(&mut rng, floor).entry().sell_price)
+                .sum();
+            total as f32 / 500.0
+        };
+        assert!(average_value(3) > average_value(0));
+    }
+
+    /// 床がすべてスポーンから歩いて行けて、外周が壁であることを確かめる
+    fn assert_connected(map: &CaveMapData, label: &str) {
+        let dist = floor_distances(&map.grid, map.spawn_position);
+        for (y, row) in map.grid.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let edge = x == 0 || y == 0 || x == CAVE_WIDTH - 1 || y == CAVE_HEIGHT - 1;
+                if edge {
+                    assert!(!tile.is_walkable(), "{}: edge ({}, {}) is open", label, x, y);
+                }
+                if tile.is_walkable() {
+                    assert_ne!(dist[y][x], u32::MAX, "{}: ({}, {}) is unreachable", label, x, y);
+                }
+            }
+        }
+        for treasure in &map.treasures {
+            assert!(map.grid[treasure.y][treasure.x].is_walkable(), "{}: chest on wall", label);
+        }
+        if let Some((sx, sy)) = map.stairs_position {
+            assert_ne!(dist[sy][sx], u32::MAX, "{}: stairs unreachable", label);
+        }
+    }
+
+    #[test]
+    fn all_generators_produce_connected_caves() {
+        for layout in CaveLayout::ALL {
+            for seed in 0..30 {
+                let mut rng = create_rng(seed);
+                let map = layout.generate(&mut rng, 0, 2, &[ItemKey::MoonFragment]);
+                let label = format!("{:?} seed {}", layout, seed);
+                assert_connected(&map, &label);
+                let (sx, sy) = map.spawn_position;
+                assert_eq!(map.structures[sy][sx], Structure::Ladder, "{}", label);
+                assert!(map.stairs_position.is_some(), "{}", label);
+                let floor_count = map.grid.iter().flatten().filter(|t| t.is_walkable()).count();
+                assert!(floor_count > 50, "{}: only {} floor tiles", label, floor_count);
+            }
+        }
+    }
+
+    #[test]
+    fn locked_doors_gate_only_random_chests() {
+        let mut doors_seen = 0;
+        for layout in CaveLayout::ALL {
+            for seed in 0..30 {
+                // 上の階（階段あり）と最下層（確定アイテムあり）の両方を確かめる
+                for floor_count in [2, 1] {
+                    let mut rng = create_rng(seed);
+                    let map = layout.generate(&mut rng, 0, floor_count, &[ItemKey::MoonFragment]);
+                    let label = format!("{:?} seed {} floors {}", layout, seed, floor_count);
+                    for &(x, y) in &map.doors {
+                        doors_seen += 1;
+                        assert_eq!(map.structures[y][x], Structure::LockedDoor, "{}", label);
+                        let mut grid = map.grid.clone();
+                        grid[y][x] = Terrain::CaveWall;
+                        let dist = floor_distances(&grid, map.spawn_position);
+                        if let Some((sx, sy)) = map.stairs_position {
+                            assert_ne!(dist[sy][sx], u32::MAX, "{}: door blocks stairs", label);
+                        } else {
+                            let first = &map.treasures[0];
+                            assert_eq!(first.content, ItemKey::MoonFragment);
+                            assert_ne!(dist[first.y][first.x], u32::MAX, "{}: door blocks guaranteed item", label);
+                        }
+                        assert!(map.treasures.iter().any(|t| dist[t.y][t.x] == u32::MAX), "{}", label);
+                    }
+                }
+            }
+        }
+        assert!(doors_seen > 0, "some caves should have locked doors");
+    }
+
+    #[test]
+    fn traps_skip_guaranteed_chests_and_grow_with_depth() {
+        let trap_rate = |floor: u8| {
+            let (mut trapped, mut total) = (0, 0);
+            for seed in 0..200 {
+                let map = generate_cave_map(&mut create_rng(seed), floor, MAX_CAVE_FLOORS, &[]);
+                total += map.treasures.len();
+                trapped += map.treasures.iter().filter(|t| t.trap.is_some()).count();
+            }
+            trapped as f64 / total as f64
+        };
+        assert!(trap_rate(3) > trap_rate(0), "deeper chests should be trapped more often");
+
+        let mut kinds = std::collections::HashSet::new();
+        for seed in 0..200 {
+            let map = generate_cave_map(&mut create_rng(seed), 0, 1, &[ItemKey::MoonFragment]);
+            assert_eq!(map.treasures[0].trap, None, "seed {}: guaranteed chest is trapped", seed);
+            kinds.extend(map.treasures.iter().filter_map(|t| t.trap));
+        }
+        assert_eq!(kinds.len(), 4, "all trap kinds should appear: {:?}", kinds);
+    }
+
+    #[test]
+    fn boss_cave_is_connected() {
+        for seed in 0..10 {
+            let mut rng = create_rng(seed);
+            let boss = generate_boss_cave_map(&mut rng);
+            let dist = floor_distances(&boss.grid, boss.spawn_position);
+            assert_ne!(dist[boss.boss_position.1][boss.boss_position.0], u32::MAX);
+            assert!(boss.grid.iter().flatten().all(|t| matches!(t, Terrain::BossCaveFloor | Terrain::BossCaveWall)));
+        }
+    }
+
+    #[test]
+    fn generators_produce_distinct_layouts() {
+        let grids: Vec<_> = CaveLayout::ALL
+            .iter()
+            .map(|layout| layout.carve(&mut create_rng(3)).0)
+            .collect();
+        for i in 0..grids.len() {
+            for j in i + 1..grids.len() {
+                assert_ne!(grids[i], grids[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn layout_choice_depends_on_continent() {
+        let count_mazes = |continent_id: u8| {
+            (0..200)
+                .filter(|&seed| CaveLayout::choose(&mut create_rng(seed), continent_id) == CaveLayout::Maze)
+                .count()
+        };
+        assert_eq!(count_mazes(0), 0, "first continent should not have mazes");
+        assert!(count_mazes(5) > 0);
+    }
+
+    #[test]
+    fn floor_count_grows_with_continent() {
+        assert_eq!(cave_floor_count(0), 2);
+        assert!(cave_floor_count(4) > cave_floor_count(0));
+        assert!(cave_floor_count(6) <= MAX_CAVE_FLOORS);
+        assert_ne!(floor_object_id(0, 1), floor_object_id(1, 1));
+        assert_eq!(floor_object_id(0, 2), 2);
+    }
 }
@@ -0,0 +1,61 @@
+use spell::{Affinity, Element};
+
+use crate::EnemyKind;
+
+use Affinity::*;
+use Element::*;
+
+/// 属性ごとの相性（載っていない属性は通常）
+type AffinityTable = &'static [(Element, Affinity)];
+
+impl EnemyKind {
+    /// 種類ごとの属性相性表
+    pub fn affinities(self) -> AffinityTable {
+        match self {
+            EnemyKind::Slime => &[],
+            EnemyKind::Bat => &[(Ice, Weak)],
+            EnemyKind::Goblin => &[(Fire, Weak)],
+            EnemyKind::Wolf => &[(Fire, Weak)],
+            EnemyKind::Ghost => &[(Holy, Weak), (Ice, Resist)],
+            EnemyKind::Scorpion => &[(Ice, Weak), (Fire, Resist)],
+            EnemyKind::Skeleton => &[(Holy, Weak), (Ice, Immune)],
+            EnemyKind::Lizardman => &[(Ice, Weak), (Fire, Resist)],
+            EnemyKind::Golem => &[(Fire, Resist), (Ice, Resist)],
+            EnemyKind::Demon => &[(Holy, Weak), (Fire, Absorb)],
+            EnemyKind::Dragon => &[(Ice, Weak), (Fire, Absorb)],
+            EnemyKind::Wraith => &[(Holy, Weak), (Ice, Immune), (Fire, Resist)],
+            EnemyKind::Mimic => &[(Fire, Weak)],
+            EnemyKind::DarkLord => &[(Holy, Weak), (Fire, Resist), (Ice, Resist)],
+        }
+    }
+
+    /// 属性に対する相性（無属性は常に通常）
+    pub fn affinity(self, element: Option<Element>) -> Affinity {
+        let Some(element) = element else {
+            return Normal;
+        };
+        self.affinities()
+            .iter()
+            .find(|(e, _)| *e == element)
+            .map_or(Normal, |(_, affinity)| *affinity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlisted_and_neutral_elements_are_normal() {
+        assert_eq!(EnemyKind::Slime.affinity(Some(Fire)), Normal);
+        assert_eq!(EnemyKind::Demon.affinity(None), Normal);
+    }
+
+    #[test]
+    fn fire_spells_differ_between_enemy_kinds() {
+        assert_eq!(EnemyKind::Goblin.affinity(Some(Fire)), Weak);
+        assert_eq!(EnemyKind::Golem.affinity(Some(Fire)), Resist);
+        assert_eq!(EnemyKind::Dragon.affinity(Some(Fire)), Absorb);
+        assert_eq!(EnemyKind::Wraith.affinity(Some(Ice)), Immune);
+    }
+}
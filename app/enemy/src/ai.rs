@@ -0,0 +1,203 @@
+use spell::SpellEntry;
+use spell_data::{
+    BLAZE1, BLAZE2, BLIND1, BREAKALL1, CONFUSE1, DRAIN1, DRAIN2, FIRE1, FIRE2, FORTIFY1, PARALYZE1, POISONALL1,
+    SILENCEALL1, SIPHON2, SLEEP1, SLEEPALL1, WEAKEN1,
+};
+
+use crate::EnemyKind;
+
+/// 攻撃対象の選び方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetPolicy {
+    /// 先頭の生存メンバー
+    First,
+    /// 生存メンバーからランダム
+    Random,
+    /// HPが最も低いメンバー
+    LowestHp,
+}
+
+/// 重み付きの呪文候補
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedSpell {
+    pub spell: SpellEntry,
+    pub weight: u32,
+}
+
+const fn w(spell: SpellEntry, weight: u32) -> WeightedSpell {
+    WeightedSpell { spell, weight }
+}
+
+/// 1形態分の行動方針
+#[derive(Debug, Clone, Copy)]
+pub struct AiProfile {
+    /// 呪文を使う確率(0.0~1.0)
+    pub spell_rate: f32,
+    /// 呪文の候補と重み
+    pub spells: &'static [WeightedSpell],
+    /// HPがこの割合(%)以下なら呪文テーブルの回復呪文を優先する（0で無効）
+    pub heal_below_percent: i32,
+    pub targeting: TargetPolicy,
+    /// 眠り・まひで動けない相手を優先して狙う
+    pub focus_sleeping: bool,
+}
+
+/// 敵の種類ごとのAI
+#[derive(Debug, Clone, Copy)]
+pub struct EnemyAi {
+    pub base: AiProfile,
+    /// HP割合(%)がしきい値以下で切り替わる形態（しきい値の高い順）
+    pub phases: &'static [(i32, AiProfile)],
+}
+
+impl EnemyAi {
+    /// 現在のHPに応じた行動方針
+    pub fn profile(&self, hp: i32, max_hp: i32) -> &AiProfile {
+        self.phases
+            .iter()
+            .rev()
+            .find(|(threshold, _)| hp * 100 <= max_hp * threshold)
+            .map(|(_, profile)| profile)
+            .unwrap_or(&self.base)
+    }
+}
+
+/// 乱数(0.0~1.0)で候補から重み付きで1つ選ぶ（候補が空ならNone）
+pub fn pick_spell(candidates: &[WeightedSpell], random: f32) -> Option<SpellEntry> {
+    crate::pick_weighted(candidates, |c| c.weight, random).map(|c| c.spell)
+}
+
+const fn melee(targeting: TargetPolicy) -> EnemyAi {
+    EnemyAi {
+        base: AiProfile {
+            spell_rate: 0.0,
+            spells: &[],
+            heal_below_percent: 0,
+            targeting,
+            focus_sleeping: false,
+        },
+        phases: &[],
+    }
+}
+
+static RANDOM_MELEE: EnemyAi = melee(TargetPolicy::Random);
+static HUNTER_MELEE: EnemyAi = melee(TargetPolicy::LowestHp);
+static FRONT_MELEE: EnemyAi = melee(TargetPolicy::First);
+
+static GHOST_AI: EnemyAi = EnemyAi {
+    base: AiProfile {
+        spell_rate: 0.5,
+        spells: &[w(FIRE1, 2), w(DRAIN1, 1), w(SLEEP1, 2), w(BLIND1, 1)],
+        heal_below_percent: 0,
+        targeting: TargetPolicy::Random,
+        focus_sleeping: false,
+    },
+    phases: &[],
+};
+
+static DEMON_AI: EnemyAi = EnemyAi {
+    base: AiProfile {
+        spell_rate: 0.5,
+        spells: &[w(FIRE1, 2), w(BLAZE1, 1), w(CONFUSE1, 1), w(WEAKEN1, 1)],
+        heal_below_percent: 0,
+        targeting: TargetPolicy::LowestHp,
+        focus_sleeping: true,
+    },
+    phases: &[],
+};
+
+static WRAITH_AI: EnemyAi = EnemyAi {
+    base: AiProfile {
+        spell_rate: 0.5,
+        spells: &[w(FIRE2, 2), w(BLAZE1, 1), w(DRAIN2, 1), w(SLEEPALL1, 1), w(SILENCEALL1, 1)],
+        heal_below_percent: 0,
+        targeting: TargetPolicy::Random,
+        focus_sleeping: true,
+    },
+    phases: &[],
+};
+
+static DRAGON_AI: EnemyAi = EnemyAi {
+    base: AiProfile {
+        spell_rate: 0.5,
+        spells: &[w(BLAZE2, 2), w(PARALYZE1, 1), w(FORTIFY1, 1)],
+        heal_below_percent: 0,
+        targeting: TargetPolicy::Random,
+        focus_sleeping: false,
+    },
+    phases: &[],
+};
+
+static DARK_LORD_AI: EnemyAi = EnemyAi {
+    base: AiProfile {
+        spell_rate: 0.5,
+        spells: &[w(BLAZE2, 2), w(FIRE2, 3), w(SIPHON2, 1), w(POISONALL1, 2), w(BREAKALL1, 1)],
+        heal_below_percent: 0,
+        targeting: TargetPolicy::LowestHp,
+        focus_sleeping: true,
+    },
+    phases: &[
+        // HP半分以下: 全体攻撃中心に切り替え、瀕死なら回復する
+        (
+            50,
+            AiProfile {
+                spell_rate: 0.7,
+                spells: &[w(BLAZE2, 3), w(FIRE2, 1), w(SIPHON2, 1), w(POISONALL1, 1), w(BREAKALL1, 1)],
+                heal_below_percent: 25,
+                targeting: TargetPolicy::LowestHp,
+                focus_sleeping: true,
+            },
+        ),
+    ],
+};
+
+impl EnemyKind {
+    /// 種類ごとのAI
+    pub fn ai(self) -> &'static EnemyAi {
+        match self {
+            EnemyKind::Ghost => &GHOST_AI,
+            EnemyKind::Demon => &DEMON_AI,
+            EnemyKind::Wraith => &WRAITH_AI,
+            EnemyKind::Dragon => &DRAGON_AI,
+            EnemyKind::DarkLord => &DARK_LORD_AI,
+            EnemyKind::Wolf | EnemyKind::Bat => &HUNTER_MELEE,
+            EnemyKind::Golem => &FRONT_MELEE,
+            _ => &RANDOM_MELEE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_spell_follows_weights() {
+        let candidates = [w(FIRE1, 1), w(BLAZE1, 3)];
+        assert_eq!(pick_spell(&candidates, 0.0).unwrap().name, "Fire1");
+        assert_eq!(pick_spell(&candidates, 0.3).unwrap().name, "Blaze1");
+        assert_eq!(pick_spell(&candidates, 1.0).unwrap().name, "Blaze1");
+        assert!(pick_spell(&[], 0.5).is_none());
+    }
+
+    #[test]
+    fn dark_lord_changes_phase_below_half_hp() {
+        let ai = EnemyKind::DarkLord.ai();
+        assert_eq!(ai.profile(200, 200).heal_below_percent, 0);
+        assert_eq!(ai.profile(100, 200).heal_below_percent, 25);
+        assert_eq!(ai.profile(10, 200).spell_rate, 0.7);
+    }
+
+    #[test]
+    fn ai_spells_are_in_spell_table() {
+        for kind in [EnemyKind::Ghost, EnemyKind::Demon, EnemyKind::Wraith, EnemyKind::Dragon, EnemyKind::DarkLord] {
+            let ai = kind.ai();
+            let profiles = std::iter::once(&ai.base).chain(ai.phases.iter().map(|(_, p)| p));
+            for profile in profiles {
+                for c in profile.spells {
+                    assert!(kind.spells().contains(&c.spell), "{:?} の {} が呪文テーブルにない", kind, c.spell.name);
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,18 @@
+pub mod affinity;
+pub mod ai;
+
+pub use ai::{pick_spell, AiProfile, EnemyAi, TargetPolicy, WeightedSpell};
+
+use item_data::ItemKey;
 use party::CombatStats;
+use serde::{Deserialize, Serialize};
 use spell::SpellEntry;
-use spell_data::{BLAZE1, BLAZE2, DRAIN1, DRAIN2, FIRE1, FIRE2, HEAL2, POISONALL1, SIPHON2, SLEEP1, SLEEPALL1};
+use spell_data::{
+    BLAZE1, BLAZE2, BLIND1, BREAKALL1, CONFUSE1, DRAIN1, DRAIN2, FIRE1, FIRE2, FORTIFY1, HEAL2, PARALYZE1, POISONALL1,
+    SILENCEALL1, SIPHON2, SLEEP1, SLEEPALL1, WEAKEN1,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EnemyKind {
     Slime,
     Bat,
@@ -16,6 +26,8 @@ pub enum EnemyKind {
     Demon,
     Dragon,
     Wraith,
+    /// 宝箱に化けた魔物（エンカウント表には出ず、宝箱からのみ現れる）
+    Mimic,
     DarkLord,
 }
 
@@ -34,6 +46,7 @@ impl EnemyKind {
             EnemyKind::Demon => "デーモン",
             EnemyKind::Dragon => "ドラゴン",
             EnemyKind::Wraith => "レイス",
+            EnemyKind::Mimic => "ミミック",
             EnemyKind::DarkLord => "まおう",
         }
     }
@@ -52,6 +65,7 @@ impl EnemyKind {
             EnemyKind::Demon => "enemies/demon.png",
             EnemyKind::Dragon => "enemies/dragon.png",
             EnemyKind::Wraith => "enemies/wraith.png",
+            EnemyKind::Mimic => "enemies/mimic.png",
             EnemyKind::DarkLord => "enemies/dark_lord.png",
         }
     }
@@ -71,10 +85,51 @@ impl EnemyKind {
             EnemyKind::Demon => 18,
             EnemyKind::Dragon => 25,
             EnemyKind::Wraith => 20,
+            EnemyKind::Mimic => 16,
             EnemyKind::DarkLord => 100,
         }
     }
 
+    /// Tier 1 の獲得ゴールド範囲 (最小, 最大)
+    pub fn base_gold_range(self) -> (u32, u32) {
+        match self {
+            EnemyKind::Slime => (2, 4),
+            EnemyKind::Bat => (2, 5),
+            EnemyKind::Goblin => (5, 9),
+            EnemyKind::Wolf => (6, 10),
+            EnemyKind::Scorpion => (5, 9),
+            EnemyKind::Skeleton => (8, 14),
+            EnemyKind::Ghost => (8, 14),
+            EnemyKind::Lizardman => (10, 16),
+            EnemyKind::Golem => (14, 22),
+            EnemyKind::Demon => (18, 28),
+            EnemyKind::Dragon => (25, 40),
+            EnemyKind::Wraith => (20, 32),
+            EnemyKind::Mimic => (30, 50),
+            EnemyKind::DarkLord => (300, 300),
+        }
+    }
+
+    /// ドロップテーブル（重み付き、Noneは何も落とさない）
+    pub fn drop_table(self) -> &'static [DropEntry] {
+        match self {
+            EnemyKind::Slime => &SLIME_DROPS,
+            EnemyKind::Bat => &BAT_DROPS,
+            EnemyKind::Goblin => &GOBLIN_DROPS,
+            EnemyKind::Wolf => &WOLF_DROPS,
+            EnemyKind::Scorpion => &SCORPION_DROPS,
+            EnemyKind::Skeleton => &SKELETON_DROPS,
+            EnemyKind::Ghost => &GHOST_DROPS,
+            EnemyKind::Lizardman => &LIZARDMAN_DROPS,
+            EnemyKind::Golem => &GOLEM_DROPS,
+            EnemyKind::Demon => &DEMON_DROPS,
+            EnemyKind::Dragon => &DRAGON_DROPS,
+            EnemyKind::Wraith => &WRAITH_DROPS,
+            EnemyKind::Mimic => &MIMIC_DROPS,
+            EnemyKind::DarkLord => &DARK_LORD_DROPS,
+        }
+    }
+
     /// 後方互換: Tier 1 の経験値
     pub fn exp_reward(self) -> u32 {
         self.base_exp_reward()
@@ -83,11 +138,11 @@ impl EnemyKind {
     /// 使用可能な呪文テーブル
     pub fn spells(self) -> &'static [SpellEntry] {
         match self {
-            EnemyKind::Ghost => &[FIRE1, DRAIN1, SLEEP1],
-            EnemyKind::Demon => &[FIRE1, BLAZE1],
-            EnemyKind::Wraith => &[FIRE2, BLAZE1, DRAIN2, SLEEPALL1],
-            EnemyKind::Dragon => &[BLAZE2],
-            EnemyKind::DarkLord => &[BLAZE2, FIRE2, HEAL2, SIPHON2, POISONALL1],
+            EnemyKind::Ghost => &[FIRE1, DRAIN1, SLEEP1, BLIND1],
+            EnemyKind::Demon => &[FIRE1, BLAZE1, CONFUSE1, WEAKEN1],
+            EnemyKind::Wraith => &[FIRE2, BLAZE1, DRAIN2, SLEEPALL1, SILENCEALL1],
+            EnemyKind::Dragon => &[BLAZE2, PARALYZE1, FORTIFY1],
+            EnemyKind::DarkLord => &[BLAZE2, FIRE2, HEAL2, SIPHON2, POISONALL1, BREAKALL1],
             _ => &[],
         }
     }
@@ -107,11 +162,20 @@ impl EnemyKind {
             EnemyKind::Demon => (28, 12, 6, 5, 15),
             EnemyKind::Dragon => (40, 15, 10, 3, 20),
             EnemyKind::Wraith => (30, 11, 7, 6, 18),
+            EnemyKind::Mimic => (26, 11, 8, 2, 0),
             EnemyKind::DarkLord => (200, 25, 15, 8, 50),
         }
     }
 }
 
+/// 敵の段階の上限
+pub const MAX_TIER: u8 = 3;
+
+/// 洞窟の階に応じて段階を上げる（1階下がるごとに1段階、上限で頭打ち）
+pub fn floor_tier(tier: u8, floor: u8) -> u8 {
+    tier.saturating_add(floor).min(MAX_TIER)
+}
+
 fn tier_multiplier(tier: u8) -> f32 {
     match tier {
         1 => 1.0,
@@ -121,6 +185,16 @@ fn tier_multiplier(tier: u8) -> f32 {
     }
 }
 
+/// 段階に応じて報酬を増やす（経験値・ゴールド共通）
+fn tier_reward(base: u32, tier: u8) -> u32 {
+    match tier {
+        1 => base,
+        2 => base * 3 / 2,
+        3 => base * 2,
+        _ => base,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Enemy {
     pub kind: EnemyKind,
@@ -150,13 +224,25 @@ impl Enemy {
 
     /// 段階を考慮した経験値
     pub fn exp_reward(&self) -> u32 {
-        let base = self.kind.base_exp_reward();
-        match self.tier {
-            1 => base,
-            2 => base * 3 / 2,
-            3 => base * 2,
-            _ => base,
-        }
+        tier_reward(self.kind.base_exp_reward(), self.tier)
+    }
+
+    /// 段階を考慮した獲得ゴールド範囲 (最小, 最大)
+    pub fn gold_range(&self) -> (u32, u32) {
+        let (min, max) = self.kind.base_gold_range();
+        (tier_reward(min, self.tier), tier_reward(max, self.tier))
+    }
+
+    /// 乱数(0.0〜1.0)から獲得ゴールドを決める
+    pub fn roll_gold(&self, random: f32) -> u32 {
+        let (min, max) = self.gold_range();
+        let span = max - min;
+        min + ((random * (span + 1) as f32) as u32).min(span)
+    }
+
+    /// 乱数(0.0〜1.0)からドロップアイテムを決める
+    pub fn roll_drop(&self, random: f32) -> Option<ItemKey> {
+        pick_drop(self.kind.drop_table(), random)
     }
 
     /// 段階付き表示名
@@ -190,6 +276,59 @@ impl Enemy {
     }
 }
 
+// ── ドロップテーブル ──────────────────────────────────
+
+/// ドロップテーブルの1エントリ
+#[derive(Debug, Clone, Copy)]
+pub struct DropEntry {
+    /// 落とすアイテム（Noneは「何も落とさない」枠）
+    pub item: Option<ItemKey>,
+    pub weight: u8,
+}
+
+const fn d(item: ItemKey, weight: u8) -> DropEntry {
+    DropEntry { item: Some(item), weight }
+}
+
+const fn none(weight: u8) -> DropEntry {
+    DropEntry { item: None, weight }
+}
+
+static SLIME_DROPS: [DropEntry; 2] = [none(90), d(ItemKey::Herb, 10)];
+static BAT_DROPS: [DropEntry; 2] = [none(92), d(ItemKey::Herb, 8)];
+static GOBLIN_DROPS: [DropEntry; 3] = [none(80), d(ItemKey::Herb, 15), d(ItemKey::AncientCoin, 5)];
+static WOLF_DROPS: [DropEntry; 3] = [none(85), d(ItemKey::Herb, 10), d(ItemKey::HighHerb, 5)];
+static SCORPION_DROPS: [DropEntry; 3] = [none(85), d(ItemKey::HighHerb, 10), d(ItemKey::SilverOre, 5)];
+static SKELETON_DROPS: [DropEntry; 3] = [none(80), d(ItemKey::AncientCoin, 12), d(ItemKey::SilverOre, 8)];
+static GHOST_DROPS: [DropEntry; 3] = [none(80), d(ItemKey::MagicStone, 15), d(ItemKey::AncientCoin, 5)];
+static LIZARDMAN_DROPS: [DropEntry; 3] = [none(80), d(ItemKey::HighHerb, 10), d(ItemKey::SilverOre, 10)];
+static GOLEM_DROPS: [DropEntry; 3] = [none(70), d(ItemKey::SilverOre, 20), d(ItemKey::MagicStone, 10)];
+static DEMON_DROPS: [DropEntry; 3] = [none(75), d(ItemKey::MagicStone, 15), d(ItemKey::AncientCoin, 10)];
+static DRAGON_DROPS: [DropEntry; 3] = [none(70), d(ItemKey::DragonScale, 20), d(ItemKey::AncientCoin, 10)];
+static WRAITH_DROPS: [DropEntry; 3] = [none(75), d(ItemKey::MagicStone, 15), d(ItemKey::AncientCoin, 10)];
+static MIMIC_DROPS: [DropEntry; 3] = [none(50), d(ItemKey::AncientCoin, 30), d(ItemKey::MagicStone, 20)];
+static DARK_LORD_DROPS: [DropEntry; 1] = [d(ItemKey::DragonScale, 1)];
+
+/// 乱数(0.0~1.0)で候補から重み付きで1つ選ぶ（重みの合計が0ならNone）
+pub(crate) fn pick_weighted<T>(items: &[T], weight: impl Fn(&T) -> u32, random: f32) -> Option<&T> {
+    let total_weight: u32 = items.iter().map(&weight).sum();
+    if total_weight == 0 {
+        return None;
+    }
+    let selected = (random * total_weight as f32).min(total_weight as f32 - 1.0) as u32;
+
+    let mut cumulative = 0u32;
+    items.iter().find(|item| {
+        cumulative += weight(item);
+        selected < cumulative
+    })
+}
+
+/// ドロップテーブルから重み付きで1つ選択する
+fn pick_drop(table: &[DropEntry], random: f32) -> Option<ItemKey> {
+    pick_weighted(table, |e| e.weight as u32, random).and_then(|e| e.item)
+}
+
 // ── エンカウントテーブル ──────────────────────────────────
 
 #[derive(Debug, Clone, Copy)]
@@ -374,17 +513,7 @@ pub fn encounter_table(continent_id: u8, is_cave: bool) -> &'static [EncounterEn
 
 /// テーブルから重み付きで1体選択するヘルパー
 fn pick_from_table(table: &[EncounterEntry], random: f32) -> &EncounterEntry {
-    let total_weight: u32 = table.iter().map(|e| e.weight as u32).sum();
-    let selected = (random * total_weight as f32).min(total_weight as f32 - 1.0) as u32;
-
-    let mut cumulative = 0u32;
-    for entry in table {
-        cumulative += entry.weight as u32;
-        if selected < cumulative {
-            return entry;
-        }
-    }
-    &table[0]
+    pick_weighted(table, |e| e.weight as u32, random).unwrap_or(&table[0])
 }
 
 /// 大陸と場所に基づいて敵グループを生成
@@ -426,6 +555,20 @@ pub fn generate_enemy_group(
         .collect()
 }
 
+/// 洞窟の階の深さを段階に反映した敵グループを生成（フィールドや1階は `floor=0`）
+pub fn generate_floor_enemy_group(
+    continent_id: u8,
+    is_cave: bool,
+    floor: u8,
+    count_random: f32,
+    kind_random: f32,
+) -> Vec<Enemy> {
+    generate_enemy_group(continent_id, is_cave, count_random, kind_random)
+        .into_iter()
+        .map(|e| Enemy::new(e.kind, floor_tier(e.tier, floor)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -537,6 +680,24 @@ mod tests {
         assert_eq!(t3.exp_reward(), 6); // 3 * 2
     }
 
+    #[test]
+    fn floor_tier_rises_with_depth_and_caps() {
+        assert_eq!(floor_tier(1, 0), 1);
+        assert_eq!(floor_tier(1, 1), 2);
+        assert_eq!(floor_tier(2, 3), MAX_TIER);
+    }
+
+    #[test]
+    fn floor_enemy_group_is_stronger_deeper() {
+        let shallow = generate_floor_enemy_group(0, true, 0, 0.7, 0.2);
+        let deep = generate_floor_enemy_group(0, true, 1, 0.7, 0.2);
+        assert_eq!(shallow.len(), deep.len());
+        for (s, d) in shallow.iter().zip(&deep) {
+            assert_eq!(s.kind, d.kind);
+            assert_eq!(d.tier, floor_tier(s.tier, 1));
+        }
+    }
+
     #[test]
     fn display_name_includes_tier_suffix() {
         let t1 = Enemy::new(EnemyKind::Goblin, 1);
@@ -573,10 +734,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mimic_never_appears_in_encounter_tables() {
+        for c in 0..7 {
+            for is_cave in [false, true] {
+                assert!(encounter_table(c, is_cave).iter().all(|e| e.kind != EnemyKind::Mimic));
+            }
+        }
+    }
+
+    #[test]
+    fn gold_range_scales_with_tier() {
+        assert_eq!(Enemy::new(EnemyKind::Goblin, 1).gold_range(), (5, 9));
+        assert_eq!(Enemy::new(EnemyKind::Goblin, 2).gold_range(), (7, 13));
+        assert_eq!(Enemy::new(EnemyKind::Goblin, 3).gold_range(), (10, 18));
+    }
+
+    #[test]
+    fn roll_gold_stays_within_range() {
+        let goblin = Enemy::new(EnemyKind::Goblin, 1);
+        assert_eq!(goblin.roll_gold(0.0), 5);
+        assert_eq!(goblin.roll_gold(0.5), 7);
+        assert_eq!(goblin.roll_gold(1.0), 9);
+    }
+
+    #[test]
+    fn roll_drop_follows_weights() {
+        // Dragon: なし70 / りゅうのウロコ20 / いにしえのコイン10
+        let dragon = Enemy::new(EnemyKind::Dragon, 1);
+        assert_eq!(dragon.roll_drop(0.0), None);
+        assert_eq!(dragon.roll_drop(0.69), None);
+        assert_eq!(dragon.roll_drop(0.75), Some(ItemKey::DragonScale));
+        assert_eq!(dragon.roll_drop(1.0), Some(ItemKey::AncientCoin));
+    }
+
+    #[test]
+    fn rare_materials_are_droppable() {
+        let all_kinds = [
+            EnemyKind::Slime, EnemyKind::Bat, EnemyKind::Goblin, EnemyKind::Wolf,
+            EnemyKind::Ghost, EnemyKind::Scorpion, EnemyKind::Skeleton, EnemyKind::Lizardman,
+            EnemyKind::Golem, EnemyKind::Demon, EnemyKind::Dragon, EnemyKind::Wraith,
+            EnemyKind::Mimic, EnemyKind::DarkLord,
+        ];
+        for item in [ItemKey::SilverOre, ItemKey::AncientCoin, ItemKey::DragonScale] {
+            assert!(
+                all_kinds
+                    .iter()
+                    .any(|k| k.drop_table().iter().any(|e| e.item == Some(item))),
+                "{:?}を落とす敵がいない",
+                item
+            );
+        }
+    }
+
     #[test]
     fn ghost_has_spells() {
         let ghost = Enemy::ghost();
-        assert_eq!(ghost.spells.len(), 3);
+        assert_eq!(ghost.spells.len(), 4);
     }
 
     #[test]
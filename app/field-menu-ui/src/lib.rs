@@ -1,14 +1,17 @@
 use bevy::prelude::*;
+use rand::Rng;
 
 use party_state::{CharacterParams, PartyState};
-use scene_state::InField;
+use world_state::GameRng;
+use scene_state::{InField, SceneState};
 use field_walk_ui::FieldMenuOpen;
 use hud_ui::command_menu::{
     self, CommandMenu, CommandMenuItem, CommandMenuScrollDown, CommandMenuScrollUp,
 };
+use hud_ui::lineup;
 use input_ui::InputSystemSet;
 use party::available_spells;
-use item::{Inventory, ItemEffect, BAG_CAPACITY, BAG_MEMBER_INDEX};
+use item::{EquipSlot, Inventory, ItemEffect, BAG_CAPACITY, BAG_MEMBER_INDEX};
 use item_data::ItemKey;
 use spell::{SpellEffect, SpellEntry, SpellTarget};
 
@@ -27,10 +30,51 @@ pub enum TargetContext {
     },
 }
 
+/// トップメニューの項目（ALLの並びが表示順で、カーソル位置はALL内の位置）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TopMenuItem {
+    Spell,
+    Item,
+    Equip,
+    Lineup,
+    Formation,
+    Save,
+    Load,
+}
+
+impl TopMenuItem {
+    const ALL: [TopMenuItem; 7] = [
+        TopMenuItem::Spell,
+        TopMenuItem::Item,
+        TopMenuItem::Equip,
+        TopMenuItem::Lineup,
+        TopMenuItem::Formation,
+        TopMenuItem::Save,
+        TopMenuItem::Load,
+    ];
+
+    const fn label(self) -> &'static str {
+        match self {
+            TopMenuItem::Spell => "じゅもん",
+            TopMenuItem::Item => "どうぐ",
+            TopMenuItem::Equip => "そうび",
+            TopMenuItem::Lineup => "いれかえ",
+            TopMenuItem::Formation => "たいけい",
+            TopMenuItem::Save => "セーブ",
+            TopMenuItem::Load => "ロード",
+        }
+    }
+
+    /// この項目を指すカーソル位置
+    fn cursor(self) -> usize {
+        Self::ALL.iter().position(|&item| item == self).unwrap_or(0)
+    }
+}
+
 /// フィールドメニューのフェーズ（各フェーズが自身のデータを保持）
 #[derive(Debug, Clone)]
 pub enum FieldMenuPhase {
-    /// トップメニュー（じゅもん/どうぐ）
+    /// トップメニュー（じゅもん/どうぐ/そうび/いれかえ/たいけい/セーブ/ロード）
     TopMenu { cursor: usize },
     /// キャスター選択（呪文フロー）
     CasterSelect { candidates: Vec<usize>, cursor: usize },
@@ -40,6 +84,24 @@ pub enum FieldMenuPhase {
     MemberSelect { candidates: Vec<usize>, cursor: usize },
     /// アイテム選択
     ItemSelect { member: usize, items: Vec<ItemKey>, cursor: usize },
+    /// メンバー選択（装備フロー）
+    EquipMemberSelect { candidates: Vec<usize>, cursor: usize },
+    /// 装備スロット選択
+    EquipSlotSelect { member: usize, cursor: usize },
+    /// 装備品選択（末尾は「はずす」、disabledは装備できない候補）
+    EquipItemSelect {
+        member: usize,
+        slot: EquipSlot,
+        items: Vec<ItemKey>,
+        disabled: Vec<usize>,
+        cursor: usize,
+    },
+    /// いれかえる戦闘メンバーの枠選択（満員でなければ末尾は空き枠）
+    LineupSelect { cursor: usize },
+    /// 枠に入れるひかえの選択（空き枠以外は末尾が「ひかえに まわす」）
+    LineupReserveSelect { slot: usize, cursor: usize },
+    /// 隊列を前後入れかえるメンバーの選択
+    FormationSelect { cursor: usize },
     /// ターゲット選択（共用）
     TargetSelect { candidates: Vec<usize>, cursor: usize, context: TargetContext },
     /// メッセージ表示
@@ -75,7 +137,7 @@ impl FieldMenuState {
         self.disabled_indices.clear();
         match &self.phase {
             FieldMenuPhase::TopMenu { .. } => {
-                self.cached_labels = vec!["じゅもん".to_string(), "どうぐ".to_string()];
+                self.cached_labels = TopMenuItem::ALL.iter().map(|item| item.label().to_string()).collect();
             }
             FieldMenuPhase::CasterSelect { candidates, .. } => {
                 for &idx in candidates {
@@ -84,9 +146,9 @@ impl FieldMenuState {
                         "{} HP:{}/{} MP:{}/{}",
                         m.kind.name(),
                         m.stats.hp,
-                        m.stats.max_hp,
+                        m.effective_max_hp(),
                         m.stats.mp,
-                        m.stats.max_mp,
+                        m.effective_max_mp(),
                     ));
                 }
             }
@@ -114,7 +176,7 @@ impl FieldMenuState {
                             "{} HP:{}/{}",
                             m.kind.name(),
                             m.stats.hp,
-                            m.stats.max_hp,
+                            m.effective_max_hp(),
                         ));
                     }
                 }
@@ -124,22 +186,22 @@ impl FieldMenuState {
                 for &item in items {
                     let count = inv.count(item);
                     let entry = item.entry();
-                    if entry.is_weapon() {
+                    if entry.is_equipment() {
                         if *member == BAG_MEMBER_INDEX {
                             self.cached_labels.push(format!(
-                                "{} ATK+{} x{}",
+                                "{} {} x{}",
                                 entry.name,
-                                entry.attack_bonus,
+                                entry.bonus_label(),
                                 count
                             ));
                         } else {
-                            let equipped = party_state.members[*member].equipment.weapon == Some(item);
+                            let equipped = party_state.members[*member].equipment.is_equipped(item);
                             let equip_mark = if equipped { "E " } else { "" };
                             self.cached_labels.push(format!(
-                                "{}{} ATK+{} x{}",
+                                "{}{} {} x{}",
                                 equip_mark,
                                 entry.name,
-                                entry.attack_bonus,
+                                entry.bonus_label(),
                                 count
                             ));
                         }
@@ -149,6 +211,54 @@ impl FieldMenuState {
                     }
                 }
             }
+            FieldMenuPhase::EquipMemberSelect { candidates, .. } => {
+                for &idx in candidates {
+                    let m = &party_state.members[idx];
+                    self.cached_labels.push(format!(
+                        "{} ATK:{} DEF:{} SPD:{}",
+                        m.kind.name(),
+                        m.effective_attack(),
+                        m.effective_defense(),
+                        m.effective_speed(),
+                    ));
+                }
+            }
+            FieldMenuPhase::EquipSlotSelect { member, .. } => {
+                let equipment = &party_state.members[*member].equipment;
+                for slot in EquipSlot::ALL {
+                    let name = equipment.get(slot).map_or("なし", |item| item.name());
+                    self.cached_labels.push(format!("{}: {}", slot.label(), name));
+                }
+            }
+            FieldMenuPhase::EquipItemSelect { member, items, disabled, .. } => {
+                let equipment = &party_state.members[*member].equipment;
+                for &item in items {
+                    let equip_mark = if equipment.is_equipped(item) { "E " } else { "" };
+                    self.cached_labels.push(format!(
+                        "{}{} {}",
+                        equip_mark,
+                        item.name(),
+                        item.entry().bonus_label()
+                    ));
+                }
+                self.cached_labels.push("はずす".to_string());
+                self.disabled_indices = disabled.clone();
+            }
+            FieldMenuPhase::LineupSelect { .. } => {
+                self.cached_labels = lineup::slot_labels(party_state);
+            }
+            FieldMenuPhase::LineupReserveSelect { slot, .. } => {
+                (self.cached_labels, self.disabled_indices) = lineup::reserve_labels(party_state, *slot);
+            }
+            FieldMenuPhase::FormationSelect { .. } => {
+                for m in &party_state.members {
+                    self.cached_labels.push(format!(
+                        "{}  {}",
+                        m.kind.name(),
+                        party_state.row_of(m.kind).label(),
+                    ));
+                }
+            }
             FieldMenuPhase::TargetSelect { candidates, .. } => {
                 for &idx in candidates {
                     let m = &party_state.members[idx];
@@ -156,7 +266,7 @@ impl FieldMenuState {
                         "{} HP:{}/{}",
                         m.kind.name(),
                         m.stats.hp,
-                        m.stats.max_hp,
+                        m.effective_max_hp(),
                     ));
                 }
             }
@@ -177,6 +287,12 @@ impl CommandMenu for FieldMenuState {
             | FieldMenuPhase::SpellSelect { cursor, .. }
             | FieldMenuPhase::MemberSelect { cursor, .. }
             | FieldMenuPhase::ItemSelect { cursor, .. }
+            | FieldMenuPhase::EquipMemberSelect { cursor, .. }
+            | FieldMenuPhase::EquipSlotSelect { cursor, .. }
+            | FieldMenuPhase::EquipItemSelect { cursor, .. }
+            | FieldMenuPhase::LineupSelect { cursor }
+            | FieldMenuPhase::LineupReserveSelect { cursor, .. }
+            | FieldMenuPhase::FormationSelect { cursor }
             | FieldMenuPhase::TargetSelect { cursor, .. } => *cursor,
             FieldMenuPhase::ShowMessage { .. } => 0,
         }
@@ -189,6 +305,12 @@ impl CommandMenu for FieldMenuState {
             | FieldMenuPhase::SpellSelect { cursor, .. }
             | FieldMenuPhase::MemberSelect { cursor, .. }
             | FieldMenuPhase::ItemSelect { cursor, .. }
+            | FieldMenuPhase::EquipMemberSelect { cursor, .. }
+            | FieldMenuPhase::EquipSlotSelect { cursor, .. }
+            | FieldMenuPhase::EquipItemSelect { cursor, .. }
+            | FieldMenuPhase::LineupSelect { cursor }
+            | FieldMenuPhase::LineupReserveSelect { cursor, .. }
+            | FieldMenuPhase::FormationSelect { cursor }
             | FieldMenuPhase::TargetSelect { cursor, .. } => *cursor = index,
             FieldMenuPhase::ShowMessage { .. } => {}
         }
@@ -200,7 +322,9 @@ impl CommandMenu for FieldMenuState {
 
     fn visible_items(&self) -> Option<usize> {
         match &self.phase {
-            FieldMenuPhase::SpellSelect { .. } | FieldMenuPhase::ItemSelect { .. } => {
+            FieldMenuPhase::SpellSelect { .. }
+            | FieldMenuPhase::ItemSelect { .. }
+            | FieldMenuPhase::EquipItemSelect { .. } => {
                 Some(VISIBLE_ITEMS)
             }
             _ => None,
@@ -354,6 +478,17 @@ fn alive_member_indices(party_state: &PartyState) -> Vec<usize> {
         .collect()
 }
 
+/// 戦闘不能のメンバー（蘇生の対象）
+fn fallen_member_indices(party_state: &PartyState) -> Vec<usize> {
+    party_state
+        .members
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| !m.stats.is_alive())
+        .map(|(i, _)| i)
+        .collect()
+}
+
 fn close_menu(commands: &mut Commands, root_query: &Query<Entity, With<FieldMenuRoot>>) {
     despawn_menu_ui(commands, root_query);
     commands.remove_resource::<FieldMenuState>();
@@ -371,6 +506,8 @@ fn field_menu_input_system(
     state: Option<ResMut<FieldMenuState>>,
     root_query: Query<Entity, With<FieldMenuRoot>>,
     char_params: Res<CharacterParams>,
+    scene_state: Res<State<SceneState>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     if menu_open.is_none() {
         // メニュー非表示: 確認キーで開く
@@ -402,6 +539,7 @@ fn field_menu_input_system(
                 &mut commands,
                 &root_query,
                 cursor,
+                scene_state.get(),
             );
         }
         FieldMenuPhase::CasterSelect { candidates, cursor } => {
@@ -419,6 +557,7 @@ fn field_menu_input_system(
                 caster,
                 spells,
                 cursor,
+                &mut game_rng.field,
             );
         }
         FieldMenuPhase::MemberSelect { candidates, cursor } => {
@@ -436,8 +575,42 @@ fn field_menu_input_system(
                 member,
                 items,
                 cursor,
+                &char_params,
+            );
+        }
+        FieldMenuPhase::EquipMemberSelect { candidates, cursor } => {
+            handle_equip_member_select(&keyboard, &mut state, &party_state, candidates, cursor);
+        }
+        FieldMenuPhase::EquipSlotSelect { member, cursor } => {
+            handle_equip_slot_select(&keyboard, &mut state, &party_state, member, cursor, &char_params);
+        }
+        FieldMenuPhase::EquipItemSelect {
+            member,
+            slot,
+            items,
+            disabled,
+            cursor,
+        } => {
+            handle_equip_item_select(
+                &keyboard,
+                &mut state,
+                &mut party_state,
+                member,
+                slot,
+                items,
+                disabled,
+                cursor,
             );
         }
+        FieldMenuPhase::LineupSelect { cursor } => {
+            handle_lineup_select(&keyboard, &mut state, &party_state, cursor);
+        }
+        FieldMenuPhase::LineupReserveSelect { slot, cursor } => {
+            handle_lineup_reserve_select(&keyboard, &mut state, &mut party_state, slot, cursor);
+        }
+        FieldMenuPhase::FormationSelect { cursor } => {
+            handle_formation_select(&keyboard, &mut state, &mut party_state, cursor);
+        }
         FieldMenuPhase::TargetSelect {
             candidates,
             cursor,
@@ -450,6 +623,7 @@ fn field_menu_input_system(
                 candidates,
                 cursor,
                 context,
+                &mut game_rng.field,
             );
         }
         FieldMenuPhase::ShowMessage { .. } => {
@@ -470,11 +644,12 @@ fn handle_top_menu(
     commands: &mut Commands,
     root_query: &Query<Entity, With<FieldMenuRoot>>,
     mut cursor: usize,
+    scene_state: &SceneState,
 ) {
     if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
         cursor -= 1;
     }
-    if input_ui::is_down_just_pressed(keyboard) && cursor < 1 {
+    if input_ui::is_down_just_pressed(keyboard) && cursor < TopMenuItem::ALL.len() - 1 {
         cursor += 1;
     }
     state.phase = FieldMenuPhase::TopMenu { cursor };
@@ -484,9 +659,11 @@ fn handle_top_menu(
         return;
     }
 
-    if input_ui::is_confirm_just_pressed(keyboard) {
-        if cursor == 0 {
-            // じゅもん → CasterSelect
+    if !input_ui::is_confirm_just_pressed(keyboard) {
+        return;
+    }
+    match TopMenuItem::ALL[cursor] {
+        TopMenuItem::Spell => {
             let candidates = alive_member_indices(party_state);
             state.set_phase(
                 FieldMenuPhase::CasterSelect {
@@ -495,8 +672,9 @@ fn handle_top_menu(
                 },
                 party_state,
             );
-        } else {
-            // どうぐ → MemberSelect（末尾にふくろを追加）
+        }
+        TopMenuItem::Item => {
+            // 末尾にふくろを追加
             let mut candidates = alive_member_indices(party_state);
             candidates.push(BAG_MEMBER_INDEX);
             state.set_phase(
@@ -507,6 +685,69 @@ fn handle_top_menu(
                 party_state,
             );
         }
+        TopMenuItem::Equip => {
+            let candidates = alive_member_indices(party_state);
+            state.set_phase(
+                FieldMenuPhase::EquipMemberSelect {
+                    candidates,
+                    cursor: 0,
+                },
+                party_state,
+            );
+        }
+        TopMenuItem::Lineup => {
+            if party_state.reserve.is_empty() {
+                state.set_phase(
+                    FieldMenuPhase::ShowMessage {
+                        message: "ひかえの なかまが いない".to_string(),
+                    },
+                    party_state,
+                );
+                return;
+            }
+            state.set_phase(FieldMenuPhase::LineupSelect { cursor: 0 }, party_state);
+        }
+        TopMenuItem::Formation => {
+            state.set_phase(FieldMenuPhase::FormationSelect { cursor: 0 }, party_state);
+        }
+        item @ (TopMenuItem::Save | TopMenuItem::Load) => {
+            // セーブ/ロードはワールドマップ上でのみ可能
+            if *scene_state != SceneState::Exploring {
+                state.set_phase(
+                    FieldMenuPhase::ShowMessage {
+                        message: "ここでは ぼうけんのしょを つかえない".to_string(),
+                    },
+                    party_state,
+                );
+                return;
+            }
+            let is_save = item == TopMenuItem::Save;
+            commands.queue(move |world: &mut World| {
+                let message = if is_save {
+                    match save_state::save_game(world) {
+                        Ok(()) => "ぼうけんのしょに きろくした".to_string(),
+                        Err(e) => {
+                            warn!("save failed: {}", e);
+                            "きろくに しっぱいした".to_string()
+                        }
+                    }
+                } else {
+                    match save_state::load_game(world) {
+                        Ok(()) => "ぼうけんのしょを よみこんだ".to_string(),
+                        Err(e) => {
+                            warn!("load failed: {}", e);
+                            "よみこみに しっぱいした".to_string()
+                        }
+                    }
+                };
+                world.resource_scope(|world, mut state: Mut<FieldMenuState>| {
+                    state.set_phase(
+                        FieldMenuPhase::ShowMessage { message },
+                        world.resource::<PartyState>(),
+                    );
+                });
+            });
+        }
     }
 }
 
@@ -531,7 +772,7 @@ fn handle_caster_select(
     };
 
     if input_ui::is_cancel_just_pressed(keyboard) {
-        state.set_phase(FieldMenuPhase::TopMenu { cursor: 0 }, party_state);
+        state.set_phase(FieldMenuPhase::TopMenu { cursor: TopMenuItem::Spell.cursor() }, party_state);
         return;
     }
 
@@ -569,6 +810,7 @@ fn handle_spell_select(
     caster: usize,
     spells: Vec<SpellEntry>,
     mut cursor: usize,
+    rng: &mut impl Rng,
 ) {
     let count = spells.len();
     if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
@@ -603,7 +845,13 @@ fn handle_spell_select(
             return;
         }
 
-        if !matches!(spell.effect, SpellEffect::Heal { .. }) {
+        let target_candidates = match spell.effect {
+            SpellEffect::Heal { .. } => alive_member_indices(party_state),
+            SpellEffect::Revive { .. } => fallen_member_indices(party_state),
+            _ => Vec::new(),
+        };
+
+        if !matches!(spell.effect, SpellEffect::Heal { .. } | SpellEffect::Revive { .. }) {
             state.set_phase(
                 FieldMenuPhase::ShowMessage {
                     message: "フィールドでは つかえない".to_string(),
@@ -612,10 +860,21 @@ fn handle_spell_select(
             );
         } else if spell.target_type == SpellTarget::AllAllies {
             // 全体回復: ターゲット選択スキップ、全味方に一括実行
-            execute_aoe_heal(state, party_state, caster, spell);
+            execute_aoe_heal(state, party_state, caster, spell, rng);
+        } else if target_candidates.is_empty() {
+            let message = if matches!(spell.effect, SpellEffect::Revive { .. }) {
+                "いきかえらせる なかまが いない"
+            } else {
+                "かいふくできる なかまが いない"
+            };
+            state.set_phase(
+                FieldMenuPhase::ShowMessage {
+                    message: message.to_string(),
+                },
+                party_state,
+            );
         } else {
-            // 単体回復: ターゲット選択へ
-            let target_candidates = alive_member_indices(party_state);
+            // 単体回復・蘇生: ターゲット選択へ
             state.set_phase(
                 FieldMenuPhase::TargetSelect {
                     candidates: target_candidates,
@@ -652,7 +911,7 @@ fn handle_member_select(
     };
 
     if input_ui::is_cancel_just_pressed(keyboard) {
-        state.set_phase(FieldMenuPhase::TopMenu { cursor: 0 }, party_state);
+        state.set_phase(FieldMenuPhase::TopMenu { cursor: TopMenuItem::Item.cursor() }, party_state);
         return;
     }
 
@@ -686,6 +945,7 @@ fn handle_item_select(
     member: usize,
     items: Vec<ItemKey>,
     mut cursor: usize,
+    char_params: &CharacterParams,
 ) {
     let count = items.len();
     if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
@@ -717,8 +977,20 @@ fn handle_item_select(
         let item = items[cursor];
         let entry = item.entry();
         match entry.effect {
-            ItemEffect::Heal { .. } => {
-                let target_candidates = alive_member_indices(party_state);
+            ItemEffect::Revive { .. } if fallen_member_indices(party_state).is_empty() => {
+                state.set_phase(
+                    FieldMenuPhase::ShowMessage {
+                        message: "いきかえらせる なかまが いない".to_string(),
+                    },
+                    party_state,
+                );
+            }
+            ItemEffect::Heal { .. } | ItemEffect::Revive { .. } => {
+                let target_candidates = if matches!(entry.effect, ItemEffect::Revive { .. }) {
+                    fallen_member_indices(party_state)
+                } else {
+                    alive_member_indices(party_state)
+                };
                 state.set_phase(
                     FieldMenuPhase::TargetSelect {
                         candidates: target_candidates,
@@ -732,10 +1004,22 @@ fn handle_item_select(
                     party_state,
                 );
             }
-            _ if entry.is_weapon() => {
+            _ if entry.is_equipment() => {
                 if member == BAG_MEMBER_INDEX {
-                    // ふくろから装備 → 誰に装備するかターゲット選択
-                    let target_candidates = alive_member_indices(party_state);
+                    // ふくろから装備 → 装備できるメンバーからターゲット選択
+                    let target_candidates: Vec<usize> = alive_member_indices(party_state)
+                        .into_iter()
+                        .filter(|&i| char_params.can_equip(party_state.members[i].kind, item))
+                        .collect();
+                    if target_candidates.is_empty() {
+                        state.set_phase(
+                            FieldMenuPhase::ShowMessage {
+                                message: format!("{}を そうびできる なかまが いない", item.name()),
+                            },
+                            party_state,
+                        );
+                        return;
+                    }
                     state.set_phase(
                         FieldMenuPhase::TargetSelect {
                             candidates: target_candidates,
@@ -748,13 +1032,21 @@ fn handle_item_select(
                         },
                         party_state,
                     );
+                } else if !char_params.can_equip(party_state.members[member].kind, item) {
+                    let name = party_state.members[member].kind.name();
+                    state.set_phase(
+                        FieldMenuPhase::ShowMessage {
+                            message: format!("{}は {}を そうびできない", name, item.name()),
+                        },
+                        party_state,
+                    );
                 } else {
                     let member_ref = &mut party_state.members[member];
                     let member_name = member_ref.kind.name();
-                    let old_weapon = member_ref.equipment.equip_weapon(item);
+                    let old_item = member_ref.equip(item);
                     let mut msg = format!("{}は {}を そうびした！", member_name, item.name());
-                    if let Some(old_w) = old_weapon {
-                        msg.push_str(&format!("\n{}を はずした", old_w.name()));
+                    if let Some(old) = old_item.filter(|&old| old != item) {
+                        msg.push_str(&format!("\n{}を はずした", old.name()));
                     }
                     state.set_phase(
                         FieldMenuPhase::ShowMessage { message: msg },
@@ -762,7 +1054,16 @@ fn handle_item_select(
                     );
                 }
             }
-            ItemEffect::KeyItem | ItemEffect::Material => {
+            ItemEffect::Cure { .. } | ItemEffect::Escape => {
+                // 状態異常・逃走は戦闘中にしか意味がない
+                state.set_phase(
+                    FieldMenuPhase::ShowMessage {
+                        message: format!("{}は せんとうちゅうに つかう どうぐだ", item.name()),
+                    },
+                    party_state,
+                );
+            }
+            ItemEffect::KeyItem | ItemEffect::Material | ItemEffect::Equipment => {
                 let name = member_name(party_state, member);
                 state.set_phase(
                     FieldMenuPhase::ShowMessage {
@@ -780,12 +1081,242 @@ fn handle_item_select(
     }
 }
 
+fn handle_equip_member_select(
+    keyboard: &ButtonInput<KeyCode>,
+    state: &mut FieldMenuState,
+    party_state: &PartyState,
+    candidates: Vec<usize>,
+    mut cursor: usize,
+) {
+    let count = candidates.len();
+    if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
+        cursor -= 1;
+    }
+    if input_ui::is_down_just_pressed(keyboard) && cursor < count - 1 {
+        cursor += 1;
+    }
+    state.phase = FieldMenuPhase::EquipMemberSelect {
+        candidates: candidates.clone(),
+        cursor,
+    };
+
+    if input_ui::is_cancel_just_pressed(keyboard) {
+        state.set_phase(FieldMenuPhase::TopMenu { cursor: TopMenuItem::Equip.cursor() }, party_state);
+        return;
+    }
+
+    if input_ui::is_confirm_just_pressed(keyboard) {
+        state.set_phase(
+            FieldMenuPhase::EquipSlotSelect {
+                member: candidates[cursor],
+                cursor: 0,
+            },
+            party_state,
+        );
+    }
+}
+
+fn handle_equip_slot_select(
+    keyboard: &ButtonInput<KeyCode>,
+    state: &mut FieldMenuState,
+    party_state: &PartyState,
+    member: usize,
+    mut cursor: usize,
+    char_params: &CharacterParams,
+) {
+    if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
+        cursor -= 1;
+    }
+    if input_ui::is_down_just_pressed(keyboard) && cursor < EquipSlot::ALL.len() - 1 {
+        cursor += 1;
+    }
+    state.phase = FieldMenuPhase::EquipSlotSelect { member, cursor };
+
+    if input_ui::is_cancel_just_pressed(keyboard) {
+        let candidates = alive_member_indices(party_state);
+        let member_cursor = candidates.iter().position(|&i| i == member).unwrap_or(0);
+        state.set_phase(
+            FieldMenuPhase::EquipMemberSelect {
+                candidates,
+                cursor: member_cursor,
+            },
+            party_state,
+        );
+        return;
+    }
+
+    if input_ui::is_confirm_just_pressed(keyboard) {
+        let slot = EquipSlot::ALL[cursor];
+        let kind = party_state.members[member].kind;
+        let items = party_state.members[member].equip_candidates(slot);
+        let disabled = items
+            .iter()
+            .enumerate()
+            .filter(|&(_, &item)| !char_params.can_equip(kind, item))
+            .map(|(i, _)| i)
+            .collect();
+        state.set_phase(
+            FieldMenuPhase::EquipItemSelect {
+                member,
+                slot,
+                items,
+                disabled,
+                cursor: 0,
+            },
+            party_state,
+        );
+    }
+}
+
+fn handle_lineup_select(
+    keyboard: &ButtonInput<KeyCode>,
+    state: &mut FieldMenuState,
+    party_state: &PartyState,
+    mut cursor: usize,
+) {
+    let count = lineup::slot_count(party_state);
+    if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
+        cursor -= 1;
+    }
+    if input_ui::is_down_just_pressed(keyboard) && cursor < count - 1 {
+        cursor += 1;
+    }
+    state.phase = FieldMenuPhase::LineupSelect { cursor };
+
+    if input_ui::is_cancel_just_pressed(keyboard) {
+        state.set_phase(FieldMenuPhase::TopMenu { cursor: TopMenuItem::Lineup.cursor() }, party_state);
+        return;
+    }
+
+    if input_ui::is_confirm_just_pressed(keyboard) {
+        state.set_phase(
+            FieldMenuPhase::LineupReserveSelect { slot: cursor, cursor: 0 },
+            party_state,
+        );
+    }
+}
+
+fn handle_lineup_reserve_select(
+    keyboard: &ButtonInput<KeyCode>,
+    state: &mut FieldMenuState,
+    party_state: &mut PartyState,
+    slot: usize,
+    mut cursor: usize,
+) {
+    let count = state.cached_labels.len();
+    if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
+        cursor -= 1;
+    }
+    if input_ui::is_down_just_pressed(keyboard) && cursor < count - 1 {
+        cursor += 1;
+    }
+    state.phase = FieldMenuPhase::LineupReserveSelect { slot, cursor };
+
+    if input_ui::is_cancel_just_pressed(keyboard) {
+        state.set_phase(FieldMenuPhase::LineupSelect { cursor: slot }, party_state);
+        return;
+    }
+
+    if input_ui::is_confirm_just_pressed(keyboard) {
+        if state.is_disabled(cursor) {
+            return;
+        }
+        let message = lineup::apply_choice(party_state, slot, cursor);
+        state.set_phase(FieldMenuPhase::ShowMessage { message }, party_state);
+    }
+}
+
+fn handle_formation_select(
+    keyboard: &ButtonInput<KeyCode>,
+    state: &mut FieldMenuState,
+    party_state: &mut PartyState,
+    mut cursor: usize,
+) {
+    let count = party_state.members.len();
+    if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
+        cursor -= 1;
+    }
+    if input_ui::is_down_just_pressed(keyboard) && cursor < count - 1 {
+        cursor += 1;
+    }
+    state.phase = FieldMenuPhase::FormationSelect { cursor };
+
+    if input_ui::is_cancel_just_pressed(keyboard) {
+        state.set_phase(FieldMenuPhase::TopMenu { cursor: TopMenuItem::Formation.cursor() }, party_state);
+        return;
+    }
+
+    // 決定で前列/後列を切りかえ、その場で表示を更新する
+    if input_ui::is_confirm_just_pressed(keyboard) {
+        let kind = party_state.members[cursor].kind;
+        party_state.toggle_row(kind);
+        state.set_phase(FieldMenuPhase::FormationSelect { cursor }, party_state);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_equip_item_select(
+    keyboard: &ButtonInput<KeyCode>,
+    state: &mut FieldMenuState,
+    party_state: &mut PartyState,
+    member: usize,
+    slot: EquipSlot,
+    items: Vec<ItemKey>,
+    disabled: Vec<usize>,
+    mut cursor: usize,
+) {
+    // 候補 + 「はずす」（末尾）
+    let count = items.len() + 1;
+    if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
+        cursor -= 1;
+    }
+    if input_ui::is_down_just_pressed(keyboard) && cursor < count - 1 {
+        cursor += 1;
+    }
+    state.phase = FieldMenuPhase::EquipItemSelect {
+        member,
+        slot,
+        items: items.clone(),
+        disabled: disabled.clone(),
+        cursor,
+    };
+
+    if input_ui::is_cancel_just_pressed(keyboard) {
+        let slot_cursor = EquipSlot::ALL.iter().position(|&s| s == slot).unwrap_or(0);
+        state.set_phase(
+            FieldMenuPhase::EquipSlotSelect {
+                member,
+                cursor: slot_cursor,
+            },
+            party_state,
+        );
+        return;
+    }
+
+    if input_ui::is_confirm_just_pressed(keyboard) {
+        let target = &mut party_state.members[member];
+        let name = target.kind.name();
+        let message = if disabled.contains(&cursor) {
+            format!("{}は {}を そうびできない", name, items[cursor].name())
+        } else if let Some(&item) = items.get(cursor) {
+            target.equip(item);
+            format!("{}は {}を そうびした！", name, item.name())
+        } else if let Some(item) = target.unequip(slot) {
+            format!("{}は {}を はずした", name, item.name())
+        } else {
+            format!("{}は {}を そうびしていない", name, slot.label())
+        };
+        state.set_phase(FieldMenuPhase::ShowMessage { message }, party_state);
+    }
+}
+
 /// 全体回復呪文をフィールドで実行
 fn execute_aoe_heal(
     state: &mut FieldMenuState,
     party_state: &mut PartyState,
     caster: usize,
     spell: SpellEntry,
+    rng: &mut impl Rng,
 ) {
     let consumed = party_state.members[caster].stats.use_mp(spell.mp_cost);
     if !consumed {
@@ -802,10 +1333,10 @@ fn execute_aoe_heal(
     };
 
     for &pi in &alive {
-        let random_factor = 0.8 + rand::random::<f32>() * 0.4;
+        let random_factor = rng.gen_range(0.8..1.2);
         let amount = spell::heal_amount(base_heal, random_factor);
         let target = &mut party_state.members[pi];
-        target.stats.hp = (target.stats.hp + amount).min(target.stats.max_hp);
+        target.stats.hp = (target.stats.hp + amount).min(target.effective_max_hp());
         let target_name = party_state.members[pi].kind.name();
         lines.push(format!("{}の HPが {}かいふく！", target_name, amount));
     }
@@ -825,6 +1356,7 @@ fn handle_target_select(
     candidates: Vec<usize>,
     mut cursor: usize,
     context: TargetContext,
+    rng: &mut impl Rng,
 ) {
     let count = candidates.len();
     if input_ui::is_up_just_pressed(keyboard) && cursor > 0 {
@@ -891,11 +1423,11 @@ fn handle_target_select(
                             return;
                         }
 
-                        let random_factor = 0.8 + rand::random::<f32>() * 0.4;
+                        let random_factor = rng.gen_range(0.8..1.2);
                         let amount = spell::heal_amount(power, random_factor);
 
                         let target = &mut party_state.members[target_idx];
-                        target.stats.hp = (target.stats.hp + amount).min(target.stats.max_hp);
+                        target.stats.hp = (target.stats.hp + amount).min(target.effective_max_hp());
 
                         let name = member_name(party_state, member);
                         let target_name = party_state.members[target_idx].kind.name();
@@ -907,23 +1439,45 @@ fn handle_target_select(
                             amount
                         )
                     }
-                    _ if entry.is_weapon() && member == BAG_MEMBER_INDEX => {
-                        let removed = party_state.bag.remove_item(item);
-                        if !removed {
+                    ItemEffect::Revive { hp_percent } => {
+                        let used = get_inventory_mut(party_state, member).use_item(item);
+                        if !used {
+                            return;
+                        }
+
+                        let target = &mut party_state.members[target_idx];
+                        target.stats.hp = spell::revive_hp(target.effective_max_hp(), hp_percent);
+
+                        let name = member_name(party_state, member);
+                        let target_name = party_state.members[target_idx].kind.name();
+                        format!(
+                            "{}は {}を つかった！\n{}が いきかえった！",
+                            name,
+                            item.name(),
+                            target_name
+                        )
+                    }
+                    _ if entry.is_equipment() && member == BAG_MEMBER_INDEX => {
+                        let target_name = party_state.members[target_idx].kind.name();
+                        // 装備品はもちものに入れて装備する
+                        if !party_state.members[target_idx].inventory.can_add(1) {
+                            state.set_phase(
+                                FieldMenuPhase::ShowMessage {
+                                    message: format!("{}の もちものが いっぱいだ！", target_name),
+                                },
+                                party_state,
+                            );
+                            return;
+                        }
+                        if !party_state.bag.remove_item(item) {
                             return;
                         }
                         let target_member = &mut party_state.members[target_idx];
-                        let target_name = target_member.kind.name().to_string();
-                        let old_weapon = target_member.equipment.equip_weapon(item);
+                        target_member.inventory.add(item, 1);
+                        let old_item = target_member.equip(item);
                         let mut msg = format!("{}は {}を そうびした！", target_name, item.name());
-                        if let Some(old_w) = old_weapon {
-                            // 旧武器をターゲットのインベントリへ、満杯なら袋へ
-                            if !party_state.members[target_idx].inventory.try_add(old_w, 1) {
-                                party_state.bag.add(old_w, 1);
-                                msg.push_str(&format!("\n{}を ふくろに しまった", old_w.name()));
-                            } else {
-                                msg.push_str(&format!("\n{}を はずした", old_w.name()));
-                            }
+                        if let Some(old) = old_item.filter(|&old| old != item) {
+                            msg.push_str(&format!("\n{}を はずした", old.name()));
                         }
                         msg
                     }
@@ -942,25 +1496,31 @@ fn handle_target_select(
                     return;
                 }
 
-                let base_heal = match spell.effect {
-                    SpellEffect::Heal { base_heal } => base_heal,
-                    _ => return,
-                };
-                let random_factor = 0.8 + rand::random::<f32>() * 0.4;
-                let amount = spell::heal_amount(base_heal, random_factor);
-
-                let target = &mut party_state.members[target_idx];
-                target.stats.hp = (target.stats.hp + amount).min(target.stats.max_hp);
-
                 let caster_name = party_state.members[caster].kind.name();
                 let target_name = party_state.members[target_idx].kind.name();
-                format!(
-                    "{}は {}を となえた！\n{}の HPが {}かいふく！",
-                    caster_name,
-                    spell.name,
-                    target_name,
-                    amount
-                )
+                let target = &mut party_state.members[target_idx];
+                match spell.effect {
+                    SpellEffect::Heal { base_heal } => {
+                        let random_factor = rng.gen_range(0.8..1.2);
+                        let amount = spell::heal_amount(base_heal, random_factor);
+                        target.stats.hp = (target.stats.hp + amount).min(target.effective_max_hp());
+                        format!(
+                            "{}は {}を となえた！\n{}の HPが {}かいふく！",
+                            caster_name,
+                            spell.name,
+                            target_name,
+                            amount
+                        )
+                    }
+                    SpellEffect::Revive { hp_percent } => {
+                        target.stats.hp = spell::revive_hp(target.effective_max_hp(), hp_percent);
+                        format!(
+                            "{}は {}を となえた！\n{}が いきかえった！",
+                            caster_name, spell.name, target_name
+                        )
+                    }
+                    _ => return,
+                }
             }
         };
         state.set_phase(
@@ -1001,6 +1561,25 @@ fn field_menu_title_system(
                     **text = format!("{}の もちもの", name);
                 }
             }
+            FieldMenuPhase::EquipMemberSelect { .. } => {
+                **text = "だれの そうびを かえる？".to_string();
+            }
+            FieldMenuPhase::EquipSlotSelect { member, .. } => {
+                let name = party_state.members[*member].kind.name();
+                **text = format!("{}の そうび", name);
+            }
+            FieldMenuPhase::EquipItemSelect { slot, .. } => {
+                **text = format!("{}を えらぶ", slot.label());
+            }
+            FieldMenuPhase::LineupSelect { .. } => {
+                **text = "どの なかまと いれかえる？".to_string();
+            }
+            FieldMenuPhase::LineupReserveSelect { slot, .. } => {
+                **text = lineup::reserve_prompt(&party_state, *slot);
+            }
+            FieldMenuPhase::FormationSelect { .. } => {
+                **text = "たいけいを かえる".to_string();
+            }
             FieldMenuPhase::TargetSelect { .. } => {
                 **text = "だれに つかう？".to_string();
             }
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
+use rand::Rng;
 
 use scene_state::BattleState;
 use field_core::{ActiveMap, OnBoat, Player, TilePosition};
+use world_state::GameRng;
 use crate::TileEnteredEvent;
 
 /// プレイヤーがタイルに到着した際にエンカウント判定を行うシステム
@@ -10,6 +12,7 @@ pub fn check_encounter_system(
     player_query: Query<(&TilePosition, Option<&OnBoat>), With<Player>>,
     active_map: Res<ActiveMap>,
     mut next_state: ResMut<NextState<BattleState>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     for _event in events.read() {
         let Ok((tile_pos, on_boat)) = player_query.single() else {
@@ -20,7 +23,7 @@ pub fn check_encounter_system(
             continue;
         }
 
-        if rand::random::<f32>() < active_map.encounter_rate_at(tile_pos.x, tile_pos.y) {
+        if game_rng.encounter.gen_range(0.0..1.0) < active_map.encounter_rate_at(tile_pos.x, tile_pos.y) {
             next_state.set(BattleState::Active);
             return;
         }
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 
 use field_core::{ActiveMap, Boat, OnBoat, Player, TilePosition};
 use field_walk::{resolve_field_move, FieldMoveResult};
+use item_data::ItemKey;
+use party_state::PartyState;
 
 use crate::{MovementBlockedEvent, PendingMove, PlayerMovedEvent};
 use crate::input::MovementInput;
@@ -12,13 +14,20 @@ pub enum ExecuteMoveResult {
     Blocked,
 }
 
+/// カギのかかった扉を通れるか（どうのカギを持っているか）
+pub fn party_has_door_key(party_state: Option<&PartyState>) -> bool {
+    party_state.is_some_and(|party| party.has_item(ItemKey::CopperKey))
+}
+
 /// 船なしの単純な移動実行（洞窟・徒歩共通）
+#[allow(clippy::too_many_arguments)]
 pub fn apply_simple_move(
     entity: Entity,
     tile_pos: &mut TilePosition,
     dx: i32,
     dy: i32,
     active_map: &ActiveMap,
+    has_key: bool,
     moved_events: &mut MessageWriter<PlayerMovedEvent>,
     blocked_events: &mut MessageWriter<MovementBlockedEvent>,
 ) -> ExecuteMoveResult {
@@ -33,6 +42,7 @@ pub fn apply_simple_move(
         dx,
         dy,
         false,
+        has_key,
     );
     match result {
         FieldMoveResult::Walked { new_x, new_y } => {
@@ -59,22 +69,23 @@ pub fn apply_simple_move(
 /// 入力に基づいて移動を実行（船なし）。
 /// 1回目の移動を実行し、斜め入力なら2回目はPendingMoveに登録する。
 /// PendingMoveはSmoothMove完了後に実行される（中間タイルでのエンカウント判定を保証）。
+#[allow(clippy::too_many_arguments)]
 pub fn apply_input_move(
     commands: &mut Commands,
     entity: Entity,
     tile_pos: &mut TilePosition,
     input: &MovementInput,
     active_map: &ActiveMap,
+    has_key: bool,
     moved_events: &mut MessageWriter<PlayerMovedEvent>,
     blocked_events: &mut MessageWriter<MovementBlockedEvent>,
 ) {
     if let ExecuteMoveResult::Success = apply_simple_move(
         entity, tile_pos, input.first_dx, input.first_dy,
-        active_map, moved_events, blocked_events,
-    ) {
-        if let Some((dx2, dy2)) = input.pending_direction {
-            commands.entity(entity).insert(PendingMove { direction: (dx2, dy2) });
-        }
+        active_map, has_key, moved_events, blocked_events,
+    ) && let Some((dx2, dy2)) = input.pending_direction
+    {
+        commands.entity(entity).insert(PendingMove { direction: (dx2, dy2) });
     }
 }
 
@@ -88,6 +99,7 @@ pub fn execute_move(
     dy: i32,
     active_map: &ActiveMap,
     on_boat: Option<&OnBoat>,
+    has_key: bool,
     boat_query: &mut Query<(Entity, &mut TilePosition), (With<Boat>, Without<Player>)>,
     moved_events: &mut MessageWriter<PlayerMovedEvent>,
     blocked_events: &mut MessageWriter<MovementBlockedEvent>,
@@ -103,6 +115,7 @@ pub fn execute_move(
         dx,
         dy,
         on_boat.is_some(),
+        has_key,
     );
     match result {
         FieldMoveResult::Walked { new_x, new_y }
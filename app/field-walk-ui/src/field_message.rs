@@ -14,7 +14,7 @@ pub struct FieldMessageUI;
 pub fn field_message_not_active(
     state: Option<Res<FieldMessageState>>,
 ) -> bool {
-    !state.is_some_and(|s| s.message.is_some())
+    state.is_none_or(|s| s.message.is_none())
 }
 
 /// メッセージ確認入力システム: 確認キーでメッセージをクリア
@@ -9,7 +9,7 @@ use world_gen::{
 
 use field_core::{ActiveMap, Player, TilePosition, TILE_SIZE};
 use party::default_candidates;
-use world_state::{ContinentCavePositions, ContinentMap, EncounterZone, HokoraPositions, RecruitmentMap};
+use world_state::{ContinentCavePositions, ContinentMap, EncounterZone, HokoraPositions, RecruitmentMap, WorldSeed};
 use terrain::Structure;
 
 use crate::{load_tile_textures, spawn_boat_entities, BoatSpawnsResource, BossCaveWorldPos};
@@ -146,9 +146,15 @@ pub fn spawn_field_map_with_rng(
     commands.insert_resource(active_map);
 }
 
-/// thread_rng使用の後方互換ラッパー（Bevyシステム用）
-pub fn spawn_field_map(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut rng = rand::thread_rng();
+/// WorldSeedからフィールドマップを生成する（Bevyシステム用）
+///
+/// WorldSeedとGameRngは起動時に登録しておくこと。
+pub fn spawn_field_map(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    world_seed: Res<WorldSeed>,
+) {
+    let mut rng = world_seed.world_rng();
     spawn_field_map_with_rng(&mut commands, &asset_server, &mut rng);
 }
 
@@ -8,6 +8,8 @@ use crate::{
     apply_simple_move, execute_move, MovementBlockedEvent,
     MovementLocked, MovementState, PendingMove, PlayerMovedEvent, TileEnteredEvent,
 };
+use crate::party_has_door_key;
+use party_state::PartyState;
 
 /// フィールドでのSmoothMove完了後の処理（2フェーズ）
 ///
@@ -19,6 +21,7 @@ pub fn handle_field_move_completed(
     mut commands: Commands,
     mut move_state: ResMut<MovementState>,
     active_map: Res<ActiveMap>,
+    party_state: Option<Res<PartyState>>,
     mut query: Query<
         (
             Entity,
@@ -43,7 +46,7 @@ pub fn handle_field_move_completed(
             commands.entity(entity).remove::<PendingMove>();
             execute_move(
                 &mut commands, entity, &mut tile_pos, dx, dy,
-                &active_map, on_boat, &mut boat_query,
+                &active_map, on_boat, party_has_door_key(party_state.as_deref()), &mut boat_query,
                 &mut moved_events, &mut blocked_events,
             );
         }
@@ -78,6 +81,7 @@ pub fn handle_simple_move_completed(
     mut commands: Commands,
     mut move_state: ResMut<MovementState>,
     active_map: Res<ActiveMap>,
+    party_state: Option<Res<PartyState>>,
     mut query: Query<
         (
             Entity,
@@ -101,7 +105,8 @@ pub fn handle_simple_move_completed(
             commands.entity(entity).remove::<PendingMove>();
             apply_simple_move(
                 entity, &mut tile_pos, dx, dy,
-                &active_map, &mut moved_events, &mut blocked_events,
+                &active_map, party_has_door_key(party_state.as_deref()),
+                &mut moved_events, &mut blocked_events,
             );
         }
         return;
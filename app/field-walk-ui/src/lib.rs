@@ -12,6 +12,7 @@ pub mod rendering;
 mod resources;
 pub mod simple_tiles;
 mod smooth_move;
+mod symbol_encounter;
 pub mod tile_pool;
 
 // world-ui から統合されたモジュール
@@ -31,7 +32,7 @@ pub use constants::*;
 pub use encounter::check_encounter_system;
 pub use events::{MovementBlockedEvent, PlayerMovedEvent, TileEnteredEvent};
 pub use execute::{
-    apply_input_move, apply_simple_move, execute_move,
+    apply_input_move, apply_simple_move, execute_move, party_has_door_key,
     ExecuteMoveResult,
 };
 pub use input::{process_movement_input, MovementInput};
@@ -43,12 +44,17 @@ pub use smooth_move::{
     ease_out_quad, is_smooth_moving, start_smooth_move, update_smooth_move, SmoothMove,
     MOVE_DURATION,
 };
+pub use symbol_encounter::{
+    cave_random_encounter_enabled, cave_symbol_encounter_enabled, despawn_symbol_enemies,
+    move_symbol_enemies_system, random_encounter_enabled, spawn_symbol_enemies_system,
+    symbol_encounter_enabled, CaveEncounterMode, EncounterMode, SymbolEnemy, MAX_SYMBOL_ENEMIES,
+};
 pub use terrain::MoveResult;
 pub use tile_pool::{create_tile_pool, init_tile_pool, update_visible_tiles, PooledTile, TilePool};
 
 // world-ui から統合された再エクスポート
 pub use hud::toggle_hud_visibility;
-pub use hud_ui::{cleanup_hud, setup_hud, update_hud};
+pub use hud_ui::{cleanup_hud, rebuild_hud_on_lineup_change, setup_hud, update_hud};
 pub use exploration_data::{init_exploration_system, update_exploration_system, ExplorationData};
 pub use minimap::{init_minimap_system, toggle_minimap_visibility_system, update_minimap_texture_system};
 pub use player_input::{player_movement, sync_boat_with_player};
@@ -141,11 +147,17 @@ pub fn register_exploring_movement_systems(app: &mut App) {
     );
 }
 
-/// タイルアクション + エンカウント
+/// タイルアクション + エンカウント（方式はEncounterModeで切り替える）
 pub fn register_exploring_event_systems(app: &mut App) {
     app.add_systems(
         Update,
-        (check_tile_action_system, check_encounter_system)
+        (
+            check_tile_action_system,
+            check_encounter_system.run_if(random_encounter_enabled),
+            (move_symbol_enemies_system, spawn_symbol_enemies_system)
+                .chain()
+                .run_if(symbol_encounter_enabled),
+        )
             .chain()
             .after(sync_boat_with_player)
             .run_if(in_state(SceneState::Exploring).and(in_state(BattleState::None))),
@@ -163,6 +175,7 @@ pub struct WorldPlugin;
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MapModeState>()
+            .init_resource::<EncounterMode>()
             .add_systems(
                 Startup,
                 (
@@ -178,11 +191,12 @@ impl Plugin for WorldPlugin {
             .add_systems(OnEnter(InField), setup_hud)
             .add_systems(
                 Update,
-                (toggle_hud_visibility, update_hud)
+                (toggle_hud_visibility, update_hud, rebuild_hud_on_lineup_change)
                     .chain()
                     .run_if(in_state(InField)),
             )
-            .add_systems(OnExit(InField), cleanup_hud);
+            .add_systems(OnExit(InField), cleanup_hud)
+            .add_systems(OnExit(SceneState::Exploring), despawn_symbol_enemies);
 
         register_exploring_all_systems(app);
     }
@@ -208,7 +222,10 @@ pub fn register_exploring_all_systems(app: &mut App) {
             sync_boat_with_player,
             camera_follow,
             check_tile_action_system,
-            check_encounter_system,
+            check_encounter_system.run_if(random_encounter_enabled),
+            (move_symbol_enemies_system, spawn_symbol_enemies_system)
+                .chain()
+                .run_if(symbol_encounter_enabled),
         )
             .chain()
             .run_if(in_state(SceneState::Exploring).and(in_state(BattleState::None))),
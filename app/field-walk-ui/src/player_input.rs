@@ -8,6 +8,8 @@ use crate::{
     MovementBlockedEvent, MovementLocked, MovementState, PendingMove, PlayerMovedEvent,
 };
 use crate::FieldMenuOpen;
+use crate::party_has_door_key;
+use party_state::PartyState;
 
 use crate::map_mode::MapModeState;
 
@@ -20,6 +22,7 @@ pub fn player_movement(
     active_map: Res<ActiveMap>,
     map_mode_state: Res<MapModeState>,
     field_menu_open: Option<Res<FieldMenuOpen>>,
+    party_state: Option<Res<PartyState>>,
     mut move_state: ResMut<MovementState>,
     mut query: Query<
         (
@@ -80,12 +83,11 @@ pub fn player_movement(
     // 通常移動（徒歩 or 船）
     if let ExecuteMoveResult::Success = execute_move(
         &mut commands, entity, &mut tile_pos, input.first_dx, input.first_dy,
-        &active_map, on_boat, &mut boat_query,
+        &active_map, on_boat, party_has_door_key(party_state.as_deref()), &mut boat_query,
         &mut moved_events, &mut blocked_events,
-    ) {
-        if let Some((dx2, dy2)) = input.pending_direction {
-            commands.entity(entity).insert(PendingMove { direction: (dx2, dy2) });
-        }
+    ) && let Some((dx2, dy2)) = input.pending_direction
+    {
+        commands.entity(entity).insert(PendingMove { direction: (dx2, dy2) });
     }
 }
 
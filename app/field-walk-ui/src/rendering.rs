@@ -47,6 +47,8 @@ pub struct TileTextures {
     pub cave_floor: Handle<Image>,
     pub warp_zone: Handle<Image>,
     pub ladder: Handle<Image>,
+    pub stairs_down: Handle<Image>,
+    pub locked_door: Handle<Image>,
     pub chest: Handle<Image>,
     pub chest_open: Handle<Image>,
     pub boss_cave: Handle<Image>,
@@ -79,6 +81,8 @@ pub fn load_tile_textures(asset_server: &AssetServer) -> TileTextures {
         cave_floor: asset_server.load("tiles/cave_floor.png"),
         warp_zone: asset_server.load("tiles/warp_zone.png"),
         ladder: asset_server.load("tiles/ladder.png"),
+        stairs_down: asset_server.load("tiles/stairs_down.png"),
+        locked_door: asset_server.load("tiles/locked_door.png"),
         chest: asset_server.load("tiles/chest.png"),
         chest_open: asset_server.load("tiles/chest_open.png"),
         boss_cave: asset_server.load("tiles/boss_cave.png"),
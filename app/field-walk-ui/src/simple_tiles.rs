@@ -140,6 +140,8 @@ pub fn update_simple_tiles(
         // 構造物があればオーバーレイとして上に描画
         let structure_texture = match structure {
             Structure::Ladder => Some(tile_textures.ladder.clone()),
+            Structure::StairsDown => Some(tile_textures.stairs_down.clone()),
+            Structure::LockedDoor => Some(tile_textures.locked_door.clone()),
             Structure::WarpZone => Some(tile_textures.warp_zone.clone()),
             Structure::Chest => Some(tile_textures.chest.clone()),
             Structure::ChestOpen => Some(tile_textures.chest_open.clone()),
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use enemy::{generate_floor_enemy_group, Enemy};
+use field_core::{ActiveMap, OnBoat, Player, TilePosition, TILE_SIZE};
+use field_walk::{step_symbol, wrapped_delta};
+use progress_state::SymbolBattlePending;
+use scene_state::BattleState;
+use terrain::Structure;
+use world_state::{EncounterZone, GameRng};
+
+use crate::TileEnteredEvent;
+
+/// 同時に出現するシンボル敵の上限
+pub const MAX_SYMBOL_ENEMIES: usize = 4;
+
+/// 1歩ごとにシンボル敵が現れる確率（上限に達していないとき）
+const SYMBOL_SPAWN_CHANCE: f64 = 0.35;
+
+/// 出現位置のプレイヤーからの距離（チェビシェフ距離）
+const SYMBOL_SPAWN_MIN_DISTANCE: i32 = 5;
+const SYMBOL_SPAWN_MAX_DISTANCE: i32 = 8;
+
+/// 出現位置を探す試行回数
+const SYMBOL_SPAWN_ATTEMPTS: usize = 10;
+
+/// これより離れたシンボル敵は消える（チェビシェフ距離）
+const SYMBOL_DESPAWN_DISTANCE: i32 = 12;
+
+/// シンボル敵の描画の重なり順（地形より上、プレイヤーより下）
+const SYMBOL_Z: f32 = 0.5;
+
+/// エンカウントの方式
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncounterMode {
+    /// 歩くたびに地形のエンカウント率で抽選する
+    #[default]
+    Random,
+    /// マップ上を動くシンボル敵に触れると戦闘になる
+    Symbol,
+}
+
+/// 洞窟内のエンカウントの方式（既定はシンボル。敵が見えるので避けて進める）
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaveEncounterMode(pub EncounterMode);
+
+impl Default for CaveEncounterMode {
+    fn default() -> Self {
+        Self(EncounterMode::Symbol)
+    }
+}
+
+/// ランダムエンカウントが有効か判定する run_if 条件
+pub fn random_encounter_enabled(mode: Option<Res<EncounterMode>>) -> bool {
+    mode.is_none_or(|m| *m == EncounterMode::Random)
+}
+
+/// シンボルエンカウントが有効か判定する run_if 条件
+pub fn symbol_encounter_enabled(mode: Option<Res<EncounterMode>>) -> bool {
+    mode.is_some_and(|m| *m == EncounterMode::Symbol)
+}
+
+/// 洞窟内でランダムエンカウントが有効か判定する run_if 条件
+pub fn cave_random_encounter_enabled(mode: Option<Res<CaveEncounterMode>>) -> bool {
+    mode.is_some_and(|m| m.0 == EncounterMode::Random)
+}
+
+/// 洞窟内でシンボルエンカウントが有効か判定する run_if 条件
+pub fn cave_symbol_encounter_enabled(mode: Option<Res<CaveEncounterMode>>) -> bool {
+    mode.is_none_or(|m| m.0 == EncounterMode::Symbol)
+}
+
+/// マップ上を歩き回る敵シンボル（出現時に決めた敵グループを持つ）
+#[derive(Component)]
+pub struct SymbolEnemy {
+    pub enemies: Vec<Enemy>,
+}
+
+/// プレイヤーが1歩進むたびにシンボル敵を動かし、接触したら戦闘を始めるシステム
+///
+/// プレイヤーが踏み込んだ場合も、シンボル敵が追いついた場合も接触になる。
+#[allow(clippy::type_complexity)]
+pub fn move_symbol_enemies_system(
+    mut commands: Commands,
+    mut events: MessageReader<TileEnteredEvent>,
+    player_query: Query<(&TilePosition, Option<&OnBoat>), With<Player>>,
+    mut symbol_query: Query<(Entity, &mut TilePosition, &mut Transform, &SymbolEnemy), Without<Player>>,
+    active_map: Res<ActiveMap>,
+    mut game_rng: ResMut<GameRng>,
+    mut next_state: ResMut<NextState<BattleState>>,
+) {
+    for _event in events.read() {
+        let Ok((player_pos, on_boat)) = player_query.single() else {
+            continue;
+        };
+        if on_boat.is_some() {
+            continue;
+        }
+        let player = (player_pos.x, player_pos.y);
+        // シンボル敵どうしは重ならない（動いた敵の位置は順に更新する）
+        let mut occupied: Vec<(usize, usize)> = symbol_query.iter().map(|(_, pos, ..)| (pos.x, pos.y)).collect();
+
+        for (i, (entity, mut pos, mut transform, symbol)) in symbol_query.iter_mut().enumerate() {
+            if (pos.x, pos.y) != player {
+                let (new_x, new_y) = step_symbol(
+                    &active_map.grid,
+                    &active_map.structures,
+                    active_map.width,
+                    active_map.height,
+                    active_map.wraps,
+                    (pos.x, pos.y),
+                    player,
+                    game_rng.encounter.gen_range(0.0..1.0),
+                    &occupied,
+                );
+                pos.x = new_x;
+                pos.y = new_y;
+                occupied[i] = (new_x, new_y);
+            }
+            place_symbol(&mut transform, &active_map, (pos.x, pos.y), player);
+
+            if (pos.x, pos.y) == player {
+                commands.insert_resource(SymbolBattlePending {
+                    enemies: symbol.enemies.clone(),
+                });
+                commands.entity(entity).despawn();
+                next_state.set(BattleState::Active);
+                return;
+            }
+        }
+    }
+}
+
+/// プレイヤーの周囲にシンボル敵を出現させ、遠く離れたものを消すシステム
+///
+/// 敵グループは出現時に現在のエンカウントゾーンから決める。
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn spawn_symbol_enemies_system(
+    mut commands: Commands,
+    mut events: MessageReader<TileEnteredEvent>,
+    player_query: Query<&TilePosition, With<Player>>,
+    symbol_query: Query<(Entity, &TilePosition), (With<SymbolEnemy>, Without<Player>)>,
+    active_map: Res<ActiveMap>,
+    encounter_zone: Option<Res<EncounterZone>>,
+    mut game_rng: ResMut<GameRng>,
+    asset_server: Option<Res<AssetServer>>,
+) {
+    for _event in events.read() {
+        let Ok(player_pos) = player_query.single() else {
+            continue;
+        };
+        let player = (player_pos.x, player_pos.y);
+
+        let mut occupied = Vec::new();
+        for (entity, pos) in &symbol_query {
+            if chebyshev_distance(&active_map, (pos.x, pos.y), player) > SYMBOL_DESPAWN_DISTANCE {
+                commands.entity(entity).despawn();
+            } else {
+                occupied.push((pos.x, pos.y));
+            }
+        }
+
+        if occupied.len() >= MAX_SYMBOL_ENEMIES || !game_rng.encounter.gen_bool(SYMBOL_SPAWN_CHANCE) {
+            continue;
+        }
+        let Some(spawn) = find_symbol_spawn(&active_map, player, &occupied, &mut game_rng) else {
+            continue;
+        };
+
+        let default_zone = EncounterZone::default();
+        let zone = encounter_zone.as_deref().unwrap_or(&default_zone);
+        let enemies = generate_floor_enemy_group(
+            zone.continent_id,
+            zone.is_cave,
+            zone.floor,
+            game_rng.encounter.gen_range(0.0..1.0),
+            game_rng.encounter.gen_range(0.0..1.0),
+        );
+
+        let mut transform = Transform::from_xyz(0.0, 0.0, SYMBOL_Z).with_scale(Vec3::splat(TILE_SIZE / 16.0));
+        place_symbol(&mut transform, &active_map, spawn, player);
+        // 先頭の敵の姿で表示する
+        let sprite_path = enemies[0].kind.sprite_path();
+        let mut entity = commands.spawn((
+            SymbolEnemy { enemies },
+            TilePosition { x: spawn.0, y: spawn.1 },
+            transform,
+        ));
+        if let Some(asset_server) = &asset_server {
+            entity.insert(Sprite::from_image(asset_server.load(sprite_path)));
+        }
+    }
+}
+
+/// すべてのシンボル敵を消すシステム（マップを離れるとき用）
+pub fn despawn_symbol_enemies(mut commands: Commands, symbol_query: Query<Entity, With<SymbolEnemy>>) {
+    for entity in &symbol_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// プレイヤーから見た位置にシンボル敵を描画する（ループするマップでは近い側に置く）
+fn place_symbol(transform: &mut Transform, active_map: &ActiveMap, pos: (usize, usize), player: (usize, usize)) {
+    let dx = wrapped_delta(player.0, pos.0, active_map.width, active_map.wraps);
+    let dy = wrapped_delta(player.1, pos.1, active_map.height, active_map.wraps);
+    let (world_x, world_y) = active_map.to_world_logical(player.0 as i32 + dx, player.1 as i32 + dy);
+    transform.translation.x = world_x;
+    transform.translation.y = world_y;
+}
+
+fn chebyshev_distance(active_map: &ActiveMap, a: (usize, usize), b: (usize, usize)) -> i32 {
+    let dx = wrapped_delta(a.0, b.0, active_map.width, active_map.wraps);
+    let dy = wrapped_delta(a.1, b.1, active_map.height, active_map.wraps);
+    dx.abs().max(dy.abs())
+}
+
+/// プレイヤーから少し離れた、敵が出る地形の空きタイル（構造物のないタイル）を探す
+fn find_symbol_spawn(
+    active_map: &ActiveMap,
+    player: (usize, usize),
+    occupied: &[(usize, usize)],
+    game_rng: &mut GameRng,
+) -> Option<(usize, usize)> {
+    let max = SYMBOL_SPAWN_MAX_DISTANCE;
+    for _ in 0..SYMBOL_SPAWN_ATTEMPTS {
+        let dx = game_rng.encounter.gen_range(-max..=max);
+        let dy = game_rng.encounter.gen_range(-max..=max);
+        if dx.abs().max(dy.abs()) < SYMBOL_SPAWN_MIN_DISTANCE {
+            continue;
+        }
+        let Some((x, y)) = offset_position(active_map, player, dx, dy) else {
+            continue;
+        };
+        if active_map.terrain_at(x, y).is_walkable()
+            && active_map.structure_at(x, y) == Structure::None
+            && active_map.encounter_rate_at(x, y) > 0.0
+            && !occupied.contains(&(x, y))
+        {
+            return Some((x, y));
+        }
+    }
+    None
+}
+
+fn offset_position(active_map: &ActiveMap, pos: (usize, usize), dx: i32, dy: i32) -> Option<(usize, usize)> {
+    if active_map.wraps {
+        let x = (pos.0 as i32 + dx).rem_euclid(active_map.width as i32) as usize;
+        let y = (pos.1 as i32 + dy).rem_euclid(active_map.height as i32) as usize;
+        return Some((x, y));
+    }
+    terrain::bounded_offset(pos.0, pos.1, dx, dy, active_map.width, active_map.height)
+}
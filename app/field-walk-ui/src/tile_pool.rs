@@ -118,6 +118,8 @@ fn get_structure_texture(structure: Structure, textures: &TileTextures) -> Optio
         Structure::BossCave => Some(textures.boss_cave.clone()),
         Structure::Hokora => Some(textures.hokora.clone()),
         Structure::Ladder => Some(textures.ladder.clone()),
+        Structure::StairsDown => Some(textures.stairs_down.clone()),
+        Structure::LockedDoor => Some(textures.locked_door.clone()),
         Structure::WarpZone => Some(textures.warp_zone.clone()),
         Structure::Chest => Some(textures.chest.clone()),
         Structure::ChestOpen => Some(textures.chest_open.clone()),
@@ -87,6 +87,15 @@ impl ExplorationMap {
             })
     }
 
+    /// 指定座標を探索済みにする（セーブデータからの復元用）
+    ///
+    /// 範囲外の座標は無視する
+    pub fn mark_explored(&mut self, x: usize, y: usize) {
+        if y < self.height && x < self.width && self.tiles[y][x] == TileVisibility::Unexplored {
+            self.tiles[y][x] = TileVisibility::Explored;
+        }
+    }
+
     /// マップの幅を取得
     pub fn width(&self) -> usize {
         self.width
@@ -322,4 +331,17 @@ mod tests {
         map.update_visibility(1, 75, 4);
         assert_eq!(count_visible(&map), 81);
     }
+
+    #[test]
+    fn test_mark_explored_does_not_downgrade_visible() {
+        let mut map = ExplorationMap::new(10, 10);
+        map.update_visibility(5, 5, 1);
+
+        map.mark_explored(5, 5);
+        map.mark_explored(0, 0);
+        map.mark_explored(100, 100);
+
+        assert_eq!(map.get(5, 5), Some(TileVisibility::Visible));
+        assert_eq!(map.get(0, 0), Some(TileVisibility::Explored));
+    }
 }
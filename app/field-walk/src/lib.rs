@@ -1,4 +1,6 @@
 pub mod exploration;
 pub mod movement;
+pub mod symbol;
 
 pub use movement::{resolve_field_move, FieldMoveResult};
+pub use symbol::{step_symbol, wrapped_delta, SYMBOL_CHASE_RADIUS};
@@ -17,6 +17,7 @@ pub enum FieldMoveResult {
 ///
 /// - `on_boat=true`: 海上移動を試行し、失敗なら下船（徒歩移動）を試行
 /// - `on_boat=false`: 徒歩移動のみ試行
+/// - `has_key`: カギのかかった扉を通れるか
 #[allow(clippy::too_many_arguments)]
 pub fn resolve_field_move(
     grid: &[Vec<Terrain>],
@@ -29,6 +30,7 @@ pub fn resolve_field_move(
     dx: i32,
     dy: i32,
     on_boat: bool,
+    has_key: bool,
 ) -> FieldMoveResult {
     if on_boat {
         // 船モード: まず海上移動を試行
@@ -38,7 +40,7 @@ pub fn resolve_field_move(
             MoveResult::Moved { new_x, new_y } => FieldMoveResult::Sailed { new_x, new_y },
             MoveResult::Blocked => {
                 // 下船を試行（陸地への移動）
-                match try_walkable_move(grid, structures, width, height, wraps, x, y, dx, dy) {
+                match try_walkable_move(grid, structures, width, height, wraps, x, y, dx, dy, has_key) {
                     MoveResult::Moved { new_x, new_y } => {
                         FieldMoveResult::Disembarked { new_x, new_y }
                     }
@@ -48,14 +50,14 @@ pub fn resolve_field_move(
         }
     } else {
         // 徒歩移動
-        match try_walkable_move(grid, structures, width, height, wraps, x, y, dx, dy) {
+        match try_walkable_move(grid, structures, width, height, wraps, x, y, dx, dy, has_key) {
             MoveResult::Moved { new_x, new_y } => FieldMoveResult::Walked { new_x, new_y },
             MoveResult::Blocked => FieldMoveResult::Blocked,
         }
     }
 }
 
-/// 徒歩移動の試行（構造物があれば通行可能）
+/// 徒歩移動の試行（構造物があれば通行可能、扉はカギがあるときだけ）
 #[allow(clippy::too_many_arguments)]
 fn try_walkable_move(
     grid: &[Vec<Terrain>],
@@ -67,9 +69,12 @@ fn try_walkable_move(
     y: usize,
     dx: i32,
     dy: i32,
+    has_key: bool,
 ) -> MoveResult {
-    try_grid_move(x, y, dx, dy, grid, width, height, wraps, |nx, ny, t| {
-        structures[ny][nx] != Structure::None || t.is_walkable()
+    try_grid_move(x, y, dx, dy, grid, width, height, wraps, |nx, ny, t| match structures[ny][nx] {
+        Structure::LockedDoor => has_key,
+        Structure::None => t.is_walkable(),
+        _ => true,
     })
 }
 
@@ -95,7 +100,7 @@ mod tests {
         let structures = test_structures();
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, false,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, false, false,
         );
         assert_eq!(result, FieldMoveResult::Walked { new_x: 6, new_y: 5 });
     }
@@ -107,7 +112,7 @@ mod tests {
         let structures = test_structures();
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, false,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, false, false,
         );
         assert_eq!(result, FieldMoveResult::Blocked);
     }
@@ -121,7 +126,7 @@ mod tests {
         structures[5][6] = Structure::Town;
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, false,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, false, false,
         );
         assert_eq!(result, FieldMoveResult::Walked { new_x: 6, new_y: 5 });
     }
@@ -136,7 +141,7 @@ mod tests {
         let structures = test_structures();
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true, false,
         );
         assert_eq!(result, FieldMoveResult::Sailed { new_x: 6, new_y: 5 });
     }
@@ -148,7 +153,7 @@ mod tests {
         let structures = test_structures();
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true, false,
         );
         assert_eq!(
             result,
@@ -163,7 +168,7 @@ mod tests {
         let structures = test_structures();
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true, false,
         );
         assert_eq!(result, FieldMoveResult::Blocked);
     }
@@ -176,7 +181,7 @@ mod tests {
         structures[5][6] = Structure::Town;
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true, false,
         );
         // Sea is navigable, so this should be Sailed (navigable check passes first)
         assert_eq!(result, FieldMoveResult::Sailed { new_x: 6, new_y: 5 });
@@ -190,7 +195,7 @@ mod tests {
         structures[5][6] = Structure::Town;
 
         let result = resolve_field_move(
-            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true,
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, true, 5, 5, 1, 0, true, false,
         );
         // Plains is not navigable → try disembark → Plains + Structure is walkable
         assert_eq!(
@@ -209,7 +214,7 @@ mod tests {
         let structures = vec![vec![Structure::None; 5]; 5];
 
         let result =
-            resolve_field_move(&grid, &structures, 5, 5, false, 2, 2, 1, 0, false);
+            resolve_field_move(&grid, &structures, 5, 5, false, 2, 2, 1, 0, false, false);
         assert_eq!(result, FieldMoveResult::Walked { new_x: 3, new_y: 2 });
     }
 
@@ -219,7 +224,39 @@ mod tests {
         let structures = vec![vec![Structure::None; 5]; 5];
 
         let result =
-            resolve_field_move(&grid, &structures, 5, 5, false, 0, 0, -1, 0, false);
+            resolve_field_move(&grid, &structures, 5, 5, false, 0, 0, -1, 0, false, false);
         assert_eq!(result, FieldMoveResult::Blocked);
     }
+
+    // ============================================
+    // カギのかかった扉
+    // ============================================
+
+    #[test]
+    fn locked_door_blocks_without_key() {
+        let mut grid = create_test_grid(Terrain::CaveWall);
+        grid[5][5] = Terrain::CaveFloor;
+        grid[5][6] = Terrain::CaveFloor;
+        let mut structures = test_structures();
+        structures[5][6] = Structure::LockedDoor;
+
+        let result = resolve_field_move(
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, false, 5, 5, 1, 0, false, false,
+        );
+        assert_eq!(result, FieldMoveResult::Blocked);
+    }
+
+    #[test]
+    fn locked_door_opens_with_key() {
+        let mut grid = create_test_grid(Terrain::CaveWall);
+        grid[5][5] = Terrain::CaveFloor;
+        grid[5][6] = Terrain::CaveFloor;
+        let mut structures = test_structures();
+        structures[5][6] = Structure::LockedDoor;
+
+        let result = resolve_field_move(
+            &grid, &structures, MAP_WIDTH, MAP_HEIGHT, false, 5, 5, 1, 0, false, true,
+        );
+        assert_eq!(result, FieldMoveResult::Walked { new_x: 6, new_y: 5 });
+    }
 }
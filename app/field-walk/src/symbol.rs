@@ -0,0 +1,139 @@
+use terrain::{try_grid_move, MoveResult, Structure, Terrain};
+
+/// シンボル敵がプレイヤーを追いかけ始める距離（マンハッタン距離）
+pub const SYMBOL_CHASE_RADIUS: i32 = 4;
+
+/// うろつくときにその場にとどまる割合
+const WANDER_STAY_RATE: f32 = 0.4;
+
+/// `from` から `to` への差分（ループするマップでは近い側を選ぶ）
+pub fn wrapped_delta(from: usize, to: usize, size: usize, wraps: bool) -> i32 {
+    let delta = to as i32 - from as i32;
+    if !wraps {
+        return delta;
+    }
+    let size = size as i32;
+    if delta > size / 2 {
+        delta - size
+    } else if delta < -size / 2 {
+        delta + size
+    } else {
+        delta
+    }
+}
+
+/// シンボル敵の1歩を決める純粋関数
+///
+/// プレイヤーが追跡範囲内にいれば近づく方向へ進み、いなければ `wander_roll`（0.0〜1.0）で
+/// うろつく。シンボル敵は構造物のない徒歩可能なタイルしか歩けず、
+/// `occupied`（ほかのシンボル敵の位置）にも入らない。進めなければその場にとどまる。
+#[allow(clippy::too_many_arguments)]
+pub fn step_symbol(
+    grid: &[Vec<Terrain>],
+    structures: &[Vec<Structure>],
+    width: usize,
+    height: usize,
+    wraps: bool,
+    pos: (usize, usize),
+    player: (usize, usize),
+    wander_roll: f32,
+    occupied: &[(usize, usize)],
+) -> (usize, usize) {
+    let dx = wrapped_delta(pos.0, player.0, width, wraps);
+    let dy = wrapped_delta(pos.1, player.1, height, wraps);
+
+    let directions: Vec<(i32, i32)> = if dx.abs() + dy.abs() <= SYMBOL_CHASE_RADIUS {
+        // 離れている軸から先に詰める
+        let horizontal = (dx.signum(), 0);
+        let vertical = (0, dy.signum());
+        let ordered = if dx.abs() >= dy.abs() { [horizontal, vertical] } else { [vertical, horizontal] };
+        ordered.into_iter().filter(|&d| d != (0, 0)).collect()
+    } else if wander_roll < WANDER_STAY_RATE {
+        Vec::new()
+    } else {
+        let all = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+        let index = ((wander_roll - WANDER_STAY_RATE) / (1.0 - WANDER_STAY_RATE) * 4.0) as usize;
+        vec![all[index.min(3)]]
+    };
+
+    for (sx, sy) in directions {
+        if let MoveResult::Moved { new_x, new_y } =
+            try_grid_move(pos.0, pos.1, sx, sy, grid, width, height, wraps, |nx, ny, t| {
+                structures[ny][nx] == Structure::None && t.is_walkable() && !occupied.contains(&(nx, ny))
+            })
+        {
+            return (new_x, new_y);
+        }
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_cave(size: usize) -> (Vec<Vec<Terrain>>, Vec<Vec<Structure>>) {
+        let mut grid = vec![vec![Terrain::CaveWall; size]; size];
+        for row in grid.iter_mut().take(size - 1).skip(1) {
+            for tile in row.iter_mut().take(size - 1).skip(1) {
+                *tile = Terrain::CaveFloor;
+            }
+        }
+        (grid, vec![vec![Structure::None; size]; size])
+    }
+
+    #[test]
+    fn wrapped_delta_takes_shorter_way() {
+        assert_eq!(wrapped_delta(2, 98, 100, true), -4);
+        assert_eq!(wrapped_delta(98, 2, 100, true), 4);
+        assert_eq!(wrapped_delta(2, 98, 100, false), 96);
+    }
+
+    #[test]
+    fn chases_player_within_radius() {
+        let (grid, structures) = open_cave(12);
+        // 横に離れているので横に詰める
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (3, 5), (6, 6), 0.0, &[]), (4, 5));
+        // 縦に離れているので縦に詰める
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (5, 2), (5, 5), 0.0, &[]), (5, 3));
+        // 隣にいればプレイヤーのマスに踏み込む
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (5, 4), (5, 5), 0.0, &[]), (5, 5));
+    }
+
+    #[test]
+    fn chase_goes_around_walls() {
+        let (mut grid, structures) = open_cave(12);
+        grid[5][4] = Terrain::CaveWall;
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (3, 5), (6, 6), 0.0, &[]), (3, 6));
+    }
+
+    #[test]
+    fn wanders_when_player_is_far() {
+        let (grid, structures) = open_cave(20);
+        let far = (17, 17);
+        assert_eq!(step_symbol(&grid, &structures, 20, 20, false, (5, 5), far, 0.1, &[]), (5, 5));
+        let moved: Vec<_> = [0.45, 0.6, 0.75, 0.99]
+            .iter()
+            .map(|&roll| step_symbol(&grid, &structures, 20, 20, false, (5, 5), far, roll, &[]))
+            .collect();
+        assert_eq!(moved, vec![(6, 5), (4, 5), (5, 6), (5, 4)]);
+    }
+
+    #[test]
+    fn never_steps_onto_structures_or_walls() {
+        let (grid, mut structures) = open_cave(12);
+        structures[5][4] = Structure::Chest;
+        structures[6][3] = Structure::StairsDown;
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (3, 5), (6, 6), 0.0, &[]), (3, 5));
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (1, 1), (1, 0), 0.0, &[]), (1, 1));
+    }
+
+    #[test]
+    fn does_not_step_onto_other_symbols() {
+        let (grid, structures) = open_cave(12);
+        // 横がふさがっていれば縦に回り込む
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (3, 5), (6, 6), 0.0, &[(4, 5)]), (3, 6));
+        // どちらもふさがっていればとどまる
+        assert_eq!(step_symbol(&grid, &structures, 12, 12, false, (3, 5), (6, 6), 0.0, &[(4, 5), (3, 6)]), (3, 5));
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use input_ui::is_confirm_just_pressed;
+use progress_state::DefeatMode;
+use scene_state::SceneState;
+use world_state::WorldSeed;
+
+/// ゲームオーバー画面の入力処理システム
+///
+/// 決定キーで全滅後の処理を行い、フィールドへ戻る。
+pub fn game_over_input_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    defeat_mode: Res<DefeatMode>,
+    mut next_state: ResMut<NextState<SceneState>>,
+) {
+    if !is_confirm_just_pressed(&keyboard) {
+        return;
+    }
+
+    match *defeat_mode {
+        DefeatMode::RunEnds => {
+            commands.queue(|world: &mut World| {
+                // 次の冒険のシードも今のシードから決まる（同じシードなら同じ展開を再現できる）
+                let seed = world
+                    .get_resource::<WorldSeed>()
+                    .map_or_else(|| WorldSeed(rand::random()), WorldSeed::next_run);
+                if let Err(e) = save_state::start_new_run(world, seed) {
+                    warn!("new run failed: {}", e);
+                }
+            });
+        }
+        DefeatMode::ReturnToTown => {
+            commands.queue(|world: &mut World| {
+                if let Err(e) = save_state::return_to_last_town(world) {
+                    warn!("return to town failed: {}", e);
+                }
+            });
+        }
+    }
+    next_state.set(SceneState::Exploring);
+}
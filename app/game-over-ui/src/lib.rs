@@ -0,0 +1,23 @@
+mod input;
+mod scene;
+
+use bevy::prelude::*;
+use scene_state::SceneState;
+use progress_state::DefeatMode;
+
+pub use input::game_over_input_system;
+pub use scene::{cleanup_game_over_scene, setup_game_over_scene, GameOverSceneRoot};
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DefeatMode>()
+            .add_systems(OnEnter(SceneState::GameOver), setup_game_over_scene)
+            .add_systems(
+                Update,
+                game_over_input_system.run_if(in_state(SceneState::GameOver)),
+            )
+            .add_systems(OnExit(SceneState::GameOver), cleanup_game_over_scene);
+    }
+}
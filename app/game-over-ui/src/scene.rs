@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use progress_state::DefeatMode;
+use hud_ui::menu_style::FONT_PATH;
+
+/// ゲームオーバー画面のルートUIエンティティを識別するマーカー
+#[derive(Component)]
+pub struct GameOverSceneRoot;
+
+/// 全滅後の扱いに応じた案内文
+fn guide_message(mode: DefeatMode) -> &'static str {
+    match mode {
+        DefeatMode::RunEnds => "ぼうけんは ここで おわってしまった…\nあたらしい せかいで たびだとう",
+        DefeatMode::ReturnToTown => "さいごに たちよった まちへ もどされる\nゴールドが はんぶんに なった",
+    }
+}
+
+pub fn setup_game_over_scene(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    defeat_mode: Res<DefeatMode>,
+) {
+    let font: Handle<Font> = asset_server.load(FONT_PATH);
+
+    commands
+        .spawn((
+            GameOverSceneRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(32.0),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK),
+            GlobalZIndex(100),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("ぜんめつ してしまった…"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.2, 0.2)),
+            ));
+            parent.spawn((
+                Text::new(guide_message(*defeat_mode)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+pub fn cleanup_game_over_scene(
+    mut commands: Commands,
+    query: Query<Entity, With<GameOverSceneRoot>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,4 +1,5 @@
 pub mod command_menu;
+pub mod lineup;
 pub mod menu_style;
 
 use bevy::prelude::*;
@@ -50,6 +51,10 @@ fn hp_bar_color(ratio: f32) -> Color {
 
 /// HUDをspawnするシステム
 pub fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>, party_state: Res<PartyState>) {
+    spawn_hud(&mut commands, &asset_server, &party_state);
+}
+
+fn spawn_hud(commands: &mut Commands, asset_server: &AssetServer, party_state: &PartyState) {
     let font: Handle<Font> = asset_server.load("fonts/NotoSansJP-Bold.ttf");
     let panel_bg = Color::srgba(0.1, 0.1, 0.15, 0.6);
     let hp_bar_bg = Color::srgb(0.2, 0.2, 0.2);
@@ -75,7 +80,7 @@ pub fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>, party_s
         .with_children(|panel| {
             for (i, member) in party_state.members.iter().enumerate() {
                 let name = member.kind.name();
-                let ratio = member.stats.hp as f32 / member.stats.max_hp as f32;
+                let ratio = member.stats.hp as f32 / member.effective_max_hp() as f32;
 
                 panel
                     .spawn(Node {
@@ -107,7 +112,7 @@ pub fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>, party_s
                             // HPテキスト
                             row.spawn((
                                 HudHpText { index: i },
-                                Text::new(format!("{}/{}", member.stats.hp, member.stats.max_hp)),
+                                Text::new(format!("{}/{}", member.stats.hp, member.effective_max_hp())),
                                 TextFont {
                                     font: font.clone(),
                                     font_size: 13.0,
@@ -119,7 +124,7 @@ pub fn setup_hud(mut commands: Commands, asset_server: Res<AssetServer>, party_s
                             // MPテキスト
                             row.spawn((
                                 HudMpText { index: i },
-                                Text::new(format!("{}/{}", member.stats.mp, member.stats.max_mp)),
+                                Text::new(format!("{}/{}", member.stats.mp, member.effective_max_mp())),
                                 TextFont {
                                     font: font.clone(),
                                     font_size: 13.0,
@@ -190,19 +195,19 @@ pub fn update_hud(
 
     for (hp_text, mut text) in &mut hp_query {
         if let Some(member) = party_state.members.get(hp_text.index) {
-            **text = format!("{}/{}", member.stats.hp, member.stats.max_hp);
+            **text = format!("{}/{}", member.stats.hp, member.effective_max_hp());
         }
     }
 
     for (mp_text, mut text) in &mut mp_query {
         if let Some(member) = party_state.members.get(mp_text.index) {
-            **text = format!("{}/{}", member.stats.mp, member.stats.max_mp);
+            **text = format!("{}/{}", member.stats.mp, member.effective_max_mp());
         }
     }
 
     for (bar, mut node, mut bg) in &mut bar_query {
         if let Some(member) = party_state.members.get(bar.index) {
-            let ratio = member.stats.hp as f32 / member.stats.max_hp as f32;
+            let ratio = member.stats.hp as f32 / member.effective_max_hp() as f32;
             node.width = Val::Percent(ratio * 100.0);
             *bg = BackgroundColor(hp_bar_color(ratio));
         }
@@ -213,6 +218,23 @@ pub fn update_hud(
     }
 }
 
+/// 戦闘メンバーの人数が変わったらHUDを作り直すシステム（いれかえ・加入時）
+pub fn rebuild_hud_on_lineup_change(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    party_state: Res<PartyState>,
+    root_query: Query<Entity, With<HudRoot>>,
+    name_query: Query<(), With<HudNameText>>,
+) {
+    if !party_state.is_changed() || root_query.is_empty() || name_query.iter().len() == party_state.members.len() {
+        return;
+    }
+    for entity in &root_query {
+        commands.entity(entity).despawn();
+    }
+    spawn_hud(&mut commands, &asset_server, &party_state);
+}
+
 /// HUDをdespawnするシステム
 pub fn cleanup_hud(mut commands: Commands, query: Query<Entity, With<HudRoot>>) {
     for entity in &query {
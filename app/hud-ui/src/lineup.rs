@@ -0,0 +1,58 @@
+use party::PartyMember;
+use party_state::{PartyState, ACTIVE_PARTY_MAX};
+
+/// いれかえで選べる枠の数（戦闘メンバー + 満員でなければ空き枠1つ）
+pub fn slot_count(party_state: &PartyState) -> usize {
+    (party_state.members.len() + 1).min(ACTIVE_PARTY_MAX)
+}
+
+/// 枠選択のラベル一覧（戦闘メンバー + 空き枠）
+pub fn slot_labels(party_state: &PartyState) -> Vec<String> {
+    let mut labels: Vec<String> = party_state.members.iter().map(member_label).collect();
+    if labels.len() < slot_count(party_state) {
+        labels.push("（あき）".to_string());
+    }
+    labels
+}
+
+/// ひかえ選択のラベル一覧と選べない項目（空き枠以外は「ひかえに まわす」を付ける）
+pub fn reserve_labels(party_state: &PartyState, slot: usize) -> (Vec<String>, Vec<usize>) {
+    let mut labels: Vec<String> = party_state.reserve.iter().map(member_label).collect();
+    let mut disabled = Vec::new();
+    if slot < party_state.members.len() {
+        // 最後の1人はひかえに回せない
+        if party_state.members.len() <= 1 {
+            disabled.push(labels.len());
+        }
+        labels.push("ひかえに まわす".to_string());
+    }
+    (labels, disabled)
+}
+
+/// ひかえ選択のヘッダ
+pub fn reserve_prompt(party_state: &PartyState, slot: usize) -> String {
+    match party_state.members.get(slot) {
+        Some(m) => format!("{}の かわりに だれを いれる？", m.kind.name()),
+        None => "だれを くわえる？".to_string(),
+    }
+}
+
+/// ひかえ選択で決定した項目でいれかえを実行し、結果のメッセージを返す
+pub fn apply_choice(party_state: &mut PartyState, slot: usize, selected: usize) -> String {
+    let reserve_index = (selected < party_state.reserve.len()).then_some(selected);
+    match party_state.change_lineup(slot, reserve_index) {
+        Some(change) => change.message(),
+        None => "その いれかえは できない".to_string(),
+    }
+}
+
+/// いれかえ画面でのメンバー表示
+fn member_label(member: &PartyMember) -> String {
+    format!(
+        "{} Lv{}  HP:{}/{}",
+        member.kind.name(),
+        member.level,
+        member.stats.hp,
+        member.effective_max_hp()
+    )
+}
@@ -1,9 +1,18 @@
-use item::{ItemEffect, ItemEntry, ItemLookup};
+use item::{EquipSlot, GearClass, ItemEffect, ItemEntry, ItemLookup};
+use spell::Ailment;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ItemKey {
     Herb,
     HighHerb,
+    LifeLeaf,
+    Antidote,
+    MoonHerb,
+    EyeDrops,
+    ThroatDrop,
+    SmellingSalts,
+    SmokeBall,
     CopperKey,
     MoonFragment,
     MagicStone,
@@ -15,6 +24,10 @@ pub enum ItemKey {
     SteelSword,
     MageStaff,
     HolyStaff,
+    LeatherArmor,
+    ChainMail,
+    LeatherShield,
+    SwiftRing,
 }
 
 pub const HERB: ItemEntry<ItemKey> = ItemEntry {
@@ -25,6 +38,12 @@ pub const HERB: ItemEntry<ItemKey> = ItemEntry {
     price: 8,
     sell_price: 4,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const HIGH_HERB: ItemEntry<ItemKey> = ItemEntry {
@@ -35,6 +54,124 @@ pub const HIGH_HERB: ItemEntry<ItemKey> = ItemEntry {
     price: 24,
     sell_price: 12,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const LIFE_LEAF: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::LifeLeaf,
+    name: "いのちのは",
+    effect: ItemEffect::Revive { hp_percent: 50 },
+    description: "たおれた なかまを いきかえらせる は",
+    price: 120,
+    sell_price: 60,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const ANTIDOTE: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::Antidote,
+    name: "どくけしそう",
+    effect: ItemEffect::Cure { ailments: &[Ailment::Poison] },
+    description: "どくを けす くすりぐさ",
+    price: 10,
+    sell_price: 5,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const MOON_HERB: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::MoonHerb,
+    name: "まんげつそう",
+    effect: ItemEffect::Cure { ailments: &[Ailment::Paralysis] },
+    description: "からだの しびれを とる くすりぐさ",
+    price: 30,
+    sell_price: 15,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const EYE_DROPS: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::EyeDrops,
+    name: "めぐすり",
+    effect: ItemEffect::Cure { ailments: &[Ailment::Blind] },
+    description: "くらやみを はらう めぐすり",
+    price: 15,
+    sell_price: 7,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const THROAT_DROP: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::ThroatDrop,
+    name: "のどあめ",
+    effect: ItemEffect::Cure { ailments: &[Ailment::Silence] },
+    description: "こえが でるように なる あめ",
+    price: 15,
+    sell_price: 7,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const SMELLING_SALTS: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::SmellingSalts,
+    name: "きつけぐすり",
+    effect: ItemEffect::Cure { ailments: &[Ailment::Sleep, Ailment::Confusion] },
+    description: "ねむりや こんらんから めを さます",
+    price: 20,
+    sell_price: 10,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
+};
+
+pub const SMOKE_BALL: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::SmokeBall,
+    name: "けむりだま",
+    effect: ItemEffect::Escape,
+    description: "けむりに まぎれて かならず にげられる",
+    price: 30,
+    sell_price: 15,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const COPPER_KEY: ItemEntry<ItemKey> = ItemEntry {
@@ -42,9 +179,15 @@ pub const COPPER_KEY: ItemEntry<ItemKey> = ItemEntry {
     name: "どうのカギ",
     effect: ItemEffect::KeyItem,
     description: "どこかの とびらを あけるカギ",
-    price: 0,
+    price: 80,
     sell_price: 0,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const MOON_FRAGMENT: ItemEntry<ItemKey> = ItemEntry {
@@ -55,6 +198,12 @@ pub const MOON_FRAGMENT: ItemEntry<ItemKey> = ItemEntry {
     price: 50,
     sell_price: 25,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const MAGIC_STONE: ItemEntry<ItemKey> = ItemEntry {
@@ -65,6 +214,12 @@ pub const MAGIC_STONE: ItemEntry<ItemKey> = ItemEntry {
     price: 0,
     sell_price: 30,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const SILVER_ORE: ItemEntry<ItemKey> = ItemEntry {
@@ -75,6 +230,12 @@ pub const SILVER_ORE: ItemEntry<ItemKey> = ItemEntry {
     price: 0,
     sell_price: 60,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const ANCIENT_COIN: ItemEntry<ItemKey> = ItemEntry {
@@ -85,6 +246,12 @@ pub const ANCIENT_COIN: ItemEntry<ItemKey> = ItemEntry {
     price: 0,
     sell_price: 120,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const DRAGON_SCALE: ItemEntry<ItemKey> = ItemEntry {
@@ -95,61 +262,168 @@ pub const DRAGON_SCALE: ItemEntry<ItemKey> = ItemEntry {
     price: 0,
     sell_price: 250,
     attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: None,
+    gear_class: None,
 };
 
 pub const WOODEN_SWORD: ItemEntry<ItemKey> = ItemEntry {
     key: ItemKey::WoodenSword,
     name: "きのつるぎ",
-    effect: ItemEffect::Material,
+    effect: ItemEffect::Equipment,
     description: "きで つくった つるぎ",
     price: 10,
     sell_price: 5,
     attack_bonus: 2,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Weapon),
+    gear_class: Some(GearClass::LightSword),
 };
 
 pub const IRON_SWORD: ItemEntry<ItemKey> = ItemEntry {
     key: ItemKey::IronSword,
     name: "てつのつるぎ",
-    effect: ItemEffect::Material,
+    effect: ItemEffect::Equipment,
     description: "てつで きたえた つるぎ",
     price: 50,
     sell_price: 25,
     attack_bonus: 5,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Weapon),
+    gear_class: Some(GearClass::Sword),
 };
 
 pub const STEEL_SWORD: ItemEntry<ItemKey> = ItemEntry {
     key: ItemKey::SteelSword,
     name: "はがねのつるぎ",
-    effect: ItemEffect::Material,
+    effect: ItemEffect::Equipment,
     description: "はがねの かたい つるぎ",
     price: 150,
     sell_price: 75,
     attack_bonus: 10,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Weapon),
+    gear_class: Some(GearClass::HeavySword),
 };
 
 pub const MAGE_STAFF: ItemEntry<ItemKey> = ItemEntry {
     key: ItemKey::MageStaff,
     name: "まどうしのつえ",
-    effect: ItemEffect::Material,
+    effect: ItemEffect::Equipment,
     description: "まりょくを たかめる つえ",
     price: 30,
     sell_price: 15,
     attack_bonus: 3,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 3,
+    equip_slot: Some(EquipSlot::Weapon),
+    gear_class: Some(GearClass::Staff),
 };
 
 pub const HOLY_STAFF: ItemEntry<ItemKey> = ItemEntry {
     key: ItemKey::HolyStaff,
     name: "せいなるつえ",
-    effect: ItemEffect::Material,
+    effect: ItemEffect::Equipment,
     description: "せいなる ちからの つえ",
     price: 80,
     sell_price: 40,
     attack_bonus: 4,
+    defense_bonus: 0,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Weapon),
+    gear_class: Some(GearClass::Staff),
+};
+
+pub const LEATHER_ARMOR: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::LeatherArmor,
+    name: "かわのよろい",
+    effect: ItemEffect::Equipment,
+    description: "なめした かわの よろい",
+    price: 40,
+    sell_price: 20,
+    attack_bonus: 0,
+    defense_bonus: 3,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Armor),
+    gear_class: Some(GearClass::LightArmor),
+};
+
+pub const CHAIN_MAIL: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::ChainMail,
+    name: "くさりかたびら",
+    effect: ItemEffect::Equipment,
+    description: "おもいが じょうぶな よろい",
+    price: 120,
+    sell_price: 60,
+    attack_bonus: 0,
+    defense_bonus: 6,
+    speed_bonus: -1,
+    max_hp_bonus: 5,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Armor),
+    gear_class: Some(GearClass::HeavyArmor),
+};
+
+pub const LEATHER_SHIELD: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::LeatherShield,
+    name: "かわのたて",
+    effect: ItemEffect::Equipment,
+    description: "かるくて あつかいやすい たて",
+    price: 30,
+    sell_price: 15,
+    attack_bonus: 0,
+    defense_bonus: 2,
+    speed_bonus: 0,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Shield),
+    gear_class: Some(GearClass::Shield),
+};
+
+pub const SWIFT_RING: ItemEntry<ItemKey> = ItemEntry {
+    key: ItemKey::SwiftRing,
+    name: "はやてのゆびわ",
+    effect: ItemEffect::Equipment,
+    description: "みのこなしが かるくなる ゆびわ",
+    price: 100,
+    sell_price: 50,
+    attack_bonus: 0,
+    defense_bonus: 0,
+    speed_bonus: 3,
+    max_hp_bonus: 0,
+    max_mp_bonus: 0,
+    equip_slot: Some(EquipSlot::Accessory),
+    gear_class: Some(GearClass::Accessory),
 };
 
 pub static ALL_ITEMS: &[ItemEntry<ItemKey>] = &[
     HERB,
     HIGH_HERB,
+    LIFE_LEAF,
+    ANTIDOTE,
+    MOON_HERB,
+    EYE_DROPS,
+    THROAT_DROP,
+    SMELLING_SALTS,
+    SMOKE_BALL,
     COPPER_KEY,
     MOON_FRAGMENT,
     MAGIC_STONE,
@@ -161,11 +435,22 @@ pub static ALL_ITEMS: &[ItemEntry<ItemKey>] = &[
     STEEL_SWORD,
     MAGE_STAFF,
     HOLY_STAFF,
+    LEATHER_ARMOR,
+    CHAIN_MAIL,
+    LEATHER_SHIELD,
+    SWIFT_RING,
 ];
 
 pub static ALL_ITEM_KEYS: &[ItemKey] = &[
     ItemKey::Herb,
     ItemKey::HighHerb,
+    ItemKey::LifeLeaf,
+    ItemKey::Antidote,
+    ItemKey::MoonHerb,
+    ItemKey::EyeDrops,
+    ItemKey::ThroatDrop,
+    ItemKey::SmellingSalts,
+    ItemKey::SmokeBall,
     ItemKey::CopperKey,
     ItemKey::MoonFragment,
     ItemKey::MagicStone,
@@ -177,11 +462,23 @@ pub static ALL_ITEM_KEYS: &[ItemKey] = &[
     ItemKey::SteelSword,
     ItemKey::MageStaff,
     ItemKey::HolyStaff,
+    ItemKey::LeatherArmor,
+    ItemKey::ChainMail,
+    ItemKey::LeatherShield,
+    ItemKey::SwiftRing,
 ];
 
 pub static SHOP_ITEMS: &[ItemKey] = &[
     ItemKey::Herb,
     ItemKey::HighHerb,
+    ItemKey::LifeLeaf,
+    ItemKey::Antidote,
+    ItemKey::MoonHerb,
+    ItemKey::EyeDrops,
+    ItemKey::ThroatDrop,
+    ItemKey::SmellingSalts,
+    ItemKey::SmokeBall,
+    ItemKey::CopperKey,
     ItemKey::MoonFragment,
 ];
 
@@ -191,11 +488,25 @@ pub static SHOP_WEAPONS: &[ItemKey] = &[
     ItemKey::MageStaff,
 ];
 
+pub static SHOP_ARMOR: &[ItemKey] = &[
+    ItemKey::LeatherArmor,
+    ItemKey::ChainMail,
+    ItemKey::LeatherShield,
+    ItemKey::SwiftRing,
+];
+
 impl ItemKey {
     pub const fn entry(self) -> ItemEntry<ItemKey> {
         match self {
             ItemKey::Herb => HERB,
             ItemKey::HighHerb => HIGH_HERB,
+            ItemKey::LifeLeaf => LIFE_LEAF,
+            ItemKey::Antidote => ANTIDOTE,
+            ItemKey::MoonHerb => MOON_HERB,
+            ItemKey::EyeDrops => EYE_DROPS,
+            ItemKey::ThroatDrop => THROAT_DROP,
+            ItemKey::SmellingSalts => SMELLING_SALTS,
+            ItemKey::SmokeBall => SMOKE_BALL,
             ItemKey::CopperKey => COPPER_KEY,
             ItemKey::MoonFragment => MOON_FRAGMENT,
             ItemKey::MagicStone => MAGIC_STONE,
@@ -207,6 +518,10 @@ impl ItemKey {
             ItemKey::SteelSword => STEEL_SWORD,
             ItemKey::MageStaff => MAGE_STAFF,
             ItemKey::HolyStaff => HOLY_STAFF,
+            ItemKey::LeatherArmor => LEATHER_ARMOR,
+            ItemKey::ChainMail => CHAIN_MAIL,
+            ItemKey::LeatherShield => LEATHER_SHIELD,
+            ItemKey::SwiftRing => SWIFT_RING,
         }
     }
 
@@ -215,7 +530,11 @@ impl ItemKey {
     }
 
     pub const fn is_weapon(self) -> bool {
-        self.entry().attack_bonus > 0
+        self.entry().is_weapon()
+    }
+
+    pub const fn equip_slot(self) -> Option<EquipSlot> {
+        self.entry().equip_slot
     }
 }
 
@@ -1,37 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::item::{EquipSlot, ItemEntry};
+
 /// 装備スロット
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Equipment<K: Copy> {
     pub weapon: Option<K>,
+    pub armor: Option<K>,
+    pub shield: Option<K>,
+    pub accessory: Option<K>,
 }
 
 impl<K: Copy> Default for Equipment<K> {
     fn default() -> Self {
-        Self { weapon: None }
+        Self::new()
     }
 }
 
 impl<K: Copy> Equipment<K> {
     pub fn new() -> Self {
-        Self { weapon: None }
+        Self {
+            weapon: None,
+            armor: None,
+            shield: None,
+            accessory: None,
+        }
+    }
+
+    /// スロットに装備中のアイテム
+    pub fn get(&self, slot: EquipSlot) -> Option<K> {
+        match slot {
+            EquipSlot::Weapon => self.weapon,
+            EquipSlot::Armor => self.armor,
+            EquipSlot::Shield => self.shield,
+            EquipSlot::Accessory => self.accessory,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: EquipSlot) -> &mut Option<K> {
+        match slot {
+            EquipSlot::Weapon => &mut self.weapon,
+            EquipSlot::Armor => &mut self.armor,
+            EquipSlot::Shield => &mut self.shield,
+            EquipSlot::Accessory => &mut self.accessory,
+        }
+    }
+
+    /// スロットに装備し、以前の装備を返す
+    pub fn equip(&mut self, slot: EquipSlot, item: K) -> Option<K> {
+        self.slot_mut(slot).replace(item)
+    }
+
+    /// スロットの装備を外して返す
+    pub fn unequip(&mut self, slot: EquipSlot) -> Option<K> {
+        self.slot_mut(slot).take()
     }
 
     /// 武器を装備し、以前の武器を返す
     pub fn equip_weapon(&mut self, weapon: K) -> Option<K> {
-        self.weapon.replace(weapon)
+        self.equip(EquipSlot::Weapon, weapon)
+    }
+
+    /// 装備中のアイテム一覧（スロット順）
+    pub fn equipped_items(&self) -> Vec<K> {
+        EquipSlot::ALL.iter().filter_map(|&slot| self.get(slot)).collect()
     }
 }
 
 impl<K: crate::ItemLookup> Equipment<K> {
+    /// アイテムが入るスロットに今装備しているもの（装備品でなければNone）
+    pub fn in_slot_of(&self, item: K) -> Option<K> {
+        item.entry().equip_slot.and_then(|slot| self.get(slot))
+    }
+
+    /// アイテムを装備中か
+    pub fn is_equipped(&self, item: K) -> bool {
+        self.in_slot_of(item) == Some(item)
+    }
+
+    fn total_bonus(&self, bonus: impl Fn(&ItemEntry<K>) -> i32) -> i32 {
+        self.equipped_items().iter().map(|item| bonus(&item.entry())).sum()
+    }
+
     /// 装備による攻撃力ボーナス合計
     pub fn attack_bonus(&self) -> i32 {
-        self.weapon.map_or(0, |w| w.entry().attack_bonus)
+        self.total_bonus(|e| e.attack_bonus)
+    }
+
+    /// 装備による防御力ボーナス合計
+    pub fn defense_bonus(&self) -> i32 {
+        self.total_bonus(|e| e.defense_bonus)
+    }
+
+    /// 装備による素早さボーナス合計
+    pub fn speed_bonus(&self) -> i32 {
+        self.total_bonus(|e| e.speed_bonus)
+    }
+
+    /// 装備による最大HPボーナス合計
+    pub fn max_hp_bonus(&self) -> i32 {
+        self.total_bonus(|e| e.max_hp_bonus)
+    }
+
+    /// 装備による最大MPボーナス合計
+    pub fn max_mp_bonus(&self) -> i32 {
+        self.total_bonus(|e| e.max_mp_bonus)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::item::{ItemEffect, ItemEntry, ItemLookup};
+    use crate::item::{GearClass, ItemEffect, ItemEntry, ItemLookup};
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     enum TestWeapon {
@@ -45,20 +125,32 @@ mod tests {
                 TestWeapon::Sword => ItemEntry {
                     key: TestWeapon::Sword,
                     name: "sword",
-                    effect: ItemEffect::Material,
+                    effect: ItemEffect::Equipment,
                     description: "",
                     price: 10,
                     sell_price: 5,
                     attack_bonus: 5,
+                    defense_bonus: 0,
+                    speed_bonus: 0,
+                    max_hp_bonus: 0,
+                    max_mp_bonus: 0,
+                    equip_slot: Some(EquipSlot::Weapon),
+                    gear_class: Some(GearClass::Sword),
                 },
                 TestWeapon::Staff => ItemEntry {
                     key: TestWeapon::Staff,
                     name: "staff",
-                    effect: ItemEffect::Material,
+                    effect: ItemEffect::Equipment,
                     description: "",
                     price: 30,
                     sell_price: 15,
                     attack_bonus: 3,
+                    defense_bonus: 0,
+                    speed_bonus: 0,
+                    max_hp_bonus: 0,
+                    max_mp_bonus: 2,
+                    equip_slot: Some(EquipSlot::Weapon),
+                    gear_class: Some(GearClass::Staff),
                 },
             }
         }
@@ -76,4 +168,49 @@ mod tests {
         assert_eq!(prev, Some(TestWeapon::Sword));
         assert_eq!(eq.attack_bonus(), 3);
     }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum TestGear {
+        Mail,
+        Ring,
+    }
+
+    impl ItemLookup for TestGear {
+        fn entry(&self) -> ItemEntry<Self> {
+            let (slot, class, defense, speed, max_hp) = match self {
+                TestGear::Mail => (EquipSlot::Armor, GearClass::HeavyArmor, 4, -1, 0),
+                TestGear::Ring => (EquipSlot::Accessory, GearClass::Accessory, 0, 2, 5),
+            };
+            ItemEntry {
+                key: *self,
+                name: "gear",
+                effect: ItemEffect::Equipment,
+                description: "",
+                price: 10,
+                sell_price: 5,
+                attack_bonus: 0,
+                defense_bonus: defense,
+                speed_bonus: speed,
+                max_hp_bonus: max_hp,
+                max_mp_bonus: 0,
+                equip_slot: Some(slot),
+                gear_class: Some(class),
+            }
+        }
+    }
+
+    #[test]
+    fn bonuses_sum_across_slots() {
+        let mut eq = Equipment::new();
+        eq.equip(EquipSlot::Armor, TestGear::Mail);
+        eq.equip(EquipSlot::Accessory, TestGear::Ring);
+        assert_eq!(eq.defense_bonus(), 4);
+        assert_eq!(eq.speed_bonus(), 1);
+        assert_eq!(eq.max_hp_bonus(), 5);
+        assert!(eq.is_equipped(TestGear::Ring));
+
+        assert_eq!(eq.unequip(EquipSlot::Armor), Some(TestGear::Mail));
+        assert_eq!(eq.defense_bonus(), 0);
+        assert_eq!(eq.in_slot_of(TestGear::Mail), None);
+    }
 }
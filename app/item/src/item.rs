@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 
+use serde::{Deserialize, Serialize};
+
 pub const INVENTORY_CAPACITY: u32 = 6;
 pub const BAG_CAPACITY: u32 = 50;
 pub const BAG_MEMBER_INDEX: usize = usize::MAX;
@@ -10,10 +12,66 @@ pub const BAG_MEMBER_INDEX: usize = usize::MAX;
 pub enum ItemEffect {
     /// HP回復
     Heal { power: i32 },
+    /// 戦闘不能の味方を最大HPのhp_percent%で復活
+    Revive { hp_percent: i32 },
+    /// 状態異常を治す
+    Cure { ailments: &'static [spell::Ailment] },
+    /// 戦闘から必ず逃げる（ボス戦を除く）
+    Escape,
     /// キーアイテム（説明表示のみ、消費しない）
     KeyItem,
     /// 素材（売却専用、使用不可）
     Material,
+    /// 装備品（装備して使う、道具としては使えない）
+    Equipment,
+}
+
+/// 装備スロットの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EquipSlot {
+    Weapon,
+    Armor,
+    Shield,
+    Accessory,
+}
+
+impl EquipSlot {
+    pub const ALL: [EquipSlot; 4] = [
+        EquipSlot::Weapon,
+        EquipSlot::Armor,
+        EquipSlot::Shield,
+        EquipSlot::Accessory,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            EquipSlot::Weapon => "ぶき",
+            EquipSlot::Armor => "よろい",
+            EquipSlot::Shield => "たて",
+            EquipSlot::Accessory => "そうしょくひん",
+        }
+    }
+}
+
+/// 装備品の系統（キャラクターごとの装備可否はこの系統で決める）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GearClass {
+    /// 杖
+    Staff,
+    /// 軽い剣
+    LightSword,
+    /// 剣
+    Sword,
+    /// 重い剣
+    HeavySword,
+    /// 軽い鎧
+    LightArmor,
+    /// 重い鎧
+    HeavyArmor,
+    /// 盾
+    Shield,
+    /// 装飾品
+    Accessory,
 }
 
 /// アイテムデータからエントリを取得するトレイト
@@ -31,6 +89,14 @@ pub struct ItemEntry<K> {
     pub price: u32,
     pub sell_price: u32,
     pub attack_bonus: i32,
+    pub defense_bonus: i32,
+    pub speed_bonus: i32,
+    pub max_hp_bonus: i32,
+    pub max_mp_bonus: i32,
+    /// 装備品なら装備先のスロット
+    pub equip_slot: Option<EquipSlot>,
+    /// 装備品なら系統
+    pub gear_class: Option<GearClass>,
 }
 
 impl<K: Copy> ItemEntry<K> {
@@ -38,14 +104,33 @@ impl<K: Copy> ItemEntry<K> {
         self.key
     }
     pub const fn is_weapon(&self) -> bool {
-        self.attack_bonus > 0
+        matches!(self.equip_slot, Some(EquipSlot::Weapon))
+    }
+    pub const fn is_equipment(&self) -> bool {
+        self.equip_slot.is_some()
     }
     pub const fn is_consumable(&self) -> bool {
-        matches!(self.effect, ItemEffect::Heal { .. })
+        matches!(self.effect, ItemEffect::Heal { .. } | ItemEffect::Revive { .. } | ItemEffect::Cure { .. } | ItemEffect::Escape)
+    }
+
+    /// 装備ボーナスの表示用文字列（例: "DEF+6 SPD-1"）
+    pub fn bonus_label(&self) -> String {
+        [
+            ("ATK", self.attack_bonus),
+            ("DEF", self.defense_bonus),
+            ("SPD", self.speed_bonus),
+            ("HP", self.max_hp_bonus),
+            ("MP", self.max_mp_bonus),
+        ]
+        .iter()
+        .filter(|(_, value)| *value != 0)
+        .map(|(name, value)| format!("{}{:+}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Inventory<K: Eq + Hash> {
     items: HashMap<K, u32>,
     capacity: u32,
@@ -142,7 +227,26 @@ mod tests {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     enum TestItem {
         A,
-        B,
+    }
+
+    #[test]
+    fn bonus_label_lists_nonzero_bonuses() {
+        let entry = ItemEntry {
+            key: TestItem::A,
+            name: "テストよろい",
+            effect: ItemEffect::Equipment,
+            description: "",
+            price: 0,
+            sell_price: 0,
+            attack_bonus: 0,
+            defense_bonus: 6,
+            speed_bonus: -1,
+            max_hp_bonus: 5,
+            max_mp_bonus: 0,
+            equip_slot: Some(EquipSlot::Armor),
+            gear_class: Some(GearClass::LightArmor),
+        };
+        assert_eq!(entry.bonus_label(), "DEF+6 SPD-1 HP+5");
     }
 
     #[test]
@@ -3,6 +3,6 @@ pub mod item;
 
 pub use equipment::Equipment;
 pub use item::{
-    Inventory, ItemEffect, ItemEntry, ItemLookup, BAG_CAPACITY, BAG_MEMBER_INDEX,
+    EquipSlot, GearClass, Inventory, ItemEffect, ItemEntry, ItemLookup, BAG_CAPACITY, BAG_MEMBER_INDEX,
     INVENTORY_CAPACITY,
 };
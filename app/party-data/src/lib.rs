@@ -1,7 +1,39 @@
+use item::GearClass;
 use item_data::ItemKey;
 use party::{
-    CharacterEntry, CharacterParamTable, CombatStats, PartyMemberKind, RecruitmentPath, StatGrowth,
+    CharacterEntry, CharacterParamTable, CombatStats, EquipRule, PartyMemberKind, RecruitmentPath,
+    StatGrowth,
 };
+
+/// 杖と軽い鎧・装飾品（魔法使い向け）
+const STAFF_GEAR: &[GearClass] = &[
+    GearClass::Staff, GearClass::LightArmor, GearClass::Accessory,
+];
+
+/// 軽装（身軽さを活かすキャラ向け）
+const LIGHT_GEAR: &[GearClass] = &[
+    GearClass::LightSword, GearClass::Sword,
+    GearClass::LightArmor, GearClass::Shield, GearClass::Accessory,
+];
+
+/// 重装（力自慢のキャラ向け）
+const HEAVY_GEAR: &[GearClass] = &[
+    GearClass::Sword, GearClass::HeavySword,
+    GearClass::LightArmor, GearClass::HeavyArmor, GearClass::Shield, GearClass::Accessory,
+];
+
+/// 剣士（杖以外）
+const SWORD_GEAR: &[GearClass] = &[
+    GearClass::LightSword, GearClass::Sword, GearClass::HeavySword,
+    GearClass::LightArmor, GearClass::HeavyArmor, GearClass::Shield, GearClass::Accessory,
+];
+
+/// 僧侶（杖・軽い剣と軽い防具）
+const CLERIC_GEAR: &[GearClass] = &[
+    GearClass::LightSword, GearClass::Staff,
+    GearClass::LightArmor, GearClass::Shield, GearClass::Accessory,
+];
+
 pub fn character_param_table() -> CharacterParamTable {
     CharacterParamTable::from_fn(|kind| match kind {
         PartyMemberKind::Laios => CharacterEntry {
@@ -22,12 +54,14 @@ pub fn character_param_table() -> CharacterParamTable {
                 (1, spell_data::SLEEP1), (1, spell_data::SLEEPALL1),
                 (1, spell_data::POISON1), (1, spell_data::POISONALL1),
             ],
+            equip_rule: EquipRule::Any,
         },
         PartyMemberKind::Chilchuck => CharacterEntry {
             initial_stats: CombatStats::new(22, 6, 2, 9, 0),
             stat_growth: StatGrowth { hp: 3, mp: 0, attack: 2, defense: 1, speed: 2 },
             recruit_method: RecruitmentPath::GoldHire { cost: 200 },
             spell_learn_table: &[],
+            equip_rule: EquipRule::Only(LIGHT_GEAR),
         },
         PartyMemberKind::Marcille => CharacterEntry {
             initial_stats: CombatStats::new(20, 2, 2, 7, 15),
@@ -37,11 +71,17 @@ pub fn character_param_table() -> CharacterParamTable {
                 (1, spell_data::FIRE1),
                 (3, spell_data::BLAZE1),
                 (5, spell_data::FIRE2),
+                (6, spell_data::ICE1),
                 (7, spell_data::BLAZE2),
                 (8, spell_data::SLEEP1),
                 (9, spell_data::DRAIN1),
                 (10, spell_data::SLEEPALL1),
+                (11, spell_data::BLIZZARD1),
+                (12, spell_data::ICE2),
+                (13, spell_data::CONFUSE1),
+                (14, spell_data::WEAKENALL1),
             ],
+            equip_rule: EquipRule::Only(STAFF_GEAR),
         },
         PartyMemberKind::Senshi => CharacterEntry {
             initial_stats: CombatStats::new(40, 7, 6, 2, 3),
@@ -50,6 +90,7 @@ pub fn character_param_table() -> CharacterParamTable {
             spell_learn_table: &[
                 (4, spell_data::SHIELD1),
             ],
+            equip_rule: EquipRule::Only(HEAVY_GEAR),
         },
         PartyMemberKind::Falin => CharacterEntry {
             initial_stats: CombatStats::new(25, 5, 4, 4, 12),
@@ -57,12 +98,20 @@ pub fn character_param_table() -> CharacterParamTable {
             recruit_method: RecruitmentPath::TavernBond,
             spell_learn_table: &[
                 (1, spell_data::HEAL1),
+                (2, spell_data::CURE1),
                 (3, spell_data::HEALALL1),
+                (4, spell_data::HOLY1),
                 (5, spell_data::HEAL2),
+                (6, spell_data::REVIVE1),
                 (7, spell_data::SHIELD2),
+                (8, spell_data::HOLYALL1),
                 (9, spell_data::HEALALL2),
                 (10, spell_data::BARRIER2),
+                (11, spell_data::CUREALL1),
+                (12, spell_data::GUARD1),
+                (13, spell_data::FORTIFY1),
             ],
+            equip_rule: EquipRule::Only(CLERIC_GEAR),
         },
         PartyMemberKind::Izutsumi => CharacterEntry {
             initial_stats: CombatStats::new(20, 7, 1, 10, 3),
@@ -71,19 +120,23 @@ pub fn character_param_table() -> CharacterParamTable {
             spell_learn_table: &[
                 (5, spell_data::FIRE1),
                 (8, spell_data::BOOST1),
+                (10, spell_data::HASTE1),
             ],
+            equip_rule: EquipRule::Only(LIGHT_GEAR),
         },
         PartyMemberKind::Shuro => CharacterEntry {
             initial_stats: CombatStats::new(28, 10, 3, 7, 0),
             stat_growth: StatGrowth { hp: 4, mp: 0, attack: 3, defense: 1, speed: 1 },
             recruit_method: RecruitmentPath::TavernBond,
             spell_learn_table: &[],
+            equip_rule: EquipRule::Only(SWORD_GEAR),
         },
         PartyMemberKind::Namari => CharacterEntry {
             initial_stats: CombatStats::new(35, 6, 5, 3, 0),
             stat_growth: StatGrowth { hp: 5, mp: 0, attack: 2, defense: 2, speed: 0 },
             recruit_method: RecruitmentPath::GoldHire { cost: 200 },
             spell_learn_table: &[],
+            equip_rule: EquipRule::Only(HEAVY_GEAR),
         },
         PartyMemberKind::Kabru => CharacterEntry {
             initial_stats: CombatStats::new(26, 7, 3, 6, 5),
@@ -91,12 +144,16 @@ pub fn character_param_table() -> CharacterParamTable {
             recruit_method: RecruitmentPath::TavernBond,
             spell_learn_table: &[
                 (3, spell_data::HEAL1),
+                (4, spell_data::BLIND1),
                 (5, spell_data::SHIELD1),
                 (6, spell_data::SIPHON1),
                 (7, spell_data::BARRIER1),
                 (8, spell_data::POISONALL1),
                 (9, spell_data::RALLY1),
+                (10, spell_data::BREAK1),
+                (11, spell_data::WEAKEN1),
             ],
+            equip_rule: EquipRule::Any,
         },
         PartyMemberKind::Rinsha => CharacterEntry {
             initial_stats: CombatStats::new(24, 5, 3, 6, 8),
@@ -105,12 +162,17 @@ pub fn character_param_table() -> CharacterParamTable {
             spell_learn_table: &[
                 (1, spell_data::FIRE1),
                 (3, spell_data::HEAL1),
+                (4, spell_data::ICE1),
                 (5, spell_data::BOOST1),
                 (6, spell_data::DRAIN1),
                 (7, spell_data::BOOST2),
                 (8, spell_data::POISON1),
                 (9, spell_data::RALLY2),
+                (10, spell_data::SILENCEALL1),
+                (11, spell_data::HASTEALL1),
+                (12, spell_data::BREAKALL1),
             ],
+            equip_rule: EquipRule::Only(CLERIC_GEAR),
         },
     })
 }
@@ -1,13 +1,27 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use item::{Inventory, BAG_CAPACITY};
 use item_data::ItemKey;
-use party::{default_candidates, initial_party, CharacterParamTable, PartyMember, RecruitCandidate};
+use party::{default_candidates, initial_party, CharacterParamTable, PartyMember, PartyMemberKind, RecruitCandidate, Row};
+
+/// 戦闘に出るメンバーの上限
+pub const ACTIVE_PARTY_MAX: usize = 4;
+
+/// ひかえのメンバーが受け取る経験値の割合(%)の初期値
+pub const DEFAULT_RESERVE_EXP_SHARE: u32 = 50;
 
 /// パーティの永続的な状態を管理するリソース（戦闘間でHP/MPを引き継ぐ）
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct PartyState {
+    /// 戦闘に出るメンバー（最大ACTIVE_PARTY_MAX人）
     pub members: Vec<PartyMember>,
+    /// ひかえのメンバー
+    pub reserve: Vec<PartyMember>,
+    /// ひかえのメンバーが受け取る経験値の割合(%)
+    pub reserve_exp_share: u32,
+    /// 後列に並ぶメンバー（いれかえても隊列を覚えておくため種類で持つ）
+    pub back_row: Vec<PartyMemberKind>,
     pub gold: u32,
     /// 仲間候補の一覧（状態付き）
     pub candidates: Vec<RecruitCandidate>,
@@ -21,11 +35,171 @@ impl PartyState {
         members[0].inventory.add(ItemKey::Herb, 2);
         Self {
             members,
+            reserve: Vec::new(),
+            reserve_exp_share: DEFAULT_RESERVE_EXP_SHARE,
+            back_row: Vec::new(),
             gold: 100,
             candidates: default_candidates(),
             bag: Inventory::with_capacity(BAG_CAPACITY),
         }
     }
+
+    /// 全滅して町へ戻るときのペナルティを適用する
+    ///
+    /// ゴールドは半分になり、先頭メンバーだけがHP/MP全快で復活する。
+    pub fn apply_wipe_penalty(&mut self) {
+        self.gold /= 2;
+        if let Some(leader) = self.members.first_mut() {
+            leader.stats.hp = leader.effective_max_hp();
+            leader.stats.mp = leader.effective_max_mp();
+        }
+    }
+
+    /// パーティの誰か（ひかえ・袋を含む）がアイテムを持っているか
+    pub fn has_item(&self, item: ItemKey) -> bool {
+        self.members
+            .iter()
+            .chain(&self.reserve)
+            .any(|m| m.inventory.count(item) > 0)
+            || self.bag.count(item) > 0
+    }
+
+    /// 仲間を加える（戦闘メンバーが満員ならひかえに入れる）。戦闘メンバーに入ればtrue
+    pub fn add_member(&mut self, member: PartyMember) -> bool {
+        if self.members.len() < ACTIVE_PARTY_MAX {
+            self.members.push(member);
+            true
+        } else {
+            self.reserve.push(member);
+            false
+        }
+    }
+
+    /// 戦闘メンバーの枠(slot)とひかえを入れかえる
+    ///
+    /// - `slot < members.len()` かつ `reserve_index` あり: 入れかえ
+    /// - `slot == members.len()`（空き枠）かつ `reserve_index` あり: ひかえから加える
+    /// - `reserve_index` なし: 枠のメンバーをひかえに回す（最後の1人は回せない）
+    ///
+    /// 戦える戦闘メンバーがいなくなる入れかえはできない。
+    pub fn change_lineup(&mut self, slot: usize, reserve_index: Option<usize>) -> Option<LineupChange> {
+        let incoming_alive = reserve_index
+            .and_then(|ri| self.reserve.get(ri))
+            .is_some_and(|m| m.stats.is_alive());
+        let others_alive = self
+            .members
+            .iter()
+            .enumerate()
+            .any(|(i, m)| i != slot && m.stats.is_alive());
+        if !incoming_alive && !others_alive {
+            return None;
+        }
+        match reserve_index {
+            Some(ri) if ri < self.reserve.len() => {
+                if slot < self.members.len() {
+                    std::mem::swap(&mut self.members[slot], &mut self.reserve[ri]);
+                    Some(LineupChange::Swapped {
+                        benched: self.reserve[ri].kind,
+                        joined: self.members[slot].kind,
+                    })
+                } else if slot == self.members.len() && slot < ACTIVE_PARTY_MAX {
+                    let member = self.reserve.remove(ri);
+                    let kind = member.kind;
+                    self.members.push(member);
+                    Some(LineupChange::Joined(kind))
+                } else {
+                    None
+                }
+            }
+            None if slot < self.members.len() && self.members.len() > 1 => {
+                let member = self.members.remove(slot);
+                let kind = member.kind;
+                self.reserve.push(member);
+                Some(LineupChange::Benched(kind))
+            }
+            _ => None,
+        }
+    }
+
+    /// メンバーの隊列
+    pub fn row_of(&self, kind: PartyMemberKind) -> Row {
+        if self.back_row.contains(&kind) {
+            Row::Back
+        } else {
+            Row::Front
+        }
+    }
+
+    /// メンバーの隊列を前後入れかえ、新しい隊列を返す
+    pub fn toggle_row(&mut self, kind: PartyMemberKind) -> Row {
+        let row = self.row_of(kind).toggled();
+        match row {
+            Row::Back => self.back_row.push(kind),
+            Row::Front => self.back_row.retain(|&k| k != kind),
+        }
+        row
+    }
+
+    /// 戦闘メンバーの並び順での隊列
+    pub fn party_rows(&self) -> Vec<Row> {
+        self.members.iter().map(|m| self.row_of(m.kind)).collect()
+    }
+
+    /// ひかえのメンバーに戦闘の経験値を分配する
+    ///
+    /// 戦闘不能のメンバーは受け取らない。1人あたりの獲得量（受け取った人がいなければ0）と
+    /// レベルアップしたメンバーを返す。
+    pub fn grant_reserve_exp(
+        &mut self,
+        total_exp: u32,
+        table: &CharacterParamTable,
+    ) -> (u32, Vec<ReserveLevelUp>) {
+        let share = total_exp * self.reserve_exp_share / 100;
+        let mut receivers = self.reserve.iter_mut().filter(|m| m.stats.is_alive()).peekable();
+        if share == 0 || receivers.peek().is_none() {
+            return (0, Vec::new());
+        }
+        let mut level_ups = Vec::new();
+        for member in receivers {
+            let old_level = member.level;
+            if member.gain_exp(share, table) > 0 {
+                level_ups.push(ReserveLevelUp {
+                    kind: member.kind,
+                    old_level,
+                    new_level: member.level,
+                });
+            }
+        }
+        (share, level_ups)
+    }
+}
+
+/// ひかえのメンバーのレベルアップ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReserveLevelUp {
+    pub kind: PartyMemberKind,
+    pub old_level: u32,
+    pub new_level: u32,
+}
+
+/// いれかえの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineupChange {
+    Swapped { benched: PartyMemberKind, joined: PartyMemberKind },
+    Joined(PartyMemberKind),
+    Benched(PartyMemberKind),
+}
+
+impl LineupChange {
+    pub fn message(self) -> String {
+        match self {
+            LineupChange::Swapped { benched, joined } => {
+                format!("{}と {}を いれかえた", benched.name(), joined.name())
+            }
+            LineupChange::Joined(kind) => format!("{}が パーティに くわわった", kind.name()),
+            LineupChange::Benched(kind) => format!("{}を ひかえに まわした", kind.name()),
+        }
+    }
 }
 
 /// キャラクターパラメータの Bevy Resource ラッパー
@@ -38,3 +212,121 @@ impl std::ops::Deref for CharacterParams {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_state() -> (PartyState, CharacterParamTable) {
+        let table = party_data::character_param_table();
+        let mut state = PartyState::new(&table);
+        for &kind in &party::all_kinds()[1..6] {
+            state.add_member(PartyMember::from_kind(kind, &table));
+        }
+        (state, table)
+    }
+
+    #[test]
+    fn add_member_overflows_into_reserve() {
+        let (state, _) = full_state();
+        assert_eq!(state.members.len(), ACTIVE_PARTY_MAX);
+        assert_eq!(state.reserve.len(), 2);
+    }
+
+    #[test]
+    fn has_item_checks_members_reserve_and_bag() {
+        let (mut state, _) = full_state();
+        assert!(!state.has_item(ItemKey::CopperKey));
+        state.reserve[0].inventory.add(ItemKey::CopperKey, 1);
+        assert!(state.has_item(ItemKey::CopperKey));
+        state.reserve[0].inventory.remove_item(ItemKey::CopperKey);
+        state.bag.add(ItemKey::CopperKey, 1);
+        assert!(state.has_item(ItemKey::CopperKey));
+    }
+
+    #[test]
+    fn change_lineup_swaps_joins_and_benches() {
+        let (mut state, _) = full_state();
+        let active_kind = state.members[1].kind;
+        let reserve_kind = state.reserve[0].kind;
+
+        let change = state.change_lineup(1, Some(0));
+        assert_eq!(change, Some(LineupChange::Swapped { benched: active_kind, joined: reserve_kind }));
+        assert_eq!(state.members[1].kind, reserve_kind);
+        assert_eq!(state.reserve[0].kind, active_kind);
+
+        // 満員なので空き枠には加えられない
+        assert_eq!(state.change_lineup(ACTIVE_PARTY_MAX, Some(0)), None);
+
+        let benched_kind = state.members[3].kind;
+        assert_eq!(state.change_lineup(3, None), Some(LineupChange::Benched(benched_kind)));
+        assert_eq!(state.members.len(), 3);
+        assert!(matches!(state.change_lineup(3, Some(0)), Some(LineupChange::Joined(_))));
+        assert_eq!(state.members.len(), 4);
+    }
+
+    #[test]
+    fn last_active_member_cannot_be_benched() {
+        let table = party_data::character_param_table();
+        let mut state = PartyState::new(&table);
+        assert_eq!(state.change_lineup(0, None), None);
+        assert_eq!(state.members.len(), 1);
+    }
+
+    #[test]
+    fn lineup_change_keeps_someone_able_to_fight() {
+        let (mut state, _) = full_state();
+        for member in &mut state.members[1..] {
+            member.stats.hp = 0;
+        }
+        state.reserve[0].stats.hp = 0;
+        // 唯一戦えるライオスを戦闘不能のひかえと入れかえることはできない
+        assert_eq!(state.change_lineup(0, Some(0)), None);
+        assert_eq!(state.change_lineup(0, None), None);
+        // 戦えるひかえとなら入れかえられる
+        assert!(state.change_lineup(0, Some(1)).is_some());
+    }
+
+    #[test]
+    fn toggle_row_follows_member_through_lineup_change() {
+        let (mut state, _) = full_state();
+        let kind = state.members[1].kind;
+        assert_eq!(state.toggle_row(kind), Row::Back);
+        assert_eq!(state.party_rows()[1], Row::Back);
+        // ひかえに回して戻しても後列のまま
+        state.change_lineup(1, Some(0));
+        assert_eq!(state.party_rows()[1], Row::Front);
+        state.change_lineup(1, Some(0));
+        assert_eq!(state.party_rows()[1], Row::Back);
+        assert_eq!(state.toggle_row(kind), Row::Front);
+        assert!(state.back_row.is_empty());
+    }
+
+    #[test]
+    fn reserve_receives_exp_share() {
+        let (mut state, table) = full_state();
+        state.reserve_exp_share = 50;
+        let (share, level_ups) = state.grant_reserve_exp(40, &table);
+        assert_eq!(share, 20);
+        assert!(state.reserve.iter().all(|m| m.exp == 20 && m.level == 2));
+        assert_eq!(level_ups.len(), state.reserve.len());
+        assert!(level_ups.iter().all(|l| l.old_level == 1 && l.new_level == 2));
+    }
+
+    #[test]
+    fn fallen_reserve_gets_no_exp_and_stays_down() {
+        let (mut state, table) = full_state();
+        state.reserve_exp_share = 50;
+        state.reserve[0].stats.hp = 0;
+        let fallen = state.reserve[0].kind;
+        let (_, level_ups) = state.grant_reserve_exp(40, &table);
+        assert_eq!(state.reserve[0].exp, 0);
+        assert_eq!(state.reserve[0].stats.hp, 0);
+        assert!(level_ups.iter().all(|l| l.kind != fallen));
+
+        for member in &mut state.reserve {
+            member.stats.hp = 0;
+        }
+        assert_eq!(state.grant_reserve_exp(40, &table), (0, Vec::new()));
+    }
+}
@@ -1,13 +1,35 @@
 use crate::party::{PartyMemberKind, RecruitmentPath};
 use crate::stats::{CombatStats, StatGrowth};
+use item::GearClass;
+use item_data::ItemKey;
 use spell::SpellEntry;
 
+/// キャラごとの装備可否ルール
+#[derive(Debug, Clone, Copy)]
+pub enum EquipRule {
+    /// すべての装備品を装備できる
+    Any,
+    /// 列挙した系統の装備品のみ装備できる
+    Only(&'static [GearClass]),
+}
+
+impl EquipRule {
+    pub fn allows(&self, item: ItemKey) -> bool {
+        match (self, item.entry().gear_class) {
+            (_, None) => false,
+            (EquipRule::Any, Some(_)) => true,
+            (EquipRule::Only(classes), Some(class)) => classes.contains(&class),
+        }
+    }
+}
+
 /// 1キャラクターのデータエントリ
 pub struct CharacterEntry {
     pub initial_stats: CombatStats,
     pub stat_growth: StatGrowth,
     pub recruit_method: RecruitmentPath,
     pub spell_learn_table: &'static [(u32, SpellEntry)],
+    pub equip_rule: EquipRule,
 }
 
 /// 全キャラのデータテーブル
@@ -64,4 +86,9 @@ impl CharacterParamTable {
     pub fn spell_learn_table(&self, kind: PartyMemberKind) -> &'static [(u32, SpellEntry)] {
         self.entries[kind_index(kind)].spell_learn_table
     }
+
+    /// キャラが装備品を装備できるか（装備品以外は常にfalse）
+    pub fn can_equip(&self, kind: PartyMemberKind, item: ItemKey) -> bool {
+        item.entry().is_equipment() && self.entries[kind_index(kind)].equip_rule.allows(item)
+    }
 }
@@ -6,8 +6,8 @@ pub mod character_table;
 pub use party::{
     consume_item, default_candidates, default_party, exp_to_next_level, has_item, initial_party,
     talk_to_candidate, PartyMember, PartyMemberKind, RecruitCandidate, RecruitmentPath,
-    RecruitmentStatus, TalkResult,
+    RecruitmentStatus, Row, TalkResult,
 };
 pub use spell_table::{available_spells, spells_learned_at_level};
 pub use stats::{CombatStats, StatGrowth};
-pub use character_table::{CharacterEntry, CharacterParamTable, all_kinds};
+pub use character_table::{CharacterEntry, CharacterParamTable, EquipRule, all_kinds};
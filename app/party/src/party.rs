@@ -1,9 +1,10 @@
-use item::{Equipment, Inventory};
+use item::{EquipSlot, Equipment, Inventory};
+use serde::{Deserialize, Serialize};
 use item_data::ItemKey;
 use crate::character_table::CharacterParamTable;
 use crate::stats::CombatStats;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PartyMemberKind {
     Laios,
     Chilchuck,
@@ -34,7 +35,31 @@ impl PartyMemberKind {
     }
 }
 
-#[derive(Debug, Clone)]
+/// 隊列（前列/後列）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Row {
+    #[default]
+    Front,
+    Back,
+}
+
+impl Row {
+    pub fn label(self) -> &'static str {
+        match self {
+            Row::Front => "まえ",
+            Row::Back => "うしろ",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Row::Front => Row::Back,
+            Row::Back => Row::Front,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PartyMember {
     pub kind: PartyMemberKind,
     pub level: u32,
@@ -66,6 +91,56 @@ impl PartyMember {
         self.stats.attack + self.equipment.attack_bonus()
     }
 
+    /// 装備込みの実効防御力
+    pub fn effective_defense(&self) -> i32 {
+        self.stats.defense + self.equipment.defense_bonus()
+    }
+
+    /// 装備込みの実効素早さ
+    pub fn effective_speed(&self) -> i32 {
+        self.stats.speed + self.equipment.speed_bonus()
+    }
+
+    /// 装備込みの最大HP
+    pub fn effective_max_hp(&self) -> i32 {
+        self.stats.max_hp + self.equipment.max_hp_bonus()
+    }
+
+    /// 装備込みの最大MP
+    pub fn effective_max_mp(&self) -> i32 {
+        self.stats.max_mp + self.equipment.max_mp_bonus()
+    }
+
+    /// 装備品を対応するスロットに装備し、以前の装備を返す（装備品でなければ何もしない）
+    pub fn equip(&mut self, item: ItemKey) -> Option<ItemKey> {
+        let slot = item.equip_slot()?;
+        let previous = self.equipment.equip(slot, item);
+        self.clamp_to_effective_max();
+        previous
+    }
+
+    /// スロットの装備を外して返す
+    pub fn unequip(&mut self, slot: EquipSlot) -> Option<ItemKey> {
+        let previous = self.equipment.unequip(slot);
+        self.clamp_to_effective_max();
+        previous
+    }
+
+    /// もちものの中でスロットに装備できるアイテム一覧
+    pub fn equip_candidates(&self, slot: EquipSlot) -> Vec<ItemKey> {
+        item_data::ALL_ITEM_KEYS
+            .iter()
+            .copied()
+            .filter(|&item| item.equip_slot() == Some(slot) && self.inventory.count(item) > 0)
+            .collect()
+    }
+
+    /// 装備を外して最大値が下がったときにHP/MPを切り詰める
+    fn clamp_to_effective_max(&mut self) {
+        self.stats.hp = self.stats.hp.min(self.effective_max_hp());
+        self.stats.mp = self.stats.mp.min(self.effective_max_mp());
+    }
+
     /// 経験値を獲得し、レベルアップがあれば回数を返す
     pub fn gain_exp(&mut self, amount: u32, table: &CharacterParamTable) -> u32 {
         self.exp += amount;
@@ -74,7 +149,11 @@ impl PartyMember {
             self.level += 1;
             level_ups += 1;
             let growth = table.stat_growth(self.kind);
+            let (hp, mp) = (self.stats.hp + growth.hp, self.stats.mp + growth.mp);
             self.stats.apply_growth(growth);
+            // 装備ボーナス分を含めた最大値で上限をかけ直す
+            self.stats.hp = hp.min(self.effective_max_hp());
+            self.stats.mp = mp.min(self.effective_max_mp());
         }
         level_ups
     }
@@ -114,7 +193,7 @@ pub enum RecruitmentPath {
     ItemTrade { item: ItemKey },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RecruitmentStatus {
     /// まだ出会っていない
     Undiscovered,
@@ -124,7 +203,7 @@ pub enum RecruitmentStatus {
     Recruited,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecruitCandidate {
     pub kind: PartyMemberKind,
     pub status: RecruitmentStatus,
@@ -197,7 +276,8 @@ pub fn consume_item(members: &mut [PartyMember], bag: &mut Inventory<ItemKey>, i
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::character_table::{CharacterEntry, CharacterParamTable};
+    use crate::character_table::{CharacterEntry, CharacterParamTable, EquipRule};
+    use item::GearClass;
     use crate::stats::StatGrowth;
 
     fn char_table() -> CharacterParamTable {
@@ -207,12 +287,14 @@ mod tests {
                 stat_growth: StatGrowth { hp: 5, mp: 1, attack: 2, defense: 1, speed: 1 },
                 recruit_method: RecruitmentPath::TavernBond,
                 spell_learn_table: &[],
+                equip_rule: EquipRule::Any,
             },
             _ => CharacterEntry {
                 initial_stats: CombatStats::new(20, 5, 2, 5, 5),
                 stat_growth: StatGrowth { hp: 3, mp: 1, attack: 1, defense: 1, speed: 1 },
                 recruit_method: RecruitmentPath::TavernBond,
                 spell_learn_table: &[],
+                equip_rule: EquipRule::Any,
             },
         })
     }
@@ -330,4 +412,51 @@ mod tests {
         laios.equipment.equip_weapon(ItemKey::IronSword);
         assert_eq!(laios.effective_attack(), laios.stats.attack + 5);
     }
+
+    #[test]
+    fn armor_and_accessory_raise_effective_stats() {
+        let table = char_table();
+        let mut laios = PartyMember::from_kind(PartyMemberKind::Laios, &table);
+        laios.inventory.add(ItemKey::ChainMail, 1);
+        laios.inventory.add(ItemKey::SwiftRing, 1);
+        assert_eq!(laios.equip_candidates(EquipSlot::Armor), vec![ItemKey::ChainMail]);
+
+        laios.equip(ItemKey::ChainMail);
+        laios.equip(ItemKey::SwiftRing);
+        assert_eq!(laios.effective_defense(), laios.stats.defense + 6);
+        assert_eq!(laios.effective_speed(), laios.stats.speed + 2);
+        assert_eq!(laios.effective_max_hp(), laios.stats.max_hp + 5);
+    }
+
+    #[test]
+    fn equip_rule_limits_equippable_items() {
+        let table = CharacterParamTable::from_fn(|kind| CharacterEntry {
+            initial_stats: CombatStats::new(20, 5, 2, 5, 5),
+            stat_growth: StatGrowth { hp: 3, mp: 1, attack: 1, defense: 1, speed: 1 },
+            recruit_method: RecruitmentPath::TavernBond,
+            spell_learn_table: &[],
+            equip_rule: match kind {
+                PartyMemberKind::Marcille => EquipRule::Only(&[GearClass::Staff]),
+                _ => EquipRule::Any,
+            },
+        });
+        assert!(table.can_equip(PartyMemberKind::Marcille, ItemKey::MageStaff));
+        // 同じ系統の装備品はリストに足さなくても装備できる
+        assert!(table.can_equip(PartyMemberKind::Marcille, ItemKey::HolyStaff));
+        assert!(!table.can_equip(PartyMemberKind::Marcille, ItemKey::IronSword));
+        assert!(table.can_equip(PartyMemberKind::Laios, ItemKey::IronSword));
+        // 装備品でないアイテムは誰も装備できない
+        assert!(!table.can_equip(PartyMemberKind::Laios, ItemKey::Herb));
+    }
+
+    #[test]
+    fn unequip_clamps_hp_to_new_max() {
+        let table = char_table();
+        let mut laios = PartyMember::from_kind(PartyMemberKind::Laios, &table);
+        laios.equip(ItemKey::ChainMail);
+        laios.stats.hp = laios.effective_max_hp();
+
+        assert_eq!(laios.unequip(EquipSlot::Armor), Some(ItemKey::ChainMail));
+        assert_eq!(laios.stats.hp, laios.stats.max_hp);
+    }
 }
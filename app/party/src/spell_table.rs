@@ -23,7 +23,7 @@ pub fn spells_learned_at_level(kind: PartyMemberKind, level: u32, table: &Charac
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::character_table::{CharacterEntry, CharacterParamTable};
+    use crate::character_table::{CharacterEntry, CharacterParamTable, EquipRule};
     use crate::party::RecruitmentPath;
     use crate::stats::{CombatStats, StatGrowth};
     
@@ -43,6 +43,7 @@ mod tests {
                     (9, spell_data::DRAIN1),
                     (10, spell_data::SLEEPALL1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Falin => CharacterEntry {
                 initial_stats: CombatStats::new(25, 5, 4, 4, 12),
@@ -56,6 +57,7 @@ mod tests {
                     (9, spell_data::HEALALL2),
                     (10, spell_data::BARRIER2),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Rinsha => CharacterEntry {
                 initial_stats: CombatStats::new(24, 5, 3, 6, 8),
@@ -70,6 +72,7 @@ mod tests {
                     (8, spell_data::POISON1),
                     (9, spell_data::RALLY2),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Kabru => CharacterEntry {
                 initial_stats: CombatStats::new(26, 7, 3, 6, 5),
@@ -83,6 +86,7 @@ mod tests {
                     (8, spell_data::POISONALL1),
                     (9, spell_data::RALLY1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Laios => CharacterEntry {
                 initial_stats: CombatStats::new(30, 8, 3, 5, 5),
@@ -102,6 +106,7 @@ mod tests {
                     (1, spell_data::SLEEP1), (1, spell_data::SLEEPALL1),
                     (1, spell_data::POISON1), (1, spell_data::POISONALL1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Izutsumi => CharacterEntry {
                 initial_stats: CombatStats::new(20, 7, 1, 10, 3),
@@ -111,6 +116,7 @@ mod tests {
                     (5, spell_data::FIRE1),
                     (8, spell_data::BOOST1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             PartyMemberKind::Senshi => CharacterEntry {
                 initial_stats: CombatStats::new(40, 7, 6, 2, 3),
@@ -119,12 +125,14 @@ mod tests {
                 spell_learn_table: const { &[
                     (4, spell_data::SHIELD1),
                 ] },
+                equip_rule: EquipRule::Any,
             },
             _ => CharacterEntry {
                 initial_stats: CombatStats::new(20, 5, 2, 5, 0),
                 stat_growth: StatGrowth { hp: 3, mp: 0, attack: 1, defense: 1, speed: 1 },
                 recruit_method: RecruitmentPath::TavernBond,
                 spell_learn_table: &[],
+                equip_rule: EquipRule::Any,
             },
         })
     }
@@ -1,4 +1,15 @@
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+/// 素早さが同じときの命中率
+pub const BASE_HIT_RATE: f32 = 0.95;
+/// 素早さの差1あたりの命中率の増減
+pub const HIT_RATE_PER_SPEED: f32 = 0.01;
+/// 命中率の下限
+pub const MIN_HIT_RATE: f32 = 0.75;
+/// 会心の一撃の発生率
+pub const CRITICAL_RATE: f32 = 1.0 / 16.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CombatStats {
     pub hp: i32,
     pub max_hp: i32,
@@ -43,6 +54,18 @@ impl CombatStats {
         damage.max(1)
     }
 
+    /// 命中率 = 基本命中率 + (攻撃側の素早さ - 回避側の素早さ) × 補正（下限あり、最大1.0）
+    pub fn hit_rate(attacker_speed: i32, defender_speed: i32) -> f32 {
+        (BASE_HIT_RATE + (attacker_speed - defender_speed) as f32 * HIT_RATE_PER_SPEED)
+            .clamp(MIN_HIT_RATE, 1.0)
+    }
+
+    /// 会心ダメージ = 攻撃 × 乱数(0.8~1.2)、防御を無視する。最小1
+    pub fn calculate_critical_damage(attacker_attack: i32, random_factor: f32) -> i32 {
+        let damage = (attacker_attack as f32 * random_factor).round() as i32;
+        damage.max(1)
+    }
+
     pub fn take_damage(&mut self, damage: i32) {
         self.hp = (self.hp - damage).max(0);
     }
@@ -104,6 +127,20 @@ mod tests {
         assert_eq!(damage_high, 8);
     }
 
+    #[test]
+    fn hit_rate_depends_on_speed_difference() {
+        assert_eq!(CombatStats::hit_rate(10, 10), BASE_HIT_RATE);
+        assert!(CombatStats::hit_rate(5, 10) < BASE_HIT_RATE);
+        assert_eq!(CombatStats::hit_rate(0, 100), MIN_HIT_RATE);
+        assert_eq!(CombatStats::hit_rate(100, 0), 1.0);
+    }
+
+    #[test]
+    fn critical_damage_ignores_defense() {
+        assert_eq!(CombatStats::calculate_critical_damage(20, 1.0), 20);
+        assert!(CombatStats::calculate_critical_damage(20, 1.0) > CombatStats::calculate_damage(20, 10, 1.0));
+    }
+
     #[test]
     fn take_damage_reduces_hp() {
         let mut stats = CombatStats::new(30, 8, 3, 5, 0);
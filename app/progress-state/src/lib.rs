@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use enemy::Enemy;
+use serde::{Deserialize, Serialize};
 
 use std::collections::{HashMap, HashSet};
 
@@ -10,18 +12,55 @@ pub struct BossDefeated;
 #[derive(Resource)]
 pub struct BossBattlePending;
 
+/// ミミック戦闘トリガー（ミミックの宝箱を開けた時に挿入、battle-uiが消費）
+#[derive(Resource)]
+pub struct MimicBattlePending;
+
+/// シンボル敵との接触で始まる戦闘の敵グループ（接触時に挿入、battle-uiが消費）
+#[derive(Resource)]
+pub struct SymbolBattlePending {
+    pub enemies: Vec<Enemy>,
+}
+
 /// 取得済み宝箱を管理するリソース
 /// key: ワールドマップ上の洞窟座標 (cave_x, cave_y)
 /// value: その洞窟内で取得済みの宝箱インデックスの集合
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
 pub struct OpenedChests {
     pub chests: HashMap<(usize, usize), HashSet<usize>>,
 }
 
+/// 開けた扉を管理するリソース
+/// key: ワールドマップ上の洞窟座標 (cave_x, cave_y)
+/// value: その洞窟内で開けた扉のインデックスの集合
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct OpenedDoors {
+    pub doors: HashMap<(usize, usize), HashSet<usize>>,
+}
+
+/// 最後に立ち寄った町（全滅時の帰還先）
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct LastVisitedTown {
+    pub position: Option<(usize, usize)>,
+}
+
+/// 全滅時の扱い
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefeatMode {
+    /// 冒険はそこで終わり、新しいワールドで最初からやり直す
+    #[default]
+    RunEnds,
+    /// 最後に立ち寄った町へ戻る（ゴールド半減）
+    ReturnToTown,
+}
+
 pub struct ProgressStatePlugin;
 
 impl Plugin for ProgressStatePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<OpenedChests>();
+        app.init_resource::<OpenedChests>()
+            .init_resource::<OpenedDoors>()
+            .init_resource::<LastVisitedTown>()
+            .init_resource::<DefeatMode>();
     }
 }
@@ -0,0 +1,216 @@
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use party_state::PartyState;
+use progress_state::{LastVisitedTown, OpenedChests, OpenedDoors};
+use tavern_state::{HeardTavernHints, TavernBounties};
+use world_state::{GameRngPositions, HokoraPositions, RecruitmentMap};
+
+/// セーブデータの形式バージョン（互換性のない変更を加えたら上げる）
+pub const SAVE_VERSION: u32 = 1;
+
+/// セーブファイルの保存先
+pub const SAVE_FILE_PATH: &str = "save.ron";
+
+/// プレイヤーの位置情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub x: usize,
+    pub y: usize,
+    /// 船に乗っているか（乗っている船はプレイヤーと同じ座標の船）
+    pub on_boat: bool,
+}
+
+/// 1回の冒険の状態をすべて保持するセーブデータ
+///
+/// ワールドマップ自体は保存せず、シードから再生成する。
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    pub world_seed: u64,
+    /// 乱数ストリームの消費位置
+    pub rng: GameRngPositions,
+    pub player: PlayerSave,
+    /// 船の現在位置
+    pub boats: Vec<(usize, usize)>,
+    pub party: PartyState,
+    pub opened_chests: OpenedChests,
+    /// 開けた扉
+    pub opened_doors: OpenedDoors,
+    pub tavern_bounties: TavernBounties,
+    pub heard_tavern_hints: HeardTavernHints,
+    pub recruitment: RecruitmentMap,
+    pub hokora: HokoraPositions,
+    pub boss_defeated: bool,
+    /// 全滅時の帰還先
+    pub last_visited_town: LastVisitedTown,
+    /// 探索済みタイルの座標
+    pub explored_tiles: Vec<(usize, usize)>,
+}
+
+/// バージョン判定用にversionだけを読むヘッダ
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// セーブ・ロードの失敗理由
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Format(String),
+    UnsupportedVersion(u32),
+    /// セーブに必要な状態が存在しない（フィールド外など）
+    MissingState(&'static str),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "io error: {}", e),
+            SaveError::Format(e) => write!(f, "format error: {}", e),
+            SaveError::UnsupportedVersion(v) => write!(f, "unsupported save version: {}", v),
+            SaveError::MissingState(name) => write!(f, "missing state: {}", name),
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(e: std::io::Error) -> Self {
+        SaveError::Io(e)
+    }
+}
+
+impl SaveData {
+    /// RON文字列に変換する
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SaveError::Format(e.to_string()))
+    }
+
+    /// RON文字列から復元する（バージョンが異なればエラー）
+    pub fn from_ron(text: &str) -> Result<Self, SaveError> {
+        let header: SaveHeader =
+            ron::from_str(text).map_err(|e| SaveError::Format(e.to_string()))?;
+        if header.version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(header.version));
+        }
+        ron::from_str(text).map_err(|e| SaveError::Format(e.to_string()))
+    }
+}
+
+/// セーブデータをファイルに書き込む
+pub fn write_save_file(path: &Path, data: &SaveData) -> Result<(), SaveError> {
+    std::fs::write(path, data.to_ron()?)?;
+    Ok(())
+}
+
+/// セーブデータをファイルから読み込む
+pub fn read_save_file(path: &Path) -> Result<SaveData, SaveError> {
+    let text = std::fs::read_to_string(path)?;
+    SaveData::from_ron(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item_data::ItemKey;
+    use std::collections::{HashMap, HashSet};
+    use tavern_state::TavernHintKind;
+
+    fn sample_data() -> SaveData {
+        let mut party = PartyState::new(&party_data::character_param_table());
+        party.gold = 1234;
+        party.bag.add(ItemKey::MagicStone, 2);
+        party.members[0].equipment.equip_weapon(ItemKey::IronSword);
+
+        let mut opened_chests = OpenedChests::default();
+        opened_chests.chests.insert((10, 20), HashSet::from([0, 2]));
+
+        let mut opened_doors = OpenedDoors::default();
+        opened_doors.doors.insert((10, 20), HashSet::from([100]));
+
+        let mut tavern_bounties = TavernBounties::default();
+        tavern_bounties.active.insert((3, 4), ItemKey::DragonScale);
+
+        let mut heard_tavern_hints = HeardTavernHints::default();
+        heard_tavern_hints
+            .heard
+            .insert((3, 4), HashSet::from([TavernHintKind::Cave, TavernHintKind::Bounty]));
+
+        SaveData {
+            version: SAVE_VERSION,
+            world_seed: 42,
+            rng: GameRngPositions { encounter: 16, battle: 1 << 40, field: 0 },
+            player: PlayerSave { x: 7, y: 8, on_boat: true },
+            boats: vec![(7, 8), (30, 40)],
+            party,
+            opened_chests,
+            opened_doors,
+            tavern_bounties,
+            heard_tavern_hints,
+            recruitment: RecruitmentMap {
+                town_to_candidate: HashMap::from([((1, 2), 0)]),
+                candidate_second_town: HashMap::from([(0, (5, 6))]),
+                hire_available: HashMap::new(),
+            },
+            hokora: HokoraPositions {
+                positions: vec![(11, 12)],
+                warp_destinations: vec![(13, 14)],
+            },
+            boss_defeated: true,
+            last_visited_town: LastVisitedTown {
+                position: Some((3, 4)),
+            },
+            explored_tiles: vec![(7, 8), (8, 8)],
+        }
+    }
+
+    #[test]
+    fn ron_round_trip_preserves_state() {
+        let data = sample_data();
+        let restored = SaveData::from_ron(&data.to_ron().unwrap()).unwrap();
+
+        assert_eq!(restored.world_seed, 42);
+        assert_eq!(restored.rng, data.rng);
+        assert_eq!(restored.player, data.player);
+        assert_eq!(restored.boats, data.boats);
+        assert_eq!(restored.party.gold, 1234);
+        assert_eq!(restored.party.bag.count(ItemKey::MagicStone), 2);
+        assert_eq!(restored.party.members[0].inventory.count(ItemKey::Herb), 2);
+        assert_eq!(restored.party.members[0].equipment.weapon, Some(ItemKey::IronSword));
+        assert_eq!(restored.party.candidates.len(), data.party.candidates.len());
+        assert_eq!(restored.opened_chests.chests, data.opened_chests.chests);
+        assert_eq!(restored.opened_doors.doors, data.opened_doors.doors);
+        assert_eq!(restored.tavern_bounties.active, data.tavern_bounties.active);
+        assert_eq!(restored.heard_tavern_hints.heard, data.heard_tavern_hints.heard);
+        assert_eq!(restored.recruitment.town_to_candidate, data.recruitment.town_to_candidate);
+        assert_eq!(
+            restored.recruitment.candidate_second_town,
+            data.recruitment.candidate_second_town
+        );
+        assert_eq!(restored.hokora.positions, data.hokora.positions);
+        assert!(restored.boss_defeated);
+        assert_eq!(restored.last_visited_town.position, Some((3, 4)));
+        assert_eq!(restored.explored_tiles, data.explored_tiles);
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let mut data = sample_data();
+        data.version = SAVE_VERSION + 1;
+        let text = data.to_ron().unwrap();
+
+        assert!(matches!(
+            SaveData::from_ron(&text),
+            Err(SaveError::UnsupportedVersion(v)) if v == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn broken_text_is_format_error() {
+        assert!(matches!(SaveData::from_ron("(version: 1, "), Err(SaveError::Format(_))));
+    }
+}
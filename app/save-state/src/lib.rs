@@ -0,0 +1,11 @@
+mod data;
+mod snapshot;
+
+pub use data::{
+    read_save_file, write_save_file, PlayerSave, SaveData, SaveError, SAVE_FILE_PATH,
+    SAVE_VERSION,
+};
+pub use snapshot::{
+    capture_save_data, load_game, restore_save_data, return_to_last_town, save_game,
+    start_new_run,
+};
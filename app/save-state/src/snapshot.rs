@@ -0,0 +1,278 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use field_core::{ActiveMap, Boat, OnBoat, Player, TilePosition};
+use field_walk::exploration::{ExplorationMap, VIEW_RADIUS};
+use field_walk_ui::{
+    create_tile_pool, spawn_field_map_with_rng, BoatSpawnsResource, ExplorationData, PooledTile,
+    SpawnPosition, StructureOverlay, TilePool, TileTextures,
+};
+use party_state::{CharacterParams, PartyState};
+use progress_state::{BossDefeated, LastVisitedTown, OpenedChests, OpenedDoors};
+use tavern_state::{HeardTavernHints, TavernBounties};
+use terrain::{MAP_HEIGHT, MAP_WIDTH};
+use world_state::{GameRng, GameRngPositions, HokoraPositions, RecruitmentMap, WorldSeed};
+
+use crate::data::{
+    read_save_file, write_save_file, PlayerSave, SaveData, SaveError, SAVE_FILE_PATH,
+    SAVE_VERSION,
+};
+
+/// 現在のフィールド状態からセーブデータを作る
+///
+/// ワールドマップ探索中（ActiveMapがワールドマップ）に呼ぶこと。
+pub fn capture_save_data(world: &mut World) -> Result<SaveData, SaveError> {
+    let seed = world
+        .get_resource::<WorldSeed>()
+        .ok_or(SaveError::MissingState("WorldSeed"))?
+        .0;
+    let rng = world
+        .get_resource::<GameRng>()
+        .ok_or(SaveError::MissingState("GameRng"))?
+        .positions();
+
+    let (player_x, player_y, on_boat) = world
+        .query_filtered::<(&TilePosition, Has<OnBoat>), With<Player>>()
+        .single(world)
+        .map(|(pos, on_boat)| (pos.x, pos.y, on_boat))
+        .map_err(|_| SaveError::MissingState("Player"))?;
+
+    let boats = world
+        .query_filtered::<&TilePosition, (With<Boat>, Without<Player>)>()
+        .iter(world)
+        .map(|pos| (pos.x, pos.y))
+        .collect();
+
+    let explored_tiles = world
+        .get_resource::<ExplorationData>()
+        .map(|data| data.map.get_explored_tiles().collect())
+        .unwrap_or_default();
+
+    Ok(SaveData {
+        version: SAVE_VERSION,
+        world_seed: seed,
+        rng,
+        player: PlayerSave {
+            x: player_x,
+            y: player_y,
+            on_boat,
+        },
+        boats,
+        party: cloned_resource::<PartyState>(world)?,
+        opened_chests: world.get_resource::<OpenedChests>().cloned().unwrap_or_default(),
+        opened_doors: world.get_resource::<OpenedDoors>().cloned().unwrap_or_default(),
+        tavern_bounties: world.get_resource::<TavernBounties>().cloned().unwrap_or_default(),
+        heard_tavern_hints: world
+            .get_resource::<HeardTavernHints>()
+            .cloned()
+            .unwrap_or_default(),
+        recruitment: world.get_resource::<RecruitmentMap>().cloned().unwrap_or_default(),
+        hokora: cloned_resource::<HokoraPositions>(world)?,
+        boss_defeated: world.contains_resource::<BossDefeated>(),
+        last_visited_town: world.get_resource::<LastVisitedTown>().cloned().unwrap_or_default(),
+        explored_tiles,
+    })
+}
+
+fn cloned_resource<R: Resource + Clone>(world: &World) -> Result<R, SaveError> {
+    world
+        .get_resource::<R>()
+        .cloned()
+        .ok_or(SaveError::MissingState(std::any::type_name::<R>()))
+}
+
+/// セーブデータをワールドに反映する
+///
+/// 現在のWorldSeedとセーブデータのシードが異なる場合はワールドマップを再生成してから反映する。
+/// 乱数ストリームはシードが同じでも毎回セーブ時点の状態に戻す。
+pub fn restore_save_data(world: &mut World, data: &SaveData) -> Result<(), SaveError> {
+    let seed = WorldSeed(data.world_seed);
+    if world.get_resource::<WorldSeed>() != Some(&seed) {
+        regenerate_world(world, seed)?;
+    }
+    world.insert_resource(GameRng::restore(seed, &data.rng));
+
+    world.insert_resource(data.party.clone());
+    world.insert_resource(data.opened_chests.clone());
+    world.insert_resource(data.opened_doors.clone());
+    world.insert_resource(data.tavern_bounties.clone());
+    world.insert_resource(data.heard_tavern_hints.clone());
+    world.insert_resource(data.recruitment.clone());
+    world.insert_resource(data.hokora.clone());
+    world.insert_resource(data.last_visited_town.clone());
+    if data.boss_defeated {
+        world.insert_resource(BossDefeated);
+    } else {
+        world.remove_resource::<BossDefeated>();
+    }
+
+    let active_map = world
+        .get_resource::<ActiveMap>()
+        .ok_or(SaveError::MissingState("ActiveMap"))?
+        .clone();
+
+    // 船を保存位置へ移動（船同士は区別しないので順に割り当てる）
+    let mut boat_query =
+        world.query_filtered::<(Entity, &mut TilePosition, &mut Transform), (With<Boat>, Without<Player>)>();
+    let mut boarded_boat = None;
+    for ((entity, mut pos, mut transform), &(x, y)) in
+        boat_query.iter_mut(world).zip(data.boats.iter())
+    {
+        pos.x = x;
+        pos.y = y;
+        let (world_x, world_y) = active_map.to_world(x, y);
+        transform.translation.x = world_x;
+        transform.translation.y = world_y;
+        if data.player.on_boat && (x, y) == (data.player.x, data.player.y) {
+            boarded_boat = Some(entity);
+        }
+    }
+    if let Some(mut boat_spawns) = world.get_resource_mut::<BoatSpawnsResource>() {
+        boat_spawns.positions = data.boats.clone();
+    }
+
+    let player = world
+        .query_filtered::<Entity, With<Player>>()
+        .single(world)
+        .map_err(|_| SaveError::MissingState("Player"))?;
+    let (world_x, world_y) = active_map.to_world(data.player.x, data.player.y);
+    let mut player_entity = world.entity_mut(player);
+    if let Some(mut pos) = player_entity.get_mut::<TilePosition>() {
+        pos.x = data.player.x;
+        pos.y = data.player.y;
+    }
+    if let Some(mut transform) = player_entity.get_mut::<Transform>() {
+        transform.translation.x = world_x;
+        transform.translation.y = world_y;
+    }
+    player_entity.remove::<OnBoat>();
+    if let Some(boat_entity) = boarded_boat {
+        player_entity.insert(OnBoat { boat_entity });
+    }
+
+    let mut exploration_map = ExplorationMap::new(MAP_WIDTH, MAP_HEIGHT);
+    for &(x, y) in &data.explored_tiles {
+        exploration_map.mark_explored(x, y);
+    }
+    exploration_map.update_visibility(data.player.x, data.player.y, VIEW_RADIUS);
+    world.insert_resource(ExplorationData {
+        map: exploration_map,
+    });
+
+    // プレイヤーが移動していなくてもタイルを貼り直させる
+    if let Some(mut tile_pool) = world.get_resource_mut::<TilePool>() {
+        tile_pool.last_player_pos = None;
+    }
+
+    Ok(())
+}
+
+/// シードからワールドマップを作り直す（タイル・船エンティティも再生成）
+fn regenerate_world(world: &mut World, seed: WorldSeed) -> Result<(), SaveError> {
+    let asset_server = world
+        .get_resource::<AssetServer>()
+        .ok_or(SaveError::MissingState("AssetServer"))?
+        .clone();
+
+    let stale: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<PooledTile>, With<StructureOverlay>, With<Boat>)>>()
+        .iter(world)
+        .collect();
+    for entity in stale {
+        world.despawn(entity);
+    }
+    world.remove_resource::<TilePool>();
+
+    let mut rng = seed.world_rng();
+    spawn_field_map_with_rng(&mut world.commands(), &asset_server, &mut rng);
+    world.insert_resource(seed);
+    world.flush();
+
+    world.resource_scope(|world, tile_textures: Mut<TileTextures>| {
+        create_tile_pool(&mut world.commands(), &tile_textures);
+    });
+    world.flush();
+
+    Ok(())
+}
+
+/// 新しいシードでワールドを作り直し、冒険を最初から始める
+///
+/// パーティ・宝箱・居酒屋などの進行状況はすべて初期状態に戻る。
+pub fn start_new_run(world: &mut World, seed: WorldSeed) -> Result<(), SaveError> {
+    regenerate_world(world, seed)?;
+
+    let party = PartyState::new(
+        world
+            .get_resource::<CharacterParams>()
+            .ok_or(SaveError::MissingState("CharacterParams"))?,
+    );
+    let spawn = world
+        .get_resource::<SpawnPosition>()
+        .ok_or(SaveError::MissingState("SpawnPosition"))?;
+    let player = PlayerSave {
+        x: spawn.x,
+        y: spawn.y,
+        on_boat: false,
+    };
+    let boats = world
+        .get_resource::<BoatSpawnsResource>()
+        .map(|spawns| spawns.positions.clone())
+        .unwrap_or_default();
+
+    let data = SaveData {
+        version: SAVE_VERSION,
+        world_seed: seed.0,
+        rng: GameRngPositions::default(),
+        player,
+        boats,
+        party,
+        opened_chests: OpenedChests::default(),
+        opened_doors: OpenedDoors::default(),
+        tavern_bounties: TavernBounties::default(),
+        heard_tavern_hints: HeardTavernHints::default(),
+        recruitment: cloned_resource::<RecruitmentMap>(world)?,
+        hokora: cloned_resource::<HokoraPositions>(world)?,
+        boss_defeated: false,
+        last_visited_town: LastVisitedTown::default(),
+        explored_tiles: Vec::new(),
+    };
+    restore_save_data(world, &data)
+}
+
+/// 全滅したパーティを最後に立ち寄った町へ戻す（町に寄っていなければスポーン地点）
+///
+/// ゴールド半減と先頭メンバーの復活は`PartyState::apply_wipe_penalty`に従う。
+pub fn return_to_last_town(world: &mut World) -> Result<(), SaveError> {
+    let mut data = capture_save_data(world)?;
+    data.party.apply_wipe_penalty();
+
+    let (x, y) = match data.last_visited_town.position {
+        Some(pos) => pos,
+        None => {
+            let spawn = world
+                .get_resource::<SpawnPosition>()
+                .ok_or(SaveError::MissingState("SpawnPosition"))?;
+            (spawn.x, spawn.y)
+        }
+    };
+    data.player = PlayerSave {
+        x,
+        y,
+        on_boat: false,
+    };
+    restore_save_data(world, &data)
+}
+
+/// 現在の状態をセーブファイルに書き込む
+pub fn save_game(world: &mut World) -> Result<(), SaveError> {
+    let data = capture_save_data(world)?;
+    write_save_file(Path::new(SAVE_FILE_PATH), &data)
+}
+
+/// セーブファイルを読み込んでワールドに反映する
+pub fn load_game(world: &mut World) -> Result<(), SaveError> {
+    let data = read_save_file(Path::new(SAVE_FILE_PATH))?;
+    restore_save_data(world, &data)
+}
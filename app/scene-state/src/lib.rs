@@ -19,6 +19,8 @@ pub enum SceneState {
     Cave,
     BossCave,
     Hokora,
+    /// パーティ全滅後のゲームオーバー画面
+    GameOver,
 }
 
 /// 戦闘オーバーレイの状態
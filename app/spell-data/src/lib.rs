@@ -1,56 +1,102 @@
-pub use spell::{Ailment, SpellEffect, SpellEntry, SpellTarget};
-pub use spell::{ailment_success, heal_amount, mp_drain_amount, spell_damage};
-pub use spell::{DEFENSE_DIVISOR, POISON_DAMAGE};
+pub use spell::{Affinity, Ailment, Element, SpellEffect, SpellEntry, SpellTarget};
+pub use spell::{ailment_success, elemental_damage, heal_amount, mp_drain_amount, revive_hp, spell_damage};
+pub use spell::{BLIND_HIT_PERCENT, DEFENSE_DIVISOR, POISON_DAMAGE};
 
 use SpellEffect::*;
 use SpellTarget::*;
+use Element::*;
 
 // 単体攻撃
-pub const FIRE1: SpellEntry = SpellEntry { name: "Fire1", mp_cost: 3, effect: Damage { base_damage: 12 }, target_type: SingleEnemy, ailment: None };
-pub const FIRE2: SpellEntry = SpellEntry { name: "Fire2", mp_cost: 7, effect: Damage { base_damage: 25 }, target_type: SingleEnemy, ailment: None };
+pub const FIRE1: SpellEntry = SpellEntry { name: "Fire1", mp_cost: 3, effect: Damage { base_damage: 12 }, target_type: SingleEnemy, ailment: None, element: Some(Fire) };
+pub const FIRE2: SpellEntry = SpellEntry { name: "Fire2", mp_cost: 7, effect: Damage { base_damage: 25 }, target_type: SingleEnemy, ailment: None, element: Some(Fire) };
 // 全体攻撃
-pub const BLAZE1: SpellEntry = SpellEntry { name: "Blaze1", mp_cost: 5, effect: Damage { base_damage: 8 }, target_type: AllEnemies, ailment: None };
-pub const BLAZE2: SpellEntry = SpellEntry { name: "Blaze2", mp_cost: 10, effect: Damage { base_damage: 18 }, target_type: AllEnemies, ailment: None };
+pub const BLAZE1: SpellEntry = SpellEntry { name: "Blaze1", mp_cost: 5, effect: Damage { base_damage: 8 }, target_type: AllEnemies, ailment: None, element: Some(Fire) };
+pub const BLAZE2: SpellEntry = SpellEntry { name: "Blaze2", mp_cost: 10, effect: Damage { base_damage: 18 }, target_type: AllEnemies, ailment: None, element: Some(Fire) };
+// 単体氷
+pub const ICE1: SpellEntry = SpellEntry { name: "Ice1", mp_cost: 3, effect: Damage { base_damage: 12 }, target_type: SingleEnemy, ailment: None, element: Some(Ice) };
+pub const ICE2: SpellEntry = SpellEntry { name: "Ice2", mp_cost: 7, effect: Damage { base_damage: 25 }, target_type: SingleEnemy, ailment: None, element: Some(Ice) };
+// 全体氷
+pub const BLIZZARD1: SpellEntry = SpellEntry { name: "Blizzard1", mp_cost: 6, effect: Damage { base_damage: 10 }, target_type: AllEnemies, ailment: None, element: Some(Ice) };
+// 単体聖
+pub const HOLY1: SpellEntry = SpellEntry { name: "Holy1", mp_cost: 5, effect: Damage { base_damage: 16 }, target_type: SingleEnemy, ailment: None, element: Some(Holy) };
+// 全体聖
+pub const HOLYALL1: SpellEntry = SpellEntry { name: "Holyall1", mp_cost: 10, effect: Damage { base_damage: 14 }, target_type: AllEnemies, ailment: None, element: Some(Holy) };
 // 単体回復
-pub const HEAL1: SpellEntry = SpellEntry { name: "Heal1", mp_cost: 3, effect: Heal { base_heal: 15 }, target_type: SingleAlly, ailment: None };
-pub const HEAL2: SpellEntry = SpellEntry { name: "Heal2", mp_cost: 7, effect: Heal { base_heal: 40 }, target_type: SingleAlly, ailment: None };
+pub const HEAL1: SpellEntry = SpellEntry { name: "Heal1", mp_cost: 3, effect: Heal { base_heal: 15 }, target_type: SingleAlly, ailment: None, element: None };
+pub const HEAL2: SpellEntry = SpellEntry { name: "Heal2", mp_cost: 7, effect: Heal { base_heal: 40 }, target_type: SingleAlly, ailment: None, element: None };
 // 全体回復
-pub const HEALALL1: SpellEntry = SpellEntry { name: "Healall1", mp_cost: 6, effect: Heal { base_heal: 10 }, target_type: AllAllies, ailment: None };
-pub const HEALALL2: SpellEntry = SpellEntry { name: "Healall2", mp_cost: 12, effect: Heal { base_heal: 25 }, target_type: AllAllies, ailment: None };
+pub const HEALALL1: SpellEntry = SpellEntry { name: "Healall1", mp_cost: 6, effect: Heal { base_heal: 10 }, target_type: AllAllies, ailment: None, element: None };
+pub const HEALALL2: SpellEntry = SpellEntry { name: "Healall2", mp_cost: 12, effect: Heal { base_heal: 25 }, target_type: AllAllies, ailment: None, element: None };
 // 味方単体ブロック
-pub const SHIELD1: SpellEntry = SpellEntry { name: "Shield1", mp_cost: 3, effect: Block { amount: 10 }, target_type: SingleAlly, ailment: None };
-pub const SHIELD2: SpellEntry = SpellEntry { name: "Shield2", mp_cost: 6, effect: Block { amount: 20 }, target_type: SingleAlly, ailment: None };
+pub const SHIELD1: SpellEntry = SpellEntry { name: "Shield1", mp_cost: 3, effect: Block { amount: 10 }, target_type: SingleAlly, ailment: None, element: None };
+pub const SHIELD2: SpellEntry = SpellEntry { name: "Shield2", mp_cost: 6, effect: Block { amount: 20 }, target_type: SingleAlly, ailment: None, element: None };
 // 味方全体ブロック
-pub const BARRIER1: SpellEntry = SpellEntry { name: "Barrier1", mp_cost: 6, effect: Block { amount: 6 }, target_type: AllAllies, ailment: None };
-pub const BARRIER2: SpellEntry = SpellEntry { name: "Barrier2", mp_cost: 10, effect: Block { amount: 12 }, target_type: AllAllies, ailment: None };
+pub const BARRIER1: SpellEntry = SpellEntry { name: "Barrier1", mp_cost: 6, effect: Block { amount: 6 }, target_type: AllAllies, ailment: None, element: None };
+pub const BARRIER2: SpellEntry = SpellEntry { name: "Barrier2", mp_cost: 10, effect: Block { amount: 12 }, target_type: AllAllies, ailment: None, element: None };
 // 味方単体ATK↑
-pub const BOOST1: SpellEntry = SpellEntry { name: "Boost1", mp_cost: 3, effect: AttackBuff { amount: 3 }, target_type: SingleAlly, ailment: None };
-pub const BOOST2: SpellEntry = SpellEntry { name: "Boost2", mp_cost: 6, effect: AttackBuff { amount: 6 }, target_type: SingleAlly, ailment: None };
+pub const BOOST1: SpellEntry = SpellEntry { name: "Boost1", mp_cost: 3, effect: AttackBuff { amount: 3 }, target_type: SingleAlly, ailment: None, element: None };
+pub const BOOST2: SpellEntry = SpellEntry { name: "Boost2", mp_cost: 6, effect: AttackBuff { amount: 6 }, target_type: SingleAlly, ailment: None, element: None };
 // 味方全体ATK↑
-pub const RALLY1: SpellEntry = SpellEntry { name: "Rally1", mp_cost: 6, effect: AttackBuff { amount: 2 }, target_type: AllAllies, ailment: None };
-pub const RALLY2: SpellEntry = SpellEntry { name: "Rally2", mp_cost: 10, effect: AttackBuff { amount: 4 }, target_type: AllAllies, ailment: None };
+pub const RALLY1: SpellEntry = SpellEntry { name: "Rally1", mp_cost: 6, effect: AttackBuff { amount: 2 }, target_type: AllAllies, ailment: None, element: None };
+pub const RALLY2: SpellEntry = SpellEntry { name: "Rally2", mp_cost: 10, effect: AttackBuff { amount: 4 }, target_type: AllAllies, ailment: None, element: None };
+// 味方単体DEF↑
+pub const GUARD1: SpellEntry = SpellEntry { name: "Guard1", mp_cost: 3, effect: DefenseBuff { amount: 4 }, target_type: SingleAlly, ailment: None, element: None };
+// 味方全体DEF↑
+pub const FORTIFY1: SpellEntry = SpellEntry { name: "Fortify1", mp_cost: 7, effect: DefenseBuff { amount: 3 }, target_type: AllAllies, ailment: None, element: None };
+// 味方単体SPD↑
+pub const HASTE1: SpellEntry = SpellEntry { name: "Haste1", mp_cost: 3, effect: SpeedBuff { amount: 5 }, target_type: SingleAlly, ailment: None, element: None };
+// 味方全体SPD↑
+pub const HASTEALL1: SpellEntry = SpellEntry { name: "Hasteall1", mp_cost: 8, effect: SpeedBuff { amount: 4 }, target_type: AllAllies, ailment: None, element: None };
+// 敵単体ATK↓
+pub const WEAKEN1: SpellEntry = SpellEntry { name: "Weaken1", mp_cost: 3, effect: AttackDebuff { amount: 3 }, target_type: SingleEnemy, ailment: None, element: None };
+// 敵全体ATK↓
+pub const WEAKENALL1: SpellEntry = SpellEntry { name: "Weakenall1", mp_cost: 7, effect: AttackDebuff { amount: 2 }, target_type: AllEnemies, ailment: None, element: None };
+// 敵単体DEF↓
+pub const BREAK1: SpellEntry = SpellEntry { name: "Break1", mp_cost: 3, effect: DefenseDebuff { amount: 4 }, target_type: SingleEnemy, ailment: None, element: None };
+// 敵全体DEF↓
+pub const BREAKALL1: SpellEntry = SpellEntry { name: "Breakall1", mp_cost: 7, effect: DefenseDebuff { amount: 3 }, target_type: AllEnemies, ailment: None, element: None };
 // 単体MP減少
-pub const DRAIN1: SpellEntry = SpellEntry { name: "Drain1", mp_cost: 4, effect: MpDrain { base_drain: 8 }, target_type: SingleEnemy, ailment: None };
-pub const DRAIN2: SpellEntry = SpellEntry { name: "Drain2", mp_cost: 8, effect: MpDrain { base_drain: 18 }, target_type: SingleEnemy, ailment: None };
+pub const DRAIN1: SpellEntry = SpellEntry { name: "Drain1", mp_cost: 4, effect: MpDrain { base_drain: 8 }, target_type: SingleEnemy, ailment: None, element: None };
+pub const DRAIN2: SpellEntry = SpellEntry { name: "Drain2", mp_cost: 8, effect: MpDrain { base_drain: 18 }, target_type: SingleEnemy, ailment: None, element: None };
 // 全体MP減少
-pub const SIPHON1: SpellEntry = SpellEntry { name: "Siphon1", mp_cost: 6, effect: MpDrain { base_drain: 5 }, target_type: AllEnemies, ailment: None };
-pub const SIPHON2: SpellEntry = SpellEntry { name: "Siphon2", mp_cost: 10, effect: MpDrain { base_drain: 12 }, target_type: AllEnemies, ailment: None };
+pub const SIPHON1: SpellEntry = SpellEntry { name: "Siphon1", mp_cost: 6, effect: MpDrain { base_drain: 5 }, target_type: AllEnemies, ailment: None, element: None };
+pub const SIPHON2: SpellEntry = SpellEntry { name: "Siphon2", mp_cost: 10, effect: MpDrain { base_drain: 12 }, target_type: AllEnemies, ailment: None, element: None };
 // 単体眠り
-pub const SLEEP1: SpellEntry = SpellEntry { name: "Sleep1", mp_cost: 4, effect: SpellEffect::Ailment { success_rate: 70 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Sleep) };
+pub const SLEEP1: SpellEntry = SpellEntry { name: "Sleep1", mp_cost: 4, effect: SpellEffect::Ailment { success_rate: 70 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Sleep), element: None };
 // 全体眠り
-pub const SLEEPALL1: SpellEntry = SpellEntry { name: "Sleepall1", mp_cost: 8, effect: SpellEffect::Ailment { success_rate: 50 }, target_type: AllEnemies, ailment: Some(spell::Ailment::Sleep) };
+pub const SLEEPALL1: SpellEntry = SpellEntry { name: "Sleepall1", mp_cost: 8, effect: SpellEffect::Ailment { success_rate: 50 }, target_type: AllEnemies, ailment: Some(spell::Ailment::Sleep), element: None };
 // 単体毒
-pub const POISON1: SpellEntry = SpellEntry { name: "Poison1", mp_cost: 3, effect: SpellEffect::Ailment { success_rate: 80 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Poison) };
+pub const POISON1: SpellEntry = SpellEntry { name: "Poison1", mp_cost: 3, effect: SpellEffect::Ailment { success_rate: 80 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Poison), element: None };
 // 全体毒
-pub const POISONALL1: SpellEntry = SpellEntry { name: "Poisonall1", mp_cost: 6, effect: SpellEffect::Ailment { success_rate: 60 }, target_type: AllEnemies, ailment: Some(spell::Ailment::Poison) };
+pub const POISONALL1: SpellEntry = SpellEntry { name: "Poisonall1", mp_cost: 6, effect: SpellEffect::Ailment { success_rate: 60 }, target_type: AllEnemies, ailment: Some(spell::Ailment::Poison), element: None };
+// 単体まひ
+pub const PARALYZE1: SpellEntry = SpellEntry { name: "Paralyze1", mp_cost: 5, effect: SpellEffect::Ailment { success_rate: 50 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Paralysis), element: None };
+// 全体沈黙
+pub const SILENCEALL1: SpellEntry = SpellEntry { name: "Silenceall1", mp_cost: 6, effect: SpellEffect::Ailment { success_rate: 60 }, target_type: AllEnemies, ailment: Some(spell::Ailment::Silence), element: None };
+// 単体混乱
+pub const CONFUSE1: SpellEntry = SpellEntry { name: "Confuse1", mp_cost: 5, effect: SpellEffect::Ailment { success_rate: 60 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Confusion), element: None };
+// 単体暗闇
+pub const BLIND1: SpellEntry = SpellEntry { name: "Blind1", mp_cost: 3, effect: SpellEffect::Ailment { success_rate: 75 }, target_type: SingleEnemy, ailment: Some(spell::Ailment::Blind), element: None };
+// 単体状態異常回復
+pub const CURE1: SpellEntry = SpellEntry { name: "Cure1", mp_cost: 4, effect: Cure { ailments: &spell::Ailment::ALL }, target_type: SingleAlly, ailment: None, element: None };
+// 全体状態異常回復
+pub const CUREALL1: SpellEntry = SpellEntry { name: "Cureall1", mp_cost: 10, effect: Cure { ailments: &spell::Ailment::ALL }, target_type: AllAllies, ailment: None, element: None };
+// 単体蘇生
+pub const REVIVE1: SpellEntry = SpellEntry { name: "Revive1", mp_cost: 10, effect: Revive { hp_percent: 50 }, target_type: SingleDeadAlly, ailment: None, element: None };
 
 pub static ALL_SPELLS: &[SpellEntry] = &[
     FIRE1, FIRE2, BLAZE1, BLAZE2,
+    ICE1, ICE2, BLIZZARD1, HOLY1, HOLYALL1,
     HEAL1, HEAL2, HEALALL1, HEALALL2,
     SHIELD1, SHIELD2, BARRIER1, BARRIER2,
     BOOST1, BOOST2, RALLY1, RALLY2,
+    GUARD1, FORTIFY1, HASTE1, HASTEALL1,
+    WEAKEN1, WEAKENALL1, BREAK1, BREAKALL1,
     DRAIN1, DRAIN2, SIPHON1, SIPHON2,
     SLEEP1, SLEEPALL1, POISON1, POISONALL1,
+    PARALYZE1, SILENCEALL1, CONFUSE1, BLIND1,
+    CURE1, CUREALL1,
+    REVIVE1,
 ];
 
 /// 全呪文リストを返す
@@ -1,12 +1,24 @@
+use serde::{Deserialize, Serialize};
+
 /// 呪文の効果（データ付き）
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpellEffect {
     Damage { base_damage: i32 },
     Heal { base_heal: i32 },
     AttackBuff { amount: i32 },
+    DefenseBuff { amount: i32 },
+    SpeedBuff { amount: i32 },
+    /// 相手の攻撃力を下げる
+    AttackDebuff { amount: i32 },
+    /// 相手の防御力を下げる
+    DefenseDebuff { amount: i32 },
     Block { amount: i32 },
     MpDrain { base_drain: i32 },
     Ailment { success_rate: i32 },
+    /// 戦闘不能の味方を最大HPのhp_percent%で復活させる
+    Revive { hp_percent: i32 },
+    /// 味方の状態異常を治す
+    Cure { ailments: &'static [Ailment] },
 }
 
 /// 1呪文のデータ
@@ -17,6 +29,8 @@ pub struct SpellEntry {
     pub effect: SpellEffect,
     pub target_type: SpellTarget,
     pub ailment: Option<Ailment>,
+    /// 攻撃呪文の属性（無属性ならNone）
+    pub element: Option<Element>,
 }
 
 impl SpellEntry {
@@ -29,25 +43,115 @@ impl SpellEntry {
     }
 }
 
+/// 呪文の属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Element {
+    Fire,
+    Ice,
+    Holy,
+}
+
+impl Element {
+    pub fn name(self) -> &'static str {
+        match self {
+            Element::Fire => "ほのお",
+            Element::Ice => "こおり",
+            Element::Holy => "ひかり",
+        }
+    }
+}
+
+/// 属性に対する相性
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Affinity {
+    #[default]
+    Normal,
+    /// 弱点: ダメージ増加
+    Weak,
+    /// 耐性: ダメージ半減
+    Resist,
+    /// 無効: ダメージ0
+    Immune,
+    /// 吸収: ダメージの分だけ回復する
+    Absorb,
+}
+
+/// 弱点を突いたときのダメージ倍率(%)
+pub const WEAK_DAMAGE_PERCENT: i32 = 150;
+/// 耐性があるときのダメージ倍率(%)
+pub const RESIST_DAMAGE_PERCENT: i32 = 50;
+
 pub const DEFENSE_DIVISOR: f32 = 4.0;
 pub const POISON_DAMAGE: i32 = 3;
+/// 暗闇状態で物理攻撃したときの命中率の倍率(%)
+pub const BLIND_HIT_PERCENT: i32 = 50;
 
 /// 状態異常の種類
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Ailment {
     /// 眠り: 行動不能、攻撃を受けると解除
     Sleep,
     /// 毒: ターン終了時に固定ダメージ
     Poison,
+    /// まひ: 行動不能
+    Paralysis,
+    /// 沈黙: 呪文を唱えられない
+    Silence,
+    /// 混乱: 攻撃の対象がでたらめになる
+    Confusion,
+    /// 暗闇: 物理攻撃が外れやすくなる
+    Blind,
 }
 
 impl Ailment {
+    pub const ALL: [Ailment; 6] = [
+        Ailment::Sleep,
+        Ailment::Poison,
+        Ailment::Paralysis,
+        Ailment::Silence,
+        Ailment::Confusion,
+        Ailment::Blind,
+    ];
+
     pub fn name(self) -> &'static str {
         match self {
             Ailment::Sleep => "ねむり",
             Ailment::Poison => "どく",
+            Ailment::Paralysis => "まひ",
+            Ailment::Silence => "ちんもく",
+            Ailment::Confusion => "こんらん",
+            Ailment::Blind => "くらやみ",
         }
     }
+
+    /// 持続ターン数（Noneなら治すまで続く）
+    pub fn duration(self) -> Option<u32> {
+        match self {
+            Ailment::Sleep => Some(4),
+            Ailment::Poison => None,
+            Ailment::Paralysis => Some(5),
+            Ailment::Silence => Some(4),
+            Ailment::Confusion => Some(3),
+            Ailment::Blind => Some(4),
+        }
+    }
+
+    /// ターン終了時に自然回復する確率(%)
+    pub fn recovery_percent(self) -> i32 {
+        match self {
+            Ailment::Sleep => 25,
+            Ailment::Poison => 0,
+            Ailment::Paralysis => 20,
+            Ailment::Silence => 15,
+            Ailment::Confusion => 30,
+            Ailment::Blind => 15,
+        }
+    }
+
+    /// 行動できなくなる状態異常か
+    pub fn prevents_action(self) -> bool {
+        matches!(self, Ailment::Sleep | Ailment::Paralysis)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +160,8 @@ pub enum SpellTarget {
     AllEnemies,
     SingleAlly,
     AllAllies,
+    /// 戦闘不能の味方単体
+    SingleDeadAlly,
 }
 
 /// 呪文ダメージ = (base_damage - defense/defense_divisor) × random_factor、最小1
@@ -65,19 +171,35 @@ pub fn spell_damage(base_damage: i32, defense: i32, defense_divisor: f32, random
     damage.max(1)
 }
 
+/// 属性の相性を呪文ダメージに適用する（吸収は負の値＝回復量を返す）
+pub fn elemental_damage(damage: i32, affinity: Affinity) -> i32 {
+    match affinity {
+        Affinity::Normal => damage,
+        Affinity::Weak => damage * WEAK_DAMAGE_PERCENT / 100,
+        Affinity::Resist => (damage * RESIST_DAMAGE_PERCENT / 100).max(1),
+        Affinity::Immune => 0,
+        Affinity::Absorb => -damage,
+    }
+}
+
 /// 回復量 = base_heal × random_factor、最小1
 pub fn heal_amount(base_heal: i32, random_factor: f32) -> i32 {
     let amount = (base_heal as f32 * random_factor).round() as i32;
     amount.max(1)
 }
 
+/// 復活時のHP = max_hp × hp_percent / 100、最小1
+pub fn revive_hp(max_hp: i32, hp_percent: i32) -> i32 {
+    (max_hp * hp_percent / 100).max(1)
+}
+
 /// MP減少量 = base_drain × random_factor、最小1
 pub fn mp_drain_amount(base_drain: i32, random_factor: f32) -> i32 {
     let amount = (base_drain as f32 * random_factor).round() as i32;
     amount.max(1)
 }
 
-/// 状態異常の成功判定
+/// 状態異常の成功判定（自然回復の判定にも使う）
 pub fn ailment_success(success_rate: i32, random_factor: f32) -> bool {
     random_factor * 100.0 < success_rate as f32
 }
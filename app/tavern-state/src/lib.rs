@@ -1,11 +1,12 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use std::collections::{HashMap, HashSet};
 
 use item_data::ItemKey;
 
 /// 居酒屋ヒントの種類
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TavernHintKind {
     Cave,
     Hokora,
@@ -14,14 +15,14 @@ pub enum TavernHintKind {
 }
 
 /// 居酒屋で聞いたヒントの既読管理リソース
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
 pub struct HeardTavernHints {
     /// key: 町座標, value: 聞いたヒント種類の集合
     pub heard: HashMap<(usize, usize), HashSet<TavernHintKind>>,
 }
 
 /// 居酒屋で受けた買い取り依頼を管理するリソース
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
 pub struct TavernBounties {
     /// 居酒屋で依頼を聞いた街 → 対象アイテム
     pub active: HashMap<(usize, usize), ItemKey>,
@@ -33,6 +33,10 @@ pub enum Structure {
     BossCave,
     Hokora,
     Ladder,
+    /// 洞窟の下り階段（下の階へ進む）
+    StairsDown,
+    /// カギのかかった扉（どうのカギを持っていれば通れる）
+    LockedDoor,
     WarpZone,
     Chest,
     ChestOpen,
@@ -48,7 +52,13 @@ impl Structure {
             Structure::BossCave => TileAction::EnterBossCave,
             Structure::Hokora => TileAction::EnterHokora,
             Structure::Ladder => TileAction::ExitCave,
-            Structure::None | Structure::WarpZone | Structure::Chest | Structure::ChestOpen => TileAction::None,
+            // 下り階段・扉は洞窟シーン内で処理するため、シーン遷移は起こさない
+            Structure::None
+            | Structure::StairsDown
+            | Structure::LockedDoor
+            | Structure::WarpZone
+            | Structure::Chest
+            | Structure::ChestOpen => TileAction::None,
         }
     }
 }
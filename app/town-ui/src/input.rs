@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use rand::prelude::SliceRandom;
 
 use input_ui::{is_cancel_just_pressed, is_confirm_just_pressed, is_down_just_pressed, is_up_just_pressed};
+use item::EquipSlot;
 use item_data::ItemKey;
 use party::{consume_item, has_item, talk_to_candidate, PartyMember, RecruitmentPath, TalkResult};
 use town::{buy_item, candidate_first_dialogue, candidate_join_dialogue, cave_hint_dialogue, companion_hint_dialogue, heal_party, hire_success_dialogue, hokora_hint_dialogue, sell_item, BuyResult, SellResult, INN_PRICE, TAVERN_PRICE};
@@ -10,9 +11,9 @@ use town::{tavern_bounty_item, bounty_offer_dialogue, bounty_has_item_dialogue,
 use scene_state::SceneState;
 use party_state::{CharacterParams, PartyState};
 use field_core::{ActiveMap, Player, TilePosition};
-use world_state::{ContinentMap, RecruitmentMap};
+use world_state::{ContinentMap, GameRng, RecruitmentMap};
 use tavern_state::{HeardTavernHints, TavernBounties, TavernHintKind};
-use hud_ui::menu_style;
+use hud_ui::{lineup, menu_style};
 
 use crate::scene::{build_town_commands, shop_goods, TownCommand, TownMenuPhase, TownResource};
 
@@ -30,6 +31,7 @@ pub fn town_input_system(
     continent_map: Option<Res<ContinentMap>>,
     mut tavern_bounties: ResMut<TavernBounties>,
     char_params: Res<CharacterParams>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     match town_res.phase.clone() {
         TownMenuPhase::MenuSelect => {
@@ -44,18 +46,8 @@ pub fn town_input_system(
             if is_confirm_just_pressed(&keyboard) {
                 match &town_res.commands[town_res.selected_item].clone() {
                     TownCommand::Inn => {
-                        // やどや → ゴールド消費してHP/MPを全回復
-                        if party_state.gold < INN_PRICE {
-                            town_res.phase = TownMenuPhase::ShowMessage {
-                                message: "おかねが たりない！".to_string(),
-                            };
-                        } else {
-                            party_state.gold -= INN_PRICE;
-                            heal_party(&mut party_state.members);
-                            town_res.phase = TownMenuPhase::ShowMessage {
-                                message: format!("{}G はらって ゆっくり やすんだ。\nHP と MP が かいふくした！", INN_PRICE),
-                            };
-                        }
+                        // やどや → とまる/いれかえ選択へ
+                        town_res.phase = TownMenuPhase::InnSelect { selected: 0 };
                     }
                     TownCommand::Shop => {
                         // よろず屋 → かう/うる選択へ
@@ -110,10 +102,10 @@ pub fn town_input_system(
                                                 TownMenuPhase::RecruitMessage { message: msg };
                                         }
                                         TalkResult::Recruited => {
-                                            party_state.members.push(PartyMember::from_kind(kind, &char_params));
+                                            let joined = party_state.add_member(PartyMember::from_kind(kind, &char_params));
                                             recruitment_map.town_to_candidate.remove(&town_pos);
                                             recruitment_map.candidate_second_town.remove(&candidate_idx);
-                                            let msg = candidate_join_dialogue(kind);
+                                            let msg = with_reserve_note(candidate_join_dialogue(kind), kind, joined);
                                             town_res.phase =
                                                 TownMenuPhase::RecruitMessage { message: msg };
                                         }
@@ -158,8 +150,7 @@ pub fn town_input_system(
                                     };
                                 } else {
                                     party_state.gold -= TAVERN_PRICE;
-                                    let mut rng = rand::thread_rng();
-                                    let &chosen = unheard.choose(&mut rng).unwrap();
+                                    let &chosen = unheard.choose(&mut game_rng.field).unwrap();
                                     heard_set.insert(chosen);
                                     let cf = continent_map.as_ref().and_then(|cm| {
                                         cm.map.get(pos.y)?.get(pos.x).copied().flatten().map(|cid| (cm.map.as_slice(), cid))
@@ -216,8 +207,8 @@ pub fn town_input_system(
                                     };
                                 } else {
                                     party_state.gold -= *cost;
-                                    // パーティに追加
-                                    party_state.members.push(PartyMember::from_kind(kind, &char_params));
+                                    // パーティに追加（満員ならひかえ）
+                                    let joined = party_state.add_member(PartyMember::from_kind(kind, &char_params));
                                     // candidates のステータスを Recruited に更新
                                     if let Ok(pos) = player_query.single() {
                                         let town_pos = (pos.x, pos.y);
@@ -235,7 +226,7 @@ pub fn town_input_system(
                                         let bounty_item = tavern_bounties.active.get(&town_pos).copied();
                                         town_res.commands = build_town_commands(bounty_item, &hire_candidates);
                                     }
-                                    let msg = hire_success_dialogue(kind);
+                                    let msg = with_reserve_note(hire_success_dialogue(kind), kind, joined);
                                     town_res.phase = TownMenuPhase::RecruitMessage { message: msg };
                                 }
                             }
@@ -262,6 +253,85 @@ pub fn town_input_system(
                 }
             }
         }
+        TownMenuPhase::InnSelect { selected } => {
+            // とまる/いれかえ選択
+            if is_up_just_pressed(&keyboard) || is_down_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::InnSelect { selected: 1 - selected };
+            }
+            if is_cancel_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::MenuSelect;
+                return;
+            }
+            if is_confirm_just_pressed(&keyboard) {
+                if selected == 0 {
+                    // とまる → ゴールド消費してひかえも含めHP/MPを全回復
+                    if party_state.gold < INN_PRICE {
+                        town_res.phase = TownMenuPhase::ShowMessage {
+                            message: "おかねが たりない！".to_string(),
+                        };
+                    } else {
+                        party_state.gold -= INN_PRICE;
+                        heal_party(&mut party_state.members);
+                        heal_party(&mut party_state.reserve);
+                        town_res.phase = TownMenuPhase::ShowMessage {
+                            message: format!("{}G はらって ゆっくり やすんだ。\nHP と MP が かいふくした！", INN_PRICE),
+                        };
+                    }
+                } else if party_state.reserve.is_empty() {
+                    town_res.phase = TownMenuPhase::ShowMessage {
+                        message: "ひかえの なかまが いない".to_string(),
+                    };
+                } else {
+                    town_res.phase = TownMenuPhase::LineupActiveSelect { selected: 0 };
+                }
+            }
+        }
+        TownMenuPhase::LineupActiveSelect { selected } => {
+            // 戦闘メンバー + 空き枠（満員でなければ）
+            let max_index = lineup::slot_count(&party_state) - 1;
+            if is_up_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::LineupActiveSelect {
+                    selected: if selected > 0 { selected - 1 } else { max_index },
+                };
+            }
+            if is_down_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::LineupActiveSelect {
+                    selected: if selected < max_index { selected + 1 } else { 0 },
+                };
+            }
+            if is_cancel_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::InnSelect { selected: 1 };
+                return;
+            }
+            if is_confirm_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::LineupReserveSelect { slot: selected, selected: 0 };
+            }
+        }
+        TownMenuPhase::LineupReserveSelect { slot, selected } => {
+            // ひかえ + 「ひかえに まわす」（空き枠以外）
+            let bench_option = usize::from(slot < party_state.members.len());
+            let max_index = party_state.reserve.len() + bench_option - 1;
+            if is_up_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::LineupReserveSelect {
+                    slot,
+                    selected: if selected > 0 { selected - 1 } else { max_index },
+                };
+            }
+            if is_down_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::LineupReserveSelect {
+                    slot,
+                    selected: if selected < max_index { selected + 1 } else { 0 },
+                };
+            }
+            if is_cancel_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::LineupActiveSelect { selected: slot };
+                return;
+            }
+            if is_confirm_just_pressed(&keyboard) {
+                let message = lineup::apply_choice(&mut party_state, slot, selected);
+                town_res.phase = TownMenuPhase::ShowMessage { message };
+            }
+        }
         TownMenuPhase::ShowMessage { .. } => {
             // メッセージ表示中は Enter または ESC でメニューに戻る
             if is_confirm_just_pressed(&keyboard) || is_cancel_just_pressed(&keyboard) {
@@ -269,15 +339,15 @@ pub fn town_input_system(
             }
         }
         TownMenuPhase::ShopModeSelect { selected } => {
-            // かう/うる選択
+            // かう/うる/そうび選択
             if is_up_just_pressed(&keyboard) {
                 town_res.phase = TownMenuPhase::ShopModeSelect {
-                    selected: if selected > 0 { selected - 1 } else { 1 },
+                    selected: if selected > 0 { selected - 1 } else { 2 },
                 };
             }
             if is_down_just_pressed(&keyboard) {
                 town_res.phase = TownMenuPhase::ShopModeSelect {
-                    selected: if selected < 1 { selected + 1 } else { 0 },
+                    selected: if selected < 2 { selected + 1 } else { 0 },
                 };
             }
             if is_cancel_just_pressed(&keyboard) {
@@ -289,10 +359,14 @@ pub fn town_input_system(
                         // かう → 商品選択へ
                         town_res.phase = TownMenuPhase::ShopSelect { selected: 0 };
                     }
-                    _ => {
+                    1 => {
                         // うる → 売却キャラ選択へ
                         town_res.phase = TownMenuPhase::SellCharacterSelect { selected: 0 };
                     }
+                    _ => {
+                        // そうび → 装備キャラ選択へ
+                        town_res.phase = TownMenuPhase::EquipCharacterSelect { selected: 0 };
+                    }
                 }
             }
         }
@@ -365,6 +439,66 @@ pub fn town_input_system(
                 selected,
             );
         }
+        TownMenuPhase::EquipCharacterSelect { selected } => {
+            let max_index = party_state.members.len().saturating_sub(1);
+            if is_up_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipCharacterSelect {
+                    selected: if selected > 0 { selected - 1 } else { max_index },
+                };
+            }
+            if is_down_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipCharacterSelect {
+                    selected: if selected < max_index { selected + 1 } else { 0 },
+                };
+            }
+            if is_cancel_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::ShopModeSelect { selected: 2 };
+                return;
+            }
+            if is_confirm_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipSlotSelect {
+                    member_index: selected,
+                    selected: 0,
+                };
+            }
+        }
+        TownMenuPhase::EquipSlotSelect { member_index, selected } => {
+            let max_index = EquipSlot::ALL.len() - 1;
+            if is_up_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipSlotSelect {
+                    member_index,
+                    selected: if selected > 0 { selected - 1 } else { max_index },
+                };
+            }
+            if is_down_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipSlotSelect {
+                    member_index,
+                    selected: if selected < max_index { selected + 1 } else { 0 },
+                };
+            }
+            if is_cancel_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipCharacterSelect { selected: member_index };
+                return;
+            }
+            if is_confirm_just_pressed(&keyboard) {
+                town_res.phase = TownMenuPhase::EquipItemSelect {
+                    member_index,
+                    slot: EquipSlot::ALL[selected],
+                    selected: 0,
+                };
+            }
+        }
+        TownMenuPhase::EquipItemSelect { member_index, slot, selected } => {
+            handle_equip_item_select(
+                &keyboard,
+                &mut town_res,
+                &mut party_state,
+                &char_params,
+                member_index,
+                slot,
+                selected,
+            );
+        }
         TownMenuPhase::RecruitMessage { .. } => {
             if is_confirm_just_pressed(&keyboard) {
                 town_res.phase = TownMenuPhase::MenuSelect;
@@ -396,7 +530,7 @@ pub fn town_input_system(
                     // はい → アイテム消費して雇用
                     let ps = &mut *party_state;
                     if consume_item(&mut ps.members, &mut ps.bag, item) {
-                        party_state.members.push(PartyMember::from_kind(kind, &char_params));
+                        let joined = party_state.add_member(PartyMember::from_kind(kind, &char_params));
                         if let Ok(pos) = player_query.single() {
                             let town_pos = (pos.x, pos.y);
                             if let Some(&candidate_idx) = recruitment_map.hire_available.get(&town_pos) {
@@ -412,7 +546,7 @@ pub fn town_input_system(
                             let bounty_item = tavern_bounties.active.get(&town_pos).copied();
                             town_res.commands = build_town_commands(bounty_item, &hire_candidates);
                         }
-                        let msg = hire_success_dialogue(kind);
+                        let msg = with_reserve_note(hire_success_dialogue(kind), kind, joined);
                         town_res.phase = TownMenuPhase::RecruitMessage { message: msg };
                     } else {
                         town_res.phase = TownMenuPhase::ShowMessage {
@@ -479,6 +613,15 @@ pub fn town_input_system(
     }
 }
 
+/// ひかえに回った仲間ならその旨をセリフに添える
+fn with_reserve_note(message: String, kind: party::PartyMemberKind, joined: bool) -> String {
+    if joined {
+        message
+    } else {
+        format!("{}\n（{}は ひかえに まわった）", message, kind.name())
+    }
+}
+
 /// 同じ大陸内の仲間候補がいる街を収集する
 fn collect_companion_towns(
     pos: &TilePosition,
@@ -631,13 +774,13 @@ fn handle_sell_character_select(
         } else {
             &party_state.members[selected].inventory
         };
-        let equipped_weapon = if is_bag { None } else { party_state.members[selected].equipment.weapon };
+        let equipment = if is_bag { None } else { Some(&party_state.members[selected].equipment) };
         let sellable: Vec<_> = inventory
             .owned_items()
             .into_iter()
             .filter(|i| {
                 if i.entry().sell_price == 0 { return false; }
-                if i.entry().is_weapon() && equipped_weapon == Some(*i) {
+                if equipment.is_some_and(|e| e.is_equipped(*i)) {
                     return inventory.count(*i) > 1;
                 }
                 true
@@ -669,13 +812,13 @@ fn handle_sell_item_select(
     } else {
         &party_state.members[member_index].inventory
     };
-    let equipped_weapon = if is_bag { None } else { party_state.members[member_index].equipment.weapon };
+    let equipment = if is_bag { None } else { Some(&party_state.members[member_index].equipment) };
     let sellable: Vec<_> = inventory
         .owned_items()
         .into_iter()
         .filter(|i| {
             if i.entry().sell_price == 0 { return false; }
-            if i.entry().is_weapon() && equipped_weapon == Some(*i) {
+            if equipment.is_some_and(|e| e.is_equipped(*i)) {
                 return inventory.count(*i) > 1;
             }
             true
@@ -709,12 +852,13 @@ fn handle_sell_item_select(
 
     if is_confirm_just_pressed(keyboard) {
         let item = sellable[selected];
+        let equipped = equipment.and_then(|e| e.in_slot_of(item));
         let inv = if is_bag {
             &mut party_state.bag
         } else {
             &mut party_state.members[member_index].inventory
         };
-        match sell_item(item, inv, equipped_weapon) {
+        match sell_item(item, inv, equipped) {
             SellResult::Success { earned_gold } => {
                 party_state.gold += earned_gold;
                 town_res.phase = TownMenuPhase::ShopMessage {
@@ -741,3 +885,59 @@ fn handle_sell_item_select(
         }
     }
 }
+
+fn handle_equip_item_select(
+    keyboard: &ButtonInput<KeyCode>,
+    town_res: &mut TownResource,
+    party_state: &mut PartyState,
+    char_params: &CharacterParams,
+    member_index: usize,
+    slot: EquipSlot,
+    selected: usize,
+) {
+    let slot_index = EquipSlot::ALL.iter().position(|&s| s == slot).unwrap_or(0);
+    let candidates = party_state.members[member_index].equip_candidates(slot);
+    // 候補 + 「はずす」（末尾）
+    let max_index = candidates.len();
+
+    if is_up_just_pressed(keyboard) {
+        town_res.phase = TownMenuPhase::EquipItemSelect {
+            member_index,
+            slot,
+            selected: if selected > 0 { selected - 1 } else { max_index },
+        };
+    }
+    if is_down_just_pressed(keyboard) {
+        town_res.phase = TownMenuPhase::EquipItemSelect {
+            member_index,
+            slot,
+            selected: if selected < max_index { selected + 1 } else { 0 },
+        };
+    }
+
+    if is_cancel_just_pressed(keyboard) {
+        town_res.phase = TownMenuPhase::EquipSlotSelect {
+            member_index,
+            selected: slot_index,
+        };
+        return;
+    }
+
+    if is_confirm_just_pressed(keyboard) {
+        let member = &mut party_state.members[member_index];
+        let name = member.kind.name();
+        let message = if let Some(&item) = candidates.get(selected) {
+            if !char_params.can_equip(member.kind, item) {
+                format!("{}は {}を そうびできない", name, item.name())
+            } else {
+                member.equip(item);
+                format!("{}は {}を そうびした！", name, item.name())
+            }
+        } else if let Some(item) = member.unequip(slot) {
+            format!("{}は {}を はずした", name, item.name())
+        } else {
+            format!("{}は {}を そうびしていない", name, slot.label())
+        };
+        town_res.phase = TownMenuPhase::ShopMessage { message };
+    }
+}
@@ -4,11 +4,12 @@ mod scene;
 use bevy::prelude::*;
 use scene_state::SceneState;
 use tavern_state::HeardTavernHints;
+use progress_state::LastVisitedTown;
 use hud_ui::menu_style;
 
 pub use input::town_input_system;
 pub use scene::{
-    build_town_commands, cleanup_town_scene, setup_town_scene, setup_town_scene_with_config,
+    build_town_commands, cleanup_town_scene, record_last_visited_town, setup_town_scene, setup_town_scene_with_config,
     town_extra_display_system, TownCommand, TownMenuPhase, TownResource, TownSceneConfig,
 };
 
@@ -17,7 +18,11 @@ pub struct TownPlugin;
 impl Plugin for TownPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<HeardTavernHints>()
-            .add_systems(OnEnter(SceneState::Town), (setup_town_scene, hud_ui::setup_hud))
+            .init_resource::<LastVisitedTown>()
+            .add_systems(
+                OnEnter(SceneState::Town),
+                (setup_town_scene, record_last_visited_town, hud_ui::setup_hud),
+            )
             .add_systems(
                 Update,
                 (
@@ -25,6 +30,7 @@ impl Plugin for TownPlugin {
                     menu_style::scene_menu_display_system::<TownResource>,
                     town_extra_display_system,
                     hud_ui::update_hud,
+                    hud_ui::rebuild_hud_on_lineup_change,
                 )
                     .chain()
                     .run_if(in_state(SceneState::Town)),
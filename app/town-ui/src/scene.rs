@@ -1,13 +1,16 @@
 use bevy::prelude::*;
 
 use item_data::ItemKey;
-use item::{BAG_CAPACITY, INVENTORY_CAPACITY};
+use item::{EquipSlot, BAG_CAPACITY, INVENTORY_CAPACITY};
 use party::PartyMemberKind;
-use party_state::PartyState;
+use party_state::{CharacterParams, PartyState};
+use town::INN_PRICE;
+use progress_state::LastVisitedTown;
 use world_state::RecruitmentMap;
 use tavern_state::TavernBounties;
 use field_core::{Player, TilePosition};
 use hud_ui::command_menu::{self, CommandMenu};
+use hud_ui::lineup;
 use hud_ui::menu_style::{self, SceneMenu, PANEL_BG, PANEL_BORDER, FONT_PATH};
 
 /// 町メニューのコマンド
@@ -55,6 +58,7 @@ pub fn build_town_commands(bounty: Option<ItemKey>, hire_candidates: &[PartyMemb
 pub fn shop_goods() -> Vec<ItemKey> {
     let mut goods: Vec<ItemKey> = item_data::SHOP_ITEMS.to_vec();
     goods.extend(item_data::SHOP_WEAPONS);
+    goods.extend(item_data::SHOP_ARMOR);
     goods
 }
 
@@ -83,12 +87,24 @@ pub enum TownMenuPhase {
     SellCharacterSelect { selected: usize },
     /// よろず屋 — 売却アイテム選択
     SellItemSelect { member_index: usize, selected: usize },
+    /// よろず屋 — 装備キャラ選択
+    EquipCharacterSelect { selected: usize },
+    /// よろず屋 — 装備スロット選択
+    EquipSlotSelect { member_index: usize, selected: usize },
+    /// よろず屋 — 装備品選択（末尾は「はずす」）
+    EquipItemSelect { member_index: usize, slot: EquipSlot, selected: usize },
     /// 買い取り依頼 — キャラクター選択
     BountyCharacterSelect { item: ItemKey, selected: usize },
     /// 買い取り依頼 — 結果メッセージ
     BountyMessage { message: String },
     /// アイテム交換雇用 — はい/いいえ確認
     ItemTradeConfirm { kind: PartyMemberKind, item: ItemKey, selected: usize },
+    /// やどや — とまる/いれかえ選択
+    InnSelect { selected: usize },
+    /// やどや — いれかえる戦闘メンバーの枠を選択（末尾は空き枠）
+    LineupActiveSelect { selected: usize },
+    /// やどや — 入れるひかえを選択（末尾は「ひかえに まわす」）
+    LineupReserveSelect { slot: usize, selected: usize },
 }
 
 /// 町の状態管理リソース
@@ -129,10 +145,16 @@ impl SceneMenu for TownResource {
                 | TownMenuPhase::SellItemSelect { .. }
                 | TownMenuPhase::ShopCharacterSelect { .. }
                 | TownMenuPhase::SellCharacterSelect { .. }
+                | TownMenuPhase::EquipCharacterSelect { .. }
+                | TownMenuPhase::EquipSlotSelect { .. }
+                | TownMenuPhase::EquipItemSelect { .. }
                 | TownMenuPhase::BountyCharacterSelect { .. }
                 | TownMenuPhase::ShopMessage { .. }
                 | TownMenuPhase::BountyMessage { .. }
                 | TownMenuPhase::ItemTradeConfirm { .. }
+                | TownMenuPhase::InnSelect { .. }
+                | TownMenuPhase::LineupActiveSelect { .. }
+                | TownMenuPhase::LineupReserveSelect { .. }
         )
     }
 
@@ -172,20 +194,20 @@ pub struct ShopCharacterMenuItem {
 }
 
 /// ショップパネル内のメニュー項目最大数（購入・売却で共用）
-const SHOP_PANEL_MAX_ITEMS: usize = 7;
+const SHOP_PANEL_MAX_ITEMS: usize = 12;
 
 /// メインメニューの最大項目数（基本4 + 買い取り依頼1 + 雇用1）
 const TOWN_MENU_MAX_ITEMS: usize = 6;
 
 fn format_goods_label(prefix: &str, item: ItemKey) -> String {
     let entry = item.entry();
-    if entry.is_weapon() {
+    if entry.is_equipment() {
         format!(
-            "{}{}  {}G  ATK+{}",
+            "{}{}  {}G  {}",
             prefix,
             entry.name,
             entry.price,
-            entry.attack_bonus,
+            entry.bonus_label(),
         )
     } else {
         format!("{}{}  {}G", prefix, entry.name, entry.price)
@@ -234,6 +256,16 @@ pub fn setup_town_scene_with_config(
     setup_town_scene_inner(&mut commands, &asset_server, &party_state, phase, selected, bounty_item, &hire_candidates);
 }
 
+/// 入った町を全滅時の帰還先として記録する
+pub fn record_last_visited_town(
+    mut last_town: ResMut<LastVisitedTown>,
+    player_query: Query<&TilePosition, With<Player>>,
+) {
+    if let Ok(pos) = player_query.single() {
+        last_town.position = Some((pos.x, pos.y));
+    }
+}
+
 /// 雇用可能なキャラを収集する
 fn collect_hire_candidates(
     town_pos: Option<(usize, usize)>,
@@ -421,6 +453,7 @@ pub fn cleanup_town_scene(
 pub fn town_extra_display_system(
     town_res: Res<TownResource>,
     party_state: Res<PartyState>,
+    char_params: Res<CharacterParams>,
     mut shop_root_query: Query<&mut Node, (With<ShopMenuRoot>, Without<ShopCharacterPanel>, Without<ShopMenuItem>)>,
     mut shop_item_query: Query<
         (&ShopMenuItem, &mut Text, &mut TextColor, &mut Node),
@@ -438,12 +471,18 @@ pub fn town_extra_display_system(
         TownMenuPhase::ShopSelect { .. }
             | TownMenuPhase::ShopModeSelect { .. }
             | TownMenuPhase::SellItemSelect { .. }
+            | TownMenuPhase::EquipSlotSelect { .. }
+            | TownMenuPhase::EquipItemSelect { .. }
             | TownMenuPhase::ItemTradeConfirm { .. }
+            | TownMenuPhase::InnSelect { .. }
+            | TownMenuPhase::LineupActiveSelect { .. }
+            | TownMenuPhase::LineupReserveSelect { .. }
     );
     let in_char_select = matches!(
         &town_res.phase,
         TownMenuPhase::ShopCharacterSelect { .. }
             | TownMenuPhase::SellCharacterSelect { .. }
+            | TownMenuPhase::EquipCharacterSelect { .. }
             | TownMenuPhase::BountyCharacterSelect { .. }
     );
 
@@ -495,7 +534,6 @@ pub fn town_extra_display_system(
             let prefix = if is_selected { "> " } else { "  " };
             if char_item.index < party_state.members.len() {
                 let member = &party_state.members[char_item.index];
-                let equipped_weapon = member.equipment.weapon;
                 let sellable_count: u32 = member
                     .inventory
                     .owned_items()
@@ -503,7 +541,7 @@ pub fn town_extra_display_system(
                     .filter(|i| i.entry().sell_price > 0)
                     .map(|i| {
                         let cnt = member.inventory.count(*i);
-                        if i.entry().is_weapon() && equipped_weapon == Some(*i) {
+                        if member.equipment.is_equipped(*i) {
                             return cnt.saturating_sub(1);
                         }
                         cnt
@@ -524,6 +562,27 @@ pub fn town_extra_display_system(
         }
     }
 
+    // キャラクター選択メニュー項目の更新（装備、ふくろは対象外）
+    if let TownMenuPhase::EquipCharacterSelect { selected } = &town_res.phase {
+        for (char_item, mut text, mut color) in &mut char_item_query {
+            let is_selected = char_item.index == *selected;
+            let prefix = if is_selected { "> " } else { "  " };
+            if let Some(member) = party_state.members.get(char_item.index) {
+                **text = format!(
+                    "{}{}  ATK:{} DEF:{} SPD:{}",
+                    prefix,
+                    member.kind.name(),
+                    member.effective_attack(),
+                    member.effective_defense(),
+                    member.effective_speed(),
+                );
+            } else {
+                **text = String::new();
+            }
+            *color = command_menu::menu_item_color(false);
+        }
+    }
+
     // ショップパネル項目の更新
     match &town_res.phase {
         TownMenuPhase::ShopSelect { selected } => {
@@ -542,7 +601,7 @@ pub fn town_extra_display_system(
             }
         }
         TownMenuPhase::ShopModeSelect { selected } => {
-            let labels = ["かう", "うる"];
+            let labels = ["かう", "うる", "そうび"];
             for (shop_item, mut text, mut color, mut node) in &mut shop_item_query {
                 if shop_item.index < labels.len() {
                     let is_selected = shop_item.index == *selected;
@@ -561,18 +620,18 @@ pub fn town_extra_display_system(
             selected,
         } => {
             let is_bag = *member_index == party_state.members.len();
-            let (inventory, equipped_weapon) = if is_bag {
+            let (inventory, equipment) = if is_bag {
                 (&party_state.bag, None)
             } else {
                 let member = &party_state.members[*member_index];
-                (&member.inventory, member.equipment.weapon)
+                (&member.inventory, Some(&member.equipment))
             };
             let sellable_items: Vec<_> = inventory
                 .owned_items()
                 .into_iter()
                 .filter(|i| {
                     if i.entry().sell_price == 0 { return false; }
-                    if i.entry().is_weapon() && equipped_weapon == Some(*i) {
+                    if equipment.is_some_and(|e| e.is_equipped(*i)) {
                         return inventory.count(*i) > 1;
                     }
                     true
@@ -584,8 +643,8 @@ pub fn town_extra_display_system(
                     let count = inventory.count(item);
                     let is_selected = shop_item.index == *selected;
                     let prefix = if is_selected { "> " } else { "  " };
-                    if item.entry().is_weapon() {
-                        let is_equipped = equipped_weapon == Some(item);
+                    if item.entry().is_equipment() {
+                        let is_equipped = equipment.is_some_and(|e| e.is_equipped(item));
                         let equip_mark = if is_equipped { "E " } else { "" };
                         **text = format!(
                             "{}{}{} x{}  {}G",
@@ -612,6 +671,49 @@ pub fn town_extra_display_system(
                 }
             }
         }
+        TownMenuPhase::EquipSlotSelect { member_index, selected } => {
+            let member = &party_state.members[*member_index];
+            let labels: Vec<String> = EquipSlot::ALL
+                .iter()
+                .map(|&slot| {
+                    let name = member.equipment.get(slot).map_or("なし", |item| item.name());
+                    format!("{}: {}", slot.label(), name)
+                })
+                .collect();
+            set_shop_labels(&mut shop_item_query, &labels, *selected, &[]);
+        }
+        TownMenuPhase::EquipItemSelect { member_index, slot, selected } => {
+            let member = &party_state.members[*member_index];
+            let candidates = member.equip_candidates(*slot);
+            // 装備できない候補はグレーアウト
+            let disabled: Vec<usize> = candidates
+                .iter()
+                .enumerate()
+                .filter(|&(_, &item)| !char_params.can_equip(member.kind, item))
+                .map(|(i, _)| i)
+                .collect();
+            let mut labels: Vec<String> = candidates
+                .into_iter()
+                .map(|item| {
+                    let equip_mark = if member.equipment.is_equipped(item) { "E " } else { "" };
+                    format!("{}{}  {}", equip_mark, item.name(), item.entry().bonus_label())
+                })
+                .collect();
+            labels.push("はずす".to_string());
+            set_shop_labels(&mut shop_item_query, &labels, *selected, &disabled);
+        }
+        TownMenuPhase::InnSelect { selected } => {
+            let labels = [format!("とまる  {}G", INN_PRICE), "いれかえ".to_string()];
+            set_shop_labels(&mut shop_item_query, &labels, *selected, &[]);
+        }
+        TownMenuPhase::LineupActiveSelect { selected } => {
+            let labels = lineup::slot_labels(&party_state);
+            set_shop_labels(&mut shop_item_query, &labels, *selected, &[]);
+        }
+        TownMenuPhase::LineupReserveSelect { slot, selected } => {
+            let (labels, disabled) = lineup::reserve_labels(&party_state, *slot);
+            set_shop_labels(&mut shop_item_query, &labels, *selected, &disabled);
+        }
         TownMenuPhase::ItemTradeConfirm { item, selected, .. } => {
             let labels = [
                 format!("{}を わたす → はい", item.name()),
@@ -638,3 +740,27 @@ pub fn town_extra_display_system(
         **text = format!("所持金: {}G", party_state.gold);
     }
 }
+
+/// ショップパネルにラベル一覧を表示し、余った項目を隠す（disabledはグレー表示）
+#[allow(clippy::type_complexity)]
+fn set_shop_labels(
+    shop_item_query: &mut Query<
+        (&ShopMenuItem, &mut Text, &mut TextColor, &mut Node),
+        (Without<ShopCharacterMenuItem>, Without<ShopGoldText>, Without<ShopMenuRoot>, Without<ShopCharacterPanel>),
+    >,
+    labels: &[String],
+    selected: usize,
+    disabled: &[usize],
+) {
+    for (shop_item, mut text, mut color, mut node) in shop_item_query.iter_mut() {
+        if shop_item.index < labels.len() {
+            let prefix = if shop_item.index == selected { "> " } else { "  " };
+            **text = format!("{}{}", prefix, labels[shop_item.index]);
+            *color = command_menu::menu_item_color(disabled.contains(&shop_item.index));
+            node.display = Display::Flex;
+        } else {
+            **text = String::new();
+            node.display = Display::None;
+        }
+    }
+}
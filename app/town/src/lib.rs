@@ -40,15 +40,15 @@ pub enum SellResult {
 
 /// アイテムを売却する
 ///
-/// `equipped_weapon` が `Some` かつ該当武器が1本のみの場合、売却不可。
-pub fn sell_item(item: ItemKey, inventory: &mut Inventory<ItemKey>, equipped_weapon: Option<ItemKey>) -> SellResult {
+/// `equipped` が `Some` かつ該当装備品が1つのみの場合、売却不可。
+pub fn sell_item(item: ItemKey, inventory: &mut Inventory<ItemKey>, equipped: Option<ItemKey>) -> SellResult {
     let sell_price = item.entry().sell_price;
     if sell_price == 0 {
         return SellResult::CannotSell;
     }
-    // 装備中の武器が1本のみの場合は売却不可
-    if item.entry().is_weapon()
-        && equipped_weapon == Some(item)
+    // 装備中の装備品が1つのみの場合は売却不可
+    if item.entry().is_equipment()
+        && equipped == Some(item)
         && inventory.count(item) <= 1
     {
         return SellResult::CannotSell;
@@ -64,8 +64,8 @@ pub fn sell_item(item: ItemKey, inventory: &mut Inventory<ItemKey>, equipped_wea
 /// パーティ全員のHP/MPを全回復する
 pub fn heal_party(party: &mut [PartyMember]) {
     for member in party.iter_mut() {
-        member.stats.hp = member.stats.max_hp;
-        member.stats.mp = member.stats.max_mp;
+        member.stats.hp = member.effective_max_hp();
+        member.stats.mp = member.effective_max_mp();
     }
 }
 
@@ -416,6 +416,7 @@ mod tests {
             stat_growth: party::StatGrowth { hp: 3, mp: 1, attack: 1, defense: 1, speed: 1 },
             recruit_method: party::RecruitmentPath::TavernBond,
             spell_learn_table: &[],
+            equip_rule: party::EquipRule::Any,
         })
     }
 
@@ -586,15 +586,15 @@ mod tests {
         let mut grid = create_test_grid(Terrain::Sea);
 
         // 大きな陸地ブロックの中に海の「湖」を作り、その中に島を置く
-        for y in 10..30 {
-            for x in 10..30 {
-                grid[y][x] = Terrain::Plains;
+        for row in &mut grid[10..30] {
+            for cell in &mut row[10..30] {
+                *cell = Terrain::Plains;
             }
         }
         // 陸地ブロック内部に海の湖
-        for y in 15..25 {
-            for x in 15..25 {
-                grid[y][x] = Terrain::Sea;
+        for row in &mut grid[15..25] {
+            for cell in &mut row[15..25] {
+                *cell = Terrain::Sea;
             }
         }
         // 湖の中に孤立島
@@ -610,9 +610,9 @@ mod tests {
         let mut grid = create_test_grid(Terrain::Sea);
         let mut structures = vec![vec![Structure::None; MAP_WIDTH]; MAP_HEIGHT];
         // スポーン大陸（十分な広さ: 30x30）
-        for y in 5..35 {
-            for x in 5..35 {
-                grid[y][x] = Terrain::Plains;
+        for row in &mut grid[5..35] {
+            for cell in &mut row[5..35] {
+                *cell = Terrain::Plains;
             }
         }
         let spawn_pos = (20, 20);
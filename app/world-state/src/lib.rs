@@ -1,9 +1,115 @@
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
 
+/// ワールド生成に使用したシード値（同じ値から同じワールドを再生成できる）
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldSeed(pub u64);
+
+/// 用途別の乱数ストリーム
+///
+/// 同じシードでも用途ごとに独立した乱数列を使うため、
+/// 例えば戦闘の回数が変わってもワールドや洞窟の形は変わらない。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    World,
+    Cave,
+    BossCave,
+    Encounter,
+    Battle,
+    /// 町・フィールドメニューなどその他の判定
+    Field,
+}
+
+impl RngStream {
+    fn id(self) -> u64 {
+        match self {
+            RngStream::World => 0,
+            RngStream::Cave => 1,
+            RngStream::BossCave => 2,
+            RngStream::Encounter => 3,
+            RngStream::Battle => 4,
+            RngStream::Field => 5,
+        }
+    }
+}
+
+impl WorldSeed {
+    /// ワールド生成用の乱数生成器を作る
+    pub fn world_rng(&self) -> ChaCha8Rng {
+        self.stream_rng(RngStream::World)
+    }
+
+    /// 指定した用途の乱数生成器を作る
+    pub fn stream_rng(&self, stream: RngStream) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.0);
+        rng.set_stream(stream.id());
+        rng
+    }
+
+    /// ワールドマップ上の場所に固有の乱数生成器を作る（同じ洞窟は常に同じ形にする）
+    pub fn location_rng(&self, stream: RngStream, x: usize, y: usize) -> ChaCha8Rng {
+        let location = (x as u64 * 10007 + y as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let mut rng = ChaCha8Rng::seed_from_u64(self.0 ^ location);
+        rng.set_stream(stream.id());
+        rng
+    }
+
+    /// 全滅して冒険をやり直すときの次のシード
+    pub fn next_run(&self) -> WorldSeed {
+        WorldSeed(self.0.wrapping_add(1))
+    }
+}
+
+/// プレイ中に消費する乱数ストリーム群（WorldSeedから作る）
+#[derive(Resource)]
+pub struct GameRng {
+    pub encounter: ChaCha8Rng,
+    pub battle: ChaCha8Rng,
+    pub field: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: WorldSeed) -> Self {
+        Self {
+            encounter: seed.stream_rng(RngStream::Encounter),
+            battle: seed.stream_rng(RngStream::Battle),
+            field: seed.stream_rng(RngStream::Field),
+        }
+    }
+
+    /// セーブデータに記録した消費位置から乱数ストリームを復元する
+    pub fn restore(seed: WorldSeed, positions: &GameRngPositions) -> Self {
+        let mut rng = Self::new(seed);
+        rng.encounter.set_word_pos(positions.encounter);
+        rng.battle.set_word_pos(positions.battle);
+        rng.field.set_word_pos(positions.field);
+        rng
+    }
+
+    /// 各ストリームをどこまで消費したか（セーブ用）
+    pub fn positions(&self) -> GameRngPositions {
+        GameRngPositions {
+            encounter: self.encounter.get_word_pos(),
+            battle: self.battle.get_word_pos(),
+            field: self.field.get_word_pos(),
+        }
+    }
+}
+
+/// GameRngの各ストリームの消費位置（既定値はシードから作り直した直後の状態）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GameRngPositions {
+    pub encounter: u128,
+    pub battle: u128,
+    pub field: u128,
+}
+
 /// 街座標 → その街にいる仲間候補のインデックスのマッピング
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
 pub struct RecruitmentMap {
     /// key: 街のタイル座標 (x, y), value: candidates配列のインデックス
     pub town_to_candidate: HashMap<(usize, usize), usize>,
@@ -14,7 +120,7 @@ pub struct RecruitmentMap {
 }
 
 /// 祠のワールドマップ座標を保持するリソース
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct HokoraPositions {
     pub positions: Vec<(usize, usize)>,
     pub warp_destinations: Vec<(usize, usize)>,
@@ -37,4 +143,53 @@ pub struct ContinentMap {
 pub struct EncounterZone {
     pub continent_id: u8,
     pub is_cave: bool,
+    /// 洞窟の階（0=1階、深いほど敵が強い）
+    pub floor: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    #[test]
+    fn same_seed_gives_same_stream() {
+        let mut a = WorldSeed(1234).stream_rng(RngStream::Battle);
+        let mut b = WorldSeed(1234).stream_rng(RngStream::Battle);
+        let a: Vec<u32> = (0..4).map(|_| a.next_u32()).collect();
+        let b: Vec<u32> = (0..4).map(|_| b.next_u32()).collect();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn streams_are_independent() {
+        let seed = WorldSeed(1234);
+        let encounter = seed.stream_rng(RngStream::Encounter).next_u64();
+        let battle = seed.stream_rng(RngStream::Battle).next_u64();
+        assert_ne!(encounter, battle);
+    }
+
+    #[test]
+    fn location_rng_depends_on_seed_and_position() {
+        let first = WorldSeed(1).location_rng(RngStream::Cave, 10, 20).next_u64();
+        let again = WorldSeed(1).location_rng(RngStream::Cave, 10, 20).next_u64();
+        let other_pos = WorldSeed(1).location_rng(RngStream::Cave, 20, 10).next_u64();
+        let other_seed = WorldSeed(2).location_rng(RngStream::Cave, 10, 20).next_u64();
+        assert_eq!(first, again);
+        assert_ne!(first, other_pos);
+        assert_ne!(first, other_seed);
+    }
+
+    #[test]
+    fn restored_game_rng_continues_where_it_left_off() {
+        let seed = WorldSeed(99);
+        let mut rng = GameRng::new(seed);
+        rng.encounter.next_u64();
+        rng.battle.next_u32();
+        rng.battle.next_u32();
+        let mut restored = GameRng::restore(seed, &rng.positions());
+        assert_eq!(restored.encounter.next_u64(), rng.encounter.next_u64());
+        assert_eq!(restored.battle.next_u64(), rng.battle.next_u64());
+        assert_eq!(restored.field.next_u64(), rng.field.next_u64());
+    }
 }
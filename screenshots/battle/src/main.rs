@@ -30,6 +30,7 @@ fn make_config(variant: &str) -> BattleSceneConfig {
     BattleSceneConfig {
         enemies: make_enemies(),
         initial_phase: Some(phase),
+        boss_battle: false,
     }
 }
 
@@ -15,9 +15,9 @@ fn setup_cave_direct(mut commands: Commands, asset_server: Res<AssetServer>) {
     // タイルテクスチャをロード
     let tile_textures = load_tile_textures(&asset_server);
 
-    // 固定シードで洞窟マップを生成
+    // 固定シードで洞窟マップを生成（下り階段のある1階）
     let mut rng = ChaCha8Rng::seed_from_u64(42);
-    let cave_data = generate_cave_map(&mut rng, &[]);
+    let cave_data = generate_cave_map(&mut rng, 0, 2, &[]);
     let (spawn_x, spawn_y) = cave_data.spawn_position;
 
     // 洞窟用ActiveMapを構築
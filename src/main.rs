@@ -1,17 +1,58 @@
 use std::time::Duration;
 
+use battle_ui::RecordBattles;
 use bevy::prelude::*;
 use bevy::window::{Window, WindowResolution};
 use bevy::winit::{UpdateMode, WinitSettings};
 use party_state::{CharacterParams, PartyState};
+use progress_state::DefeatMode;
 use scene_state::InField;
 use field_core::WINDOW_SIZE;
+use field_walk_ui::EncounterMode;
+use world_state::{GameRng, WorldSeed};
 
 fn main() {
-    let title = std::env::args()
-        .nth(1)
-        .map(|name| format!("Roguelike JRPG [{}]", name))
-        .unwrap_or_else(|| "Roguelike JRPG".to_string());
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // --return-to-town: 全滅時に冒険を終わらせず最後の町へ戻る
+    let defeat_mode = if args.iter().any(|arg| arg == "--return-to-town") {
+        DefeatMode::ReturnToTown
+    } else {
+        DefeatMode::RunEnds
+    };
+    // --record-battles: 戦闘終了ごとに直近の戦闘記録をbattle_record.ronへ書き出す
+    let record_battles = RecordBattles(args.iter().any(|arg| arg == "--record-battles"));
+    // --symbol-encounters: フィールドでもシンボルエンカウントにする（洞窟は既定でシンボル）
+    let encounter_mode = if args.iter().any(|arg| arg == "--symbol-encounters") {
+        EncounterMode::Symbol
+    } else {
+        EncounterMode::Random
+    };
+    // --seed N / --seed=N: ワールドシードを固定する（未指定ならランダム、不正な値ならエラー終了）
+    let seed_value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--seed" {
+            Some(args.get(i + 1).cloned().unwrap_or_default())
+        } else {
+            arg.strip_prefix("--seed=").map(str::to_string)
+        }
+    });
+    let world_seed = match seed_value {
+        Some(value) => match value.parse() {
+            Ok(seed) => WorldSeed(seed),
+            Err(_) => {
+                eprintln!("--seed には0以上の整数を指定してください: {value}");
+                std::process::exit(2);
+            }
+        },
+        None => WorldSeed(rand::random()),
+    };
+    let name = args.iter().enumerate().find(|(i, arg)| {
+        !arg.starts_with("--") && (*i == 0 || args[i - 1] != "--seed")
+    });
+    // 不具合報告で再現できるようシードをタイトルに表示する
+    let title = match name {
+        Some((_, name)) => format!("Roguelike JRPG [{}] seed {}", name, world_seed.0),
+        None => format!("Roguelike JRPG seed {}", world_seed.0),
+    };
 
     let char_table = party_data::character_param_table();
     let party_state = PartyState::new(&char_table);
@@ -36,6 +77,11 @@ fn main() {
         )
         .insert_resource(CharacterParams(char_table))
         .insert_resource(party_state)
+        .insert_resource(defeat_mode)
+        .insert_resource(record_battles)
+        .insert_resource(encounter_mode)
+        .insert_resource(world_seed)
+        .insert_resource(GameRng::new(world_seed))
         .add_plugins((
             scene_state::SceneStatePlugin,
             progress_state::ProgressStatePlugin,
@@ -47,6 +93,7 @@ fn main() {
             town_ui::TownPlugin,
             hokora_ui::HokoraPlugin,
             cave_ui::CavePlugin,
+            game_over_ui::GameOverPlugin,
         ))
         .add_systems(OnExit(InField), field_walk_ui::cleanup_player_movement)
         .run();
@@ -0,0 +1,401 @@
+/// 戦闘ロジックをUIなしで大量に回し、バランス確認用の統計を出力する
+///
+/// 使い方:
+///   cargo run -p battle-sim -- --party laios:5,marcille:5,falin:5 --area field:2 --battles 5000
+///   cargo run -p battle-sim -- --area cave:3 --seed 42 --csv
+///
+/// --area を省略すると全大陸のフィールドと洞窟を順に回す。
+use std::process::exit;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use battle::{BattleAction, BattleState, TargetId, TurnRandomFactors};
+use enemy::generate_enemy_group;
+use party::{available_spells, exp_to_next_level, CharacterParamTable, PartyMember, PartyMemberKind};
+use spell::{SpellEffect, SpellEntry, SpellTarget};
+
+/// 1戦闘の打ち切りターン数（超えたら敗北扱い）
+const MAX_TURNS: u32 = 100;
+
+/// 回復呪文を使い始めるHP割合(%)
+const HEAL_THRESHOLD_PERCENT: i32 = 50;
+
+const FIELD_CONTINENTS: u8 = 7;
+const CAVE_CONTINENTS: u8 = 6;
+
+/// エンカウント場所
+#[derive(Debug, Clone, Copy)]
+struct Area {
+    continent_id: u8,
+    is_cave: bool,
+}
+
+impl Area {
+    fn label(self) -> String {
+        let place = if self.is_cave { "cave" } else { "field" };
+        format!("{}:{}", place, self.continent_id)
+    }
+}
+
+struct Options {
+    party: Vec<(PartyMemberKind, u32)>,
+    areas: Vec<Area>,
+    battles: u32,
+    seed: u64,
+    csv: bool,
+}
+
+/// 1戦闘の結果
+struct BattleOutcome {
+    won: bool,
+    turns: u32,
+    hp_used: i32,
+    mp_used: i32,
+    exp: u32,
+}
+
+/// 場所ごとの集計
+#[derive(Default)]
+struct Summary {
+    battles: u32,
+    wins: u32,
+    turns: u64,
+    hp_used: i64,
+    mp_used: i64,
+    exp: u64,
+}
+
+impl Summary {
+    fn add(&mut self, outcome: &BattleOutcome) {
+        self.battles += 1;
+        self.wins += outcome.won as u32;
+        self.turns += outcome.turns as u64;
+        self.hp_used += outcome.hp_used as i64;
+        self.mp_used += outcome.mp_used as i64;
+        self.exp += outcome.exp as u64;
+    }
+
+    fn average(&self, total: f64) -> f64 {
+        total / self.battles.max(1) as f64
+    }
+
+    fn win_rate(&self) -> f64 {
+        self.average(self.wins as f64) * 100.0
+    }
+}
+
+fn main() {
+    let options = parse_args(std::env::args().skip(1).collect()).unwrap_or_else(|message| {
+        eprintln!("error: {}", message);
+        eprintln!("usage: battle-sim [--party kind:level,...] [--area field:N|cave:N]... [--battles N] [--seed N] [--csv]");
+        exit(1);
+    });
+
+    let table = party_data::character_param_table();
+    let party = build_party(&options.party, &table);
+
+    let summaries: Vec<(Area, Summary)> = options
+        .areas
+        .iter()
+        .enumerate()
+        .map(|(i, &area)| {
+            let mut area_rng = ChaCha8Rng::seed_from_u64(options.seed.wrapping_add(i as u64));
+            let mut summary = Summary::default();
+            for _ in 0..options.battles {
+                let mut rng = ChaCha8Rng::seed_from_u64(area_rng.next_u64());
+                summary.add(&simulate_battle(&party, area, &table, &mut rng));
+            }
+            (area, summary)
+        })
+        .collect();
+
+    if options.csv {
+        print_csv(&summaries);
+    } else {
+        print_table(&options, &summaries);
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        party: vec![
+            (PartyMemberKind::Laios, 1),
+            (PartyMemberKind::Marcille, 1),
+            (PartyMemberKind::Falin, 1),
+        ],
+        areas: Vec::new(),
+        battles: 1000,
+        seed: 0,
+        csv: false,
+    };
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--csv" => options.csv = true,
+            "--party" => options.party = parse_party(&next_value(&mut iter, &arg)?)?,
+            "--area" => options.areas.push(parse_area(&next_value(&mut iter, &arg)?)?),
+            "--battles" => {
+                options.battles = next_value(&mut iter, &arg)?
+                    .parse()
+                    .map_err(|_| "--battles には数値を指定してください".to_string())?;
+            }
+            "--seed" => {
+                options.seed = next_value(&mut iter, &arg)?
+                    .parse()
+                    .map_err(|_| "--seed には数値を指定してください".to_string())?;
+            }
+            _ => return Err(format!("不明な引数: {}", arg)),
+        }
+    }
+
+    if options.areas.is_empty() {
+        options.areas = (0..FIELD_CONTINENTS)
+            .map(|continent_id| Area { continent_id, is_cave: false })
+            .chain((0..CAVE_CONTINENTS).map(|continent_id| Area { continent_id, is_cave: true }))
+            .collect();
+    }
+    Ok(options)
+}
+
+fn next_value(iter: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    iter.next().ok_or_else(|| format!("{} の値がありません", flag))
+}
+
+/// "laios:5,marcille:3" 形式のパーティ指定を読む
+fn parse_party(value: &str) -> Result<Vec<(PartyMemberKind, u32)>, String> {
+    let party = value
+        .split(',')
+        .map(|spec| {
+            let (name, level) = spec.split_once(':').unwrap_or((spec, "1"));
+            let kind = party::all_kinds()
+                .iter()
+                .copied()
+                .find(|kind| format!("{:?}", kind).eq_ignore_ascii_case(name))
+                .ok_or_else(|| format!("不明なキャラクター: {}", name))?;
+            let level = level
+                .parse::<u32>()
+                .ok()
+                .filter(|&level| level >= 1)
+                .ok_or_else(|| format!("レベルが不正です: {}", spec))?;
+            Ok((kind, level))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if party.is_empty() {
+        return Err("パーティが空です".to_string());
+    }
+    Ok(party)
+}
+
+/// "field:2" / "cave:3" 形式の場所指定を読む
+fn parse_area(value: &str) -> Result<Area, String> {
+    let (place, continent) = value
+        .split_once(':')
+        .ok_or_else(|| format!("場所は field:N か cave:N で指定してください: {}", value))?;
+    let (is_cave, count) = match place {
+        "field" => (false, FIELD_CONTINENTS),
+        "cave" => (true, CAVE_CONTINENTS),
+        _ => return Err(format!("不明な場所: {}", place)),
+    };
+    let continent_id = continent
+        .parse::<u8>()
+        .ok()
+        .filter(|&id| id < count)
+        .ok_or_else(|| format!("大陸IDが不正です: {}", value))?;
+    Ok(Area { continent_id, is_cave })
+}
+
+/// 指定レベルまで経験値を与えたパーティを作る
+fn build_party(spec: &[(PartyMemberKind, u32)], table: &CharacterParamTable) -> Vec<PartyMember> {
+    spec.iter()
+        .map(|&(kind, level)| {
+            let mut member = PartyMember::from_kind(kind, table);
+            if level > 1 {
+                member.gain_exp(exp_to_next_level(level - 1), table);
+            }
+            member
+        })
+        .collect()
+}
+
+/// 1戦闘をシミュレートする（乱数の引き方は戦闘画面と同じ）
+fn simulate_battle(
+    party: &[PartyMember],
+    area: Area,
+    table: &CharacterParamTable,
+    rng: &mut ChaCha8Rng,
+) -> BattleOutcome {
+    let enemies = generate_enemy_group(
+        area.continent_id,
+        area.is_cave,
+        rng.gen_range(0.0..1.0),
+        rng.gen_range(0.0..1.0),
+    );
+    let mut state = BattleState::new(party.to_vec(), enemies);
+
+    let mut turns = 0;
+    while !state.is_over() && turns < MAX_TURNS {
+        turns += 1;
+        let commands = choose_commands(&state, table);
+        let enemy_count = state.enemies.len();
+        let total_actors = state.party.len() + enemy_count;
+        let random_factors = TurnRandomFactors {
+            damage_randoms: (0..total_actors).map(|_| rng.gen_range(0.8..1.2)).collect(),
+            flee_random: rng.gen_range(0.0..1.0),
+            spell_randoms: (0..enemy_count).map(|_| rng.gen_range(0.0..1.0)).collect(),
+            target_randoms: (0..enemy_count).map(|_| rng.gen_range(0.0..1.0)).collect(),
+            hit_randoms: (0..total_actors).map(|_| rng.gen_range(0.0..1.0)).collect(),
+            critical_randoms: (0..total_actors).map(|_| rng.gen_range(0.0..1.0)).collect(),
+            confusion_randoms: (0..total_actors).map(|_| rng.gen_range(0.0..1.0)).collect(),
+            recovery_randoms: (0..total_actors).map(|_| rng.gen_range(0.0..1.0)).collect(),
+            speed_randoms: (0..total_actors).map(|_| rng.gen_range(0.0..1.0)).collect(),
+        };
+        state.execute_turn(&commands, &random_factors);
+    }
+
+    let won = state.is_victory();
+    BattleOutcome {
+        won,
+        turns,
+        hp_used: party
+            .iter()
+            .zip(&state.party)
+            .map(|(before, after)| (before.stats.hp - after.stats.hp).max(0))
+            .sum(),
+        mp_used: party
+            .iter()
+            .zip(&state.party)
+            .map(|(before, after)| (before.stats.mp - after.stats.mp).max(0))
+            .sum(),
+        exp: if won { state.total_exp_reward() } else { 0 },
+    }
+}
+
+/// 決め打ちの作戦でパーティ全員のコマンドを決める
+///
+/// 1. 倒れた仲間がいれば蘇生
+/// 2. HPが減った仲間がいれば回復（複数なら全体回復を優先）
+/// 3. 敵が複数なら全体攻撃呪文
+/// 4. それ以外は一番HPの低い敵を攻撃（攻撃呪文があればそれを使う）
+fn choose_commands(state: &BattleState, table: &CharacterParamTable) -> Vec<BattleAction> {
+    let alive_enemies = state.alive_enemy_indices();
+    let weakest_enemy = alive_enemies
+        .iter()
+        .copied()
+        .min_by_key(|&ei| state.enemies[ei].stats.hp)
+        .unwrap_or(0);
+    let attack = BattleAction::Attack { target: TargetId::Enemy(weakest_enemy) };
+
+    // 同じターンに同じ対象へ回復・蘇生を重ねない
+    let mut handled_allies: Vec<usize> = Vec::new();
+
+    state
+        .party
+        .iter()
+        .map(|member| {
+            if !member.stats.is_alive() {
+                return attack;
+            }
+            let spells: Vec<SpellEntry> = available_spells(member.kind, member.level, table)
+                .into_iter()
+                .filter(|spell| spell.mp_cost <= member.stats.mp)
+                .collect();
+
+            let fallen = state
+                .dead_party_indices()
+                .into_iter()
+                .find(|pi| !handled_allies.contains(pi));
+            if let (Some(pi), Some(spell)) = (fallen, find_spell(&spells, |s| matches!(s.effect, SpellEffect::Revive { .. })))
+            {
+                handled_allies.push(pi);
+                return BattleAction::Spell { spell, target: TargetId::Party(pi) };
+            }
+
+            let hurt: Vec<usize> = state
+                .alive_party_indices()
+                .into_iter()
+                .filter(|&pi| {
+                    let ally = &state.party[pi];
+                    ally.stats.hp * 100 < ally.effective_max_hp() * HEAL_THRESHOLD_PERCENT
+                        && !handled_allies.contains(&pi)
+                })
+                .collect();
+            let heal = |target_type: SpellTarget| {
+                find_spell(&spells, |s| matches!(s.effect, SpellEffect::Heal { .. }) && s.target_type == target_type)
+            };
+            if hurt.len() >= 2
+                && let Some(spell) = heal(SpellTarget::AllAllies)
+            {
+                handled_allies.extend(&hurt);
+                return BattleAction::Spell { spell, target: TargetId::Party(hurt[0]) };
+            }
+            if let Some(&pi) = hurt.iter().min_by_key(|&&pi| state.party[pi].stats.hp)
+                && let Some(spell) = heal(SpellTarget::SingleAlly)
+            {
+                handled_allies.push(pi);
+                return BattleAction::Spell { spell, target: TargetId::Party(pi) };
+            }
+
+            let damage = |target_type: SpellTarget| {
+                find_spell(&spells, |s| matches!(s.effect, SpellEffect::Damage { .. }) && s.target_type == target_type)
+            };
+            if alive_enemies.len() >= 2
+                && let Some(spell) = damage(SpellTarget::AllEnemies)
+            {
+                return BattleAction::Spell { spell, target: TargetId::Enemy(weakest_enemy) };
+            }
+            if let Some(spell) = damage(SpellTarget::SingleEnemy) {
+                return BattleAction::Spell { spell, target: TargetId::Enemy(weakest_enemy) };
+            }
+            attack
+        })
+        .collect()
+}
+
+/// 条件に合う呪文のうちMP消費が最も大きい（＝強い）ものを選ぶ
+fn find_spell(spells: &[SpellEntry], pred: impl Fn(&SpellEntry) -> bool) -> Option<SpellEntry> {
+    spells.iter().filter(|s| pred(s)).max_by_key(|s| s.mp_cost).copied()
+}
+
+fn print_table(options: &Options, summaries: &[(Area, Summary)]) {
+    let party: Vec<String> = options
+        .party
+        .iter()
+        .map(|(kind, level)| format!("{}Lv{}", kind.name(), level))
+        .collect();
+    println!("party: {}  battles/area: {}  seed: {}", party.join(" "), options.battles, options.seed);
+    println!();
+    println!(
+        "{:<8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "area", "battles", "win%", "turns", "hp_used", "mp_used", "exp"
+    );
+    for (area, s) in summaries {
+        println!(
+            "{:<8} {:>8} {:>8.1} {:>8.2} {:>8.1} {:>8.1} {:>8.1}",
+            area.label(),
+            s.battles,
+            s.win_rate(),
+            s.average(s.turns as f64),
+            s.average(s.hp_used as f64),
+            s.average(s.mp_used as f64),
+            s.average(s.exp as f64),
+        );
+    }
+}
+
+fn print_csv(summaries: &[(Area, Summary)]) {
+    println!("area,battles,win_rate,avg_turns,avg_hp_used,avg_mp_used,avg_exp");
+    for (area, s) in summaries {
+        println!(
+            "{},{},{:.3},{:.3},{:.3},{:.3},{:.3}",
+            area.label(),
+            s.battles,
+            s.win_rate(),
+            s.average(s.turns as f64),
+            s.average(s.hp_used as f64),
+            s.average(s.mp_used as f64),
+            s.average(s.exp as f64),
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use image::Rgba;
+use std::path::Path;
+
+use crate::generators::common::{new_image, save_image, TILE_SIZE};
+
+pub fn generate_locked_door(output_dir: &Path) {
+    let mut img = new_image();
+
+    let frame = Rgba([90, 80, 70, 255]);
+    let wood = Rgba([120, 75, 40, 255]);
+    let wood_dark = Rgba([85, 50, 25, 255]);
+    let copper = Rgba([200, 120, 60, 255]);
+    let keyhole = Rgba([20, 15, 10, 255]);
+
+    for y in 0..TILE_SIZE {
+        for x in 0..TILE_SIZE {
+            img.put_pixel(x, y, frame);
+        }
+    }
+
+    // 板張りの扉
+    for y in 1..TILE_SIZE {
+        for x in 2..14 {
+            let color = if x % 4 == 1 { wood_dark } else { wood };
+            img.put_pixel(x, y, color);
+        }
+    }
+
+    // 銅の帯
+    for &by in &[4u32, 11] {
+        for x in 2..14 {
+            img.put_pixel(x, by, copper);
+        }
+    }
+
+    // 錠前と鍵穴
+    for y in 6..10 {
+        for x in 6..10 {
+            img.put_pixel(x, y, copper);
+        }
+    }
+    img.put_pixel(7, 7, keyhole);
+    img.put_pixel(8, 7, keyhole);
+    img.put_pixel(7, 8, keyhole);
+
+    save_image(&img, output_dir, "locked_door.png");
+}
@@ -4,6 +4,8 @@ mod cave_floor;
 mod cave_wall;
 mod chest;
 mod ladder;
+mod locked_door;
+mod stairs_down;
 mod warp_zone;
 
 pub use boss_cave_floor::generate_boss_cave_floor;
@@ -13,4 +15,6 @@ pub use cave_wall::generate_cave_wall;
 pub use chest::generate_chest;
 pub use chest::generate_chest_open;
 pub use ladder::generate_ladder;
+pub use locked_door::generate_locked_door;
+pub use stairs_down::generate_stairs_down;
 pub use warp_zone::generate_warp_zone;
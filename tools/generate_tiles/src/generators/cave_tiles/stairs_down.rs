@@ -0,0 +1,36 @@
+use image::Rgba;
+use std::path::Path;
+
+use crate::generators::common::{new_image, save_image, TILE_SIZE};
+
+pub fn generate_stairs_down(output_dir: &Path) {
+    let mut img = new_image();
+
+    let floor_base = Rgba([80, 75, 65, 255]);
+    let stone_light = Rgba([170, 165, 150, 255]);
+    let stone_dark = Rgba([110, 105, 95, 255]);
+    let hole_dark = Rgba([20, 18, 15, 255]);
+
+    for y in 0..TILE_SIZE {
+        for x in 0..TILE_SIZE {
+            img.put_pixel(x, y, floor_base);
+        }
+    }
+
+    // 奥に向かって暗く細くなる段
+    for y in 2..14 {
+        for x in 2..14 {
+            img.put_pixel(x, y, hole_dark);
+        }
+    }
+
+    let steps: [(u32, u32, u32); 4] = [(11, 2, 14), (8, 3, 13), (5, 4, 12), (2, 5, 11)];
+    for &(sy, left, right) in &steps {
+        for x in left..right {
+            img.put_pixel(x, sy, stone_light);
+            img.put_pixel(x, sy + 1, stone_dark);
+        }
+    }
+
+    save_image(&img, output_dir, "stairs_down.png");
+}
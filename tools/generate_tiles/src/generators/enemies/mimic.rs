@@ -0,0 +1,64 @@
+use image::Rgba;
+use std::path::Path;
+
+use crate::generators::common::{new_image, save_image};
+
+pub fn generate_mimic(output_dir: &Path) {
+    let mut img = new_image();
+
+    let wood_dark = Rgba([90, 55, 25, 255]);
+    let wood = Rgba([140, 90, 40, 255]);
+    let gold = Rgba([220, 180, 60, 255]);
+    let mouth = Rgba([60, 10, 20, 255]);
+    let tongue = Rgba([200, 60, 90, 255]);
+    let tooth = Rgba([245, 240, 225, 255]);
+    let eye = Rgba([255, 230, 60, 255]);
+
+    // 開いたふた
+    for y in 1..=4 {
+        for x in 2..=13 {
+            let color = if y == 1 || x == 2 || x == 13 { wood_dark } else { wood };
+            img.put_pixel(x, y, color);
+        }
+    }
+    for x in 2..=13 {
+        img.put_pixel(x, 3, gold);
+    }
+    // ふたの裏の目
+    img.put_pixel(5, 2, eye);
+    img.put_pixel(10, 2, eye);
+
+    // 口の中
+    for y in 5..=9 {
+        for x in 3..=12 {
+            img.put_pixel(x, y, mouth);
+        }
+    }
+    for x in 5..=10 {
+        img.put_pixel(x, 8, tongue);
+        img.put_pixel(x, 9, tongue);
+    }
+    // 上下の牙
+    for x in (3..=12).step_by(2) {
+        img.put_pixel(x, 5, tooth);
+        img.put_pixel(x, 10, tooth);
+    }
+
+    // 箱の本体
+    for y in 10..=14 {
+        for x in 2..=13 {
+            let color = if y == 14 || x == 2 || x == 13 { wood_dark } else { wood };
+            img.put_pixel(x, y, color);
+        }
+    }
+    for x in 2..=13 {
+        img.put_pixel(x, 12, gold);
+    }
+    for x in (3..=12).step_by(2) {
+        img.put_pixel(x, 10, tooth);
+    }
+    img.put_pixel(7, 12, wood_dark);
+    img.put_pixel(8, 12, wood_dark);
+
+    save_image(&img, output_dir, "mimic.png");
+}
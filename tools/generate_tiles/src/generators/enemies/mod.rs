@@ -6,6 +6,7 @@ mod ghost;
 mod goblin;
 mod golem;
 mod lizardman;
+mod mimic;
 mod scorpion;
 mod skeleton;
 mod slime;
@@ -20,6 +21,7 @@ pub use ghost::generate_ghost;
 pub use goblin::generate_goblin;
 pub use golem::generate_golem;
 pub use lizardman::generate_lizardman;
+pub use mimic::generate_mimic;
 pub use scorpion::generate_scorpion;
 pub use skeleton::generate_skeleton;
 pub use slime::generate_slime;
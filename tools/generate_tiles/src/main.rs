@@ -34,6 +34,8 @@ fn main() {
     generate_boss_cave_floor(tiles_dir);
     generate_warp_zone(tiles_dir);
     generate_ladder(tiles_dir);
+    generate_stairs_down(tiles_dir);
+    generate_locked_door(tiles_dir);
     generate_chest(tiles_dir);
     generate_chest_open(tiles_dir);
 
@@ -54,6 +56,7 @@ fn main() {
     generate_demon(enemies_dir);
     generate_dragon(enemies_dir);
     generate_wraith(enemies_dir);
+    generate_mimic(enemies_dir);
 
     println!("Assets generated in assets/");
 }